@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::{DataType, Value};
 
+use super::function::ScalarFunction;
+use super::query_graph::QueryGraph;
+
 /// Bound expression after semantic analysis.
 #[derive(Debug, Clone)]
 pub enum BoundExpression {
@@ -63,6 +66,37 @@ pub enum BoundExpression {
         negated: bool,
         data_type: DataType, // Always Bool
     },
+
+    /// IN / NOT IN list membership.
+    InList {
+        operand: Box<BoundExpression>,
+        list: Box<BoundExpression>,
+        negated: bool,
+        data_type: DataType, // Always Bool
+    },
+
+    /// CASE: the result of the first branch whose condition holds. A simple
+    /// CASE is bound as equality conditions on its operand.
+    Case {
+        branches: Vec<(BoundExpression, BoundExpression)>, // (condition, result)
+        default: Option<Box<BoundExpression>>,
+        data_type: DataType,
+    },
+
+    /// Scalar function call.
+    Function {
+        function: ScalarFunction,
+        args: Vec<BoundExpression>,
+        data_type: DataType,
+    },
+
+    /// EXISTS subquery: whether the pattern has a match satisfying the
+    /// predicate, correlated through the variables it shares with the query.
+    Exists {
+        query_graph: Box<QueryGraph>,
+        predicate: Option<Box<BoundExpression>>,
+        data_type: DataType, // Always Bool
+    },
 }
 
 impl BoundExpression {
@@ -77,7 +111,11 @@ impl BoundExpression {
             | BoundExpression::Logical { data_type, .. }
             | BoundExpression::Arithmetic { data_type, .. }
             | BoundExpression::Aggregate { data_type, .. }
-            | BoundExpression::IsNull { data_type, .. } => *data_type,
+            | BoundExpression::IsNull { data_type, .. }
+            | BoundExpression::InList { data_type, .. }
+            | BoundExpression::Case { data_type, .. }
+            | BoundExpression::Function { data_type, .. }
+            | BoundExpression::Exists { data_type, .. } => *data_type,
         }
     }
 
@@ -109,6 +147,17 @@ impl BoundExpression {
         }
     }
 
+    /// Creates an IN (or, when `negated`, NOT IN) list membership test.
+    #[must_use]
+    pub fn in_list(operand: BoundExpression, list: BoundExpression, negated: bool) -> Self {
+        BoundExpression::InList {
+            operand: Box::new(operand),
+            list: Box::new(list),
+            negated,
+            data_type: DataType::Bool,
+        }
+    }
+
     /// Creates a logical AND expression.
     #[must_use]
     pub fn and(operands: Vec<BoundExpression>) -> Self {
@@ -182,6 +231,14 @@ pub enum ComparisonOp {
     Gt,
     /// Greater than or equal (>=).
     Gte,
+    /// String prefix test (STARTS WITH).
+    StartsWith,
+    /// String suffix test (ENDS WITH).
+    EndsWith,
+    /// Substring test (CONTAINS).
+    Contains,
+    /// Full-string regular expression match (=~).
+    RegexMatch,
 }
 
 impl ComparisonOp {
@@ -195,8 +252,24 @@ impl ComparisonOp {
             ComparisonOp::Lte => "<=",
             ComparisonOp::Gt => ">",
             ComparisonOp::Gte => ">=",
+            ComparisonOp::StartsWith => "STARTS WITH",
+            ComparisonOp::EndsWith => "ENDS WITH",
+            ComparisonOp::Contains => "CONTAINS",
+            ComparisonOp::RegexMatch => "=~",
         }
     }
+
+    /// Returns true for the operators that only apply to strings.
+    #[must_use]
+    pub fn is_string_predicate(&self) -> bool {
+        matches!(
+            self,
+            ComparisonOp::StartsWith
+                | ComparisonOp::EndsWith
+                | ComparisonOp::Contains
+                | ComparisonOp::RegexMatch
+        )
+    }
 }
 
 /// Logical operators.
@@ -239,6 +312,11 @@ pub enum AggregateFunction {
     Avg,
     Min,
     Max,
+    Collect,
+    StDev,
+    StDevP,
+    PercentileCont,
+    PercentileDisc,
 }
 
 impl AggregateFunction {
@@ -251,6 +329,11 @@ impl AggregateFunction {
             AggregateFunction::Avg => "AVG",
             AggregateFunction::Min => "MIN",
             AggregateFunction::Max => "MAX",
+            AggregateFunction::Collect => "COLLECT",
+            AggregateFunction::StDev => "STDEV",
+            AggregateFunction::StDevP => "STDEVP",
+            AggregateFunction::PercentileCont => "PERCENTILECONT",
+            AggregateFunction::PercentileDisc => "PERCENTILEDISC",
         }
     }
 
@@ -259,10 +342,15 @@ impl AggregateFunction {
     pub fn output_type(&self, input_type: Option<DataType>) -> DataType {
         match self {
             AggregateFunction::Count => DataType::Int64,
-            AggregateFunction::Avg => DataType::Float64,
-            AggregateFunction::Sum | AggregateFunction::Min | AggregateFunction::Max => {
-                input_type.unwrap_or(DataType::Int64)
-            }
+            AggregateFunction::Avg
+            | AggregateFunction::StDev
+            | AggregateFunction::StDevP
+            | AggregateFunction::PercentileCont => DataType::Float64,
+            AggregateFunction::Collect => DataType::List,
+            AggregateFunction::Sum
+            | AggregateFunction::Min
+            | AggregateFunction::Max
+            | AggregateFunction::PercentileDisc => input_type.unwrap_or(DataType::Int64),
         }
     }
 }
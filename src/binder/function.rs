@@ -0,0 +1,139 @@
+//! Registry of built-in scalar functions.
+
+use serde::{Deserialize, Serialize};
+
+/// Built-in scalar functions, callable in any value expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScalarFunction {
+    /// `lower(string)`.
+    Lower,
+    /// `upper(string)`.
+    Upper,
+    /// `trim(string)`: strips leading and trailing whitespace.
+    Trim,
+    /// `substring(string, start [, length])`, with a 0-based `start`.
+    Substring,
+    /// `size(string | list)`: number of characters or elements.
+    Size,
+    /// `replace(string, search, replacement)`.
+    Replace,
+    /// `split(string, delimiter)`: list of the parts.
+    Split,
+    /// `abs(number)`.
+    Abs,
+    /// `round(number [, precision])`: rounds half away from zero.
+    Round,
+    /// `floor(number)`.
+    Floor,
+    /// `ceil(number)`.
+    Ceil,
+    /// `sqrt(number)`.
+    Sqrt,
+    /// `coalesce(value, ...)`: first non-NULL argument.
+    Coalesce,
+    /// `toInteger(value)`: NULL for a string that is not a number.
+    ToInteger,
+    /// `toFloat(value)`: NULL for a string that is not a number.
+    ToFloat,
+    /// `toString(value)`.
+    ToString,
+    /// `toBoolean(value)`: NULL for a string other than `true` or `false`.
+    ToBoolean,
+    /// `date(value)`: converts a string or timestamp to a DATE.
+    Date,
+    /// `timestamp(value)`: converts a string or date to a TIMESTAMP.
+    Timestamp,
+    /// `year(date | timestamp)`.
+    Year,
+    /// `month(date | timestamp)`: 1 to 12.
+    Month,
+    /// `day(date | timestamp)`: day of the month.
+    Day,
+}
+
+/// Every registered function, looked up by name.
+const REGISTRY: &[ScalarFunction] = &[
+    ScalarFunction::Lower,
+    ScalarFunction::Upper,
+    ScalarFunction::Trim,
+    ScalarFunction::Substring,
+    ScalarFunction::Size,
+    ScalarFunction::Replace,
+    ScalarFunction::Split,
+    ScalarFunction::Abs,
+    ScalarFunction::Round,
+    ScalarFunction::Floor,
+    ScalarFunction::Ceil,
+    ScalarFunction::Sqrt,
+    ScalarFunction::Coalesce,
+    ScalarFunction::ToInteger,
+    ScalarFunction::ToFloat,
+    ScalarFunction::ToString,
+    ScalarFunction::ToBoolean,
+    ScalarFunction::Date,
+    ScalarFunction::Timestamp,
+    ScalarFunction::Year,
+    ScalarFunction::Month,
+    ScalarFunction::Day,
+];
+
+impl ScalarFunction {
+    /// Looks up a function by name, ignoring case.
+    #[must_use]
+    pub fn lookup(name: &str) -> Option<Self> {
+        REGISTRY
+            .iter()
+            .copied()
+            .find(|function| function.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the name of the function.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            ScalarFunction::Lower => "lower",
+            ScalarFunction::Upper => "upper",
+            ScalarFunction::Trim => "trim",
+            ScalarFunction::Substring => "substring",
+            ScalarFunction::Size => "size",
+            ScalarFunction::Replace => "replace",
+            ScalarFunction::Split => "split",
+            ScalarFunction::Abs => "abs",
+            ScalarFunction::Round => "round",
+            ScalarFunction::Floor => "floor",
+            ScalarFunction::Ceil => "ceil",
+            ScalarFunction::Sqrt => "sqrt",
+            ScalarFunction::Coalesce => "coalesce",
+            ScalarFunction::ToInteger => "toInteger",
+            ScalarFunction::ToFloat => "toFloat",
+            ScalarFunction::ToString => "toString",
+            ScalarFunction::ToBoolean => "toBoolean",
+            ScalarFunction::Date => "date",
+            ScalarFunction::Timestamp => "timestamp",
+            ScalarFunction::Year => "year",
+            ScalarFunction::Month => "month",
+            ScalarFunction::Day => "day",
+        }
+    }
+
+    /// Returns the minimum and maximum number of arguments; `None` means
+    /// any number.
+    #[must_use]
+    pub fn arity(self) -> (usize, Option<usize>) {
+        match self {
+            ScalarFunction::Substring => (2, Some(3)),
+            ScalarFunction::Round => (1, Some(2)),
+            ScalarFunction::Replace => (3, Some(3)),
+            ScalarFunction::Split => (2, Some(2)),
+            ScalarFunction::Coalesce => (1, None),
+            _ => (1, Some(1)),
+        }
+    }
+
+    /// Whether `count` arguments are accepted.
+    #[must_use]
+    pub fn accepts_arity(self, count: usize) -> bool {
+        let (min, max) = self.arity();
+        count >= min && max.map_or(true, |max| count <= max)
+    }
+}
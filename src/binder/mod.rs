@@ -8,11 +8,15 @@
 //! The output is a bound query graph ready for planning.
 
 mod expression;
+mod function;
+mod parameters;
 mod query_graph;
 mod scope;
 mod semantic;
 
 pub use expression::{AggregateFunction, ArithmeticOp, BoundExpression, ComparisonOp, LogicalOp};
+pub use function::ScalarFunction;
+pub use parameters::{bind_parameters, parameter_names};
 pub use query_graph::{BoundNode, BoundRelationship, Direction, QueryGraph};
 pub use scope::{BinderScope, BoundVariable, VariableType};
-pub use semantic::{BindError, Binder, BoundQuery, BoundReturn, BoundStatement};
+pub use semantic::{BindError, Binder, BoundQuery, BoundReturn, BoundStatement, ProjectedColumn};
@@ -0,0 +1,394 @@
+//! Query parameters (`$name`) and their substitution into a parsed statement.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::error::{Result, RuzuError};
+use crate::parser::ast::{
+    CaseWhen, Clause, Expression, Literal, MatchPattern, NodeFilter, OrderByItem, PathPattern, Projection,
+    ReturnItem, RowCount, SetItem, Statement, ValueExpr,
+};
+use crate::types::Value;
+
+use super::semantic::BindError;
+
+/// Replaces every `$name` parameter in `statement` with its value from `params`.
+///
+/// Values become literals, so a string is never re-parsed as Cypher and
+/// needs no quoting. Parameters not used by the statement are ignored.
+///
+/// # Errors
+///
+/// Returns `BindError::MissingParameter` if a parameter has no value,
+/// `BindError::InvalidRowCount` if the value of a SKIP or LIMIT parameter is
+/// not a non-negative INT64, or an error if a value has a type that has no
+/// literal form (MAP).
+pub fn bind_parameters<S: BuildHasher>(
+    statement: &mut Statement,
+    params: &HashMap<String, Value, S>,
+) -> Result<()> {
+    visit_statement(statement, &mut |literal| {
+        if let Literal::Parameter(name) = literal {
+            let value = params
+                .get(name.as_str())
+                .ok_or_else(|| BindError::MissingParameter(name.clone()))?;
+            *literal = value_to_literal(value)?;
+        }
+        Ok(())
+    })
+}
+
+/// Returns the names of the parameters used by `statement`, in order of first
+/// appearance.
+#[must_use]
+pub fn parameter_names(statement: &Statement) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let _ = visit_statement(&mut statement.clone(), &mut |literal| {
+        if let Literal::Parameter(name) = literal {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        Ok(())
+    });
+    names
+}
+
+/// Converts a parameter value to the literal it stands for.
+fn value_to_literal(value: &Value) -> Result<Literal> {
+    Ok(match value {
+        Value::Null => Literal::Null,
+        Value::Bool(b) => Literal::Bool(*b),
+        Value::Int64(n) => Literal::Int64(*n),
+        Value::Float32(f) => Literal::Float64(f64::from(*f)),
+        Value::Float64(f) => Literal::Float64(*f),
+        Value::String(s) => Literal::String(s.clone()),
+        Value::List(items) => {
+            Literal::List(items.iter().map(value_to_literal).collect::<Result<_>>()?)
+        }
+        Value::Date(days) => Literal::Date(*days),
+        Value::Timestamp(micros) => Literal::Timestamp(*micros),
+        Value::Map(_) => {
+            return Err(RuzuError::UnsupportedOperation(format!(
+                "{} parameter values",
+                value.data_type().map_or("NULL", |t| t.name())
+            )))
+        }
+    })
+}
+
+/// Callback applied to every literal of a statement.
+type LiteralVisitor<'a> = dyn FnMut(&mut Literal) -> Result<()> + 'a;
+
+#[allow(clippy::too_many_lines)]
+fn visit_statement(statement: &mut Statement, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    match statement {
+        Statement::CreateNodeTable { .. }
+        | Statement::CreateRelTable { .. }
+        | Statement::Copy { .. } => Ok(()),
+        Statement::CreateNode { properties, .. } => visit_properties(properties, f),
+        Statement::MatchCreate {
+            src_node,
+            dst_node,
+            rel_props,
+            ..
+        } => {
+            visit_node(src_node, f)?;
+            visit_node(dst_node, f)?;
+            visit_properties(rel_props, f)
+        }
+        Statement::Match {
+            filter,
+            projections,
+            order_by,
+            skip,
+            limit,
+            ..
+        } => {
+            visit_filter(filter.as_mut(), f)?;
+            visit_return_items(projections.iter_mut(), f)?;
+            visit_order_by(order_by.as_mut(), f)?;
+            visit_row_count(skip.as_mut(), f)?;
+            visit_row_count(limit.as_mut(), f)
+        }
+        Statement::MatchRel {
+            src_node,
+            rel_filters,
+            dst_node,
+            filter,
+            projections,
+            order_by,
+            skip,
+            limit,
+            ..
+        } => {
+            visit_node(src_node, f)?;
+            visit_filters(rel_filters, f)?;
+            visit_node(dst_node, f)?;
+            visit_filter(filter.as_mut(), f)?;
+            visit_return_items(projections.iter_mut(), f)?;
+            visit_order_by(order_by.as_mut(), f)?;
+            visit_row_count(skip.as_mut(), f)?;
+            visit_row_count(limit.as_mut(), f)
+        }
+        Statement::Merge {
+            properties,
+            on_create,
+            on_match,
+            ..
+        } => {
+            visit_properties(properties, f)?;
+            visit_set_items(on_create, f)?;
+            visit_set_items(on_match, f)
+        }
+        Statement::UnwindWrite {
+            list,
+            node,
+            on_create,
+            on_match,
+            ..
+        } => {
+            visit_value(list, f)?;
+            visit_node(node, f)?;
+            visit_set_items(on_create, f)?;
+            visit_set_items(on_match, f)
+        }
+        Statement::MatchMerge {
+            src_node,
+            dst_node,
+            rel_props,
+            on_create,
+            on_match,
+            ..
+        } => {
+            visit_node(src_node, f)?;
+            visit_node(dst_node, f)?;
+            visit_properties(rel_props, f)?;
+            visit_set_items(on_create, f)?;
+            visit_set_items(on_match, f)
+        }
+        Statement::MatchSet {
+            pattern,
+            filter,
+            items,
+        } => {
+            visit_match_pattern(pattern, f)?;
+            visit_filter(filter.as_mut(), f)?;
+            visit_set_items(items, f)
+        }
+        Statement::MatchDelete {
+            pattern, filter, ..
+        } => {
+            visit_match_pattern(pattern, f)?;
+            visit_filter(filter.as_mut(), f)
+        }
+        Statement::Query {
+            clauses,
+            projection,
+        } => visit_query(clauses, projection, f),
+        Statement::Union { queries, .. } => {
+            for query in queries {
+                visit_query(&mut query.clauses, &mut query.projection, f)?;
+            }
+            Ok(())
+        }
+        Statement::Explain { inner } => visit_statement(inner, f),
+    }
+}
+
+fn visit_query(
+    clauses: &mut [Clause],
+    projection: &mut Projection,
+    f: &mut LiteralVisitor<'_>,
+) -> Result<()> {
+    for clause in clauses {
+        match clause {
+            Clause::Match {
+                patterns, filter, ..
+            } => {
+                for pattern in patterns {
+                    visit_path(pattern, f)?;
+                }
+                visit_filter(filter.as_mut(), f)?;
+            }
+            Clause::With(with) => visit_projection(with, f)?,
+            Clause::Unwind { list, .. } => visit_value(list, f)?,
+        }
+    }
+    visit_projection(projection, f)
+}
+
+fn visit_projection(projection: &mut Projection, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    visit_return_items(projection.items.iter_mut().map(|item| &mut item.item), f)?;
+    visit_filter(projection.filter.as_mut(), f)?;
+    visit_order_by(projection.order_by.as_mut(), f)?;
+    visit_row_count(projection.skip.as_mut(), f)?;
+    visit_row_count(projection.limit.as_mut(), f)
+}
+
+fn visit_return_items<'a>(
+    items: impl Iterator<Item = &'a mut ReturnItem>,
+    f: &mut LiteralVisitor<'_>,
+) -> Result<()> {
+    for item in items {
+        match item {
+            ReturnItem::Expression(expr) => visit_value(expr, f)?,
+            ReturnItem::Aggregate(agg) => {
+                for expr in agg.input.iter_mut().chain(agg.percentile.iter_mut()) {
+                    visit_value(expr, f)?;
+                }
+            }
+            ReturnItem::Projection { .. } | ReturnItem::Variable(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn visit_order_by(
+    order_by: Option<&mut Vec<OrderByItem>>,
+    f: &mut LiteralVisitor<'_>,
+) -> Result<()> {
+    for item in order_by.into_iter().flatten() {
+        visit_value(&mut item.expr, f)?;
+    }
+    Ok(())
+}
+
+/// Visits the parameter of a SKIP or LIMIT count as a literal, keeping the
+/// value it is bound to if that is a valid count.
+fn visit_row_count(count: Option<&mut RowCount>, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    if let Some(count) = count {
+        if let RowCount::Parameter(name) = count {
+            let name = name.clone();
+            let mut literal = Literal::Parameter(name.clone());
+            f(&mut literal)?;
+            match literal {
+                Literal::Parameter(_) => {}
+                Literal::Int64(n) if n >= 0 => *count = RowCount::Count(n),
+                _ => return Err(BindError::InvalidRowCount(name).into()),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn visit_match_pattern(pattern: &mut MatchPattern, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    match pattern {
+        MatchPattern::Node(node) => visit_node(node, f),
+        MatchPattern::Rel {
+            src_node,
+            rel_filters,
+            dst_node,
+            ..
+        } => {
+            visit_node(src_node, f)?;
+            visit_filters(rel_filters, f)?;
+            visit_node(dst_node, f)
+        }
+    }
+}
+
+fn visit_path(pattern: &mut PathPattern, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    visit_node(&mut pattern.start, f)?;
+    for hop in &mut pattern.hops {
+        visit_filters(&mut hop.rel_filters, f)?;
+        visit_node(&mut hop.node, f)?;
+    }
+    Ok(())
+}
+
+fn visit_node(node: &mut NodeFilter, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    visit_filters(&mut node.property_filters, f)
+}
+
+fn visit_filters(filters: &mut [(String, ValueExpr)], f: &mut LiteralVisitor<'_>) -> Result<()> {
+    for (_, value) in filters {
+        visit_value(value, f)?;
+    }
+    Ok(())
+}
+
+fn visit_properties(properties: &mut [(String, Literal)], f: &mut LiteralVisitor<'_>) -> Result<()> {
+    for (_, literal) in properties {
+        visit_literal(literal, f)?;
+    }
+    Ok(())
+}
+
+fn visit_set_items(items: &mut [SetItem], f: &mut LiteralVisitor<'_>) -> Result<()> {
+    for item in items {
+        visit_value(&mut item.value, f)?;
+    }
+    Ok(())
+}
+
+fn visit_filter(filter: Option<&mut Expression>, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    match filter {
+        Some(expr) => visit_expression(expr, f),
+        None => Ok(()),
+    }
+}
+
+fn visit_expression(expr: &mut Expression, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    match expr {
+        Expression::Comparison { left, right, .. } => {
+            visit_value(left, f)?;
+            visit_value(right, f)
+        }
+        Expression::Value(value) | Expression::IsNull { operand: value, .. } => {
+            visit_value(value, f)
+        }
+        Expression::In { operand, list, .. } => {
+            visit_value(operand, f)?;
+            visit_value(list, f)
+        }
+        Expression::And(left, right) | Expression::Or(left, right) => {
+            visit_expression(left, f)?;
+            visit_expression(right, f)
+        }
+        Expression::Not(operand) => visit_expression(operand, f),
+        Expression::Exists { pattern, filter } => {
+            visit_path(pattern, f)?;
+            visit_filter(filter.as_deref_mut(), f)
+        }
+    }
+}
+
+fn visit_value(expr: &mut ValueExpr, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    match expr {
+        ValueExpr::Literal(literal) => visit_literal(literal, f),
+        ValueExpr::Property { .. } | ValueExpr::Variable(_) => Ok(()),
+        ValueExpr::Arithmetic { left, right, .. } => {
+            visit_value(left, f)?;
+            visit_value(right, f)
+        }
+        ValueExpr::Negate(operand) => visit_value(operand, f),
+        ValueExpr::Case(case) => {
+            if let Some(operand) = &mut case.operand {
+                visit_value(operand, f)?;
+            }
+            for (when, result) in &mut case.branches {
+                match when {
+                    CaseWhen::Value(value) => visit_value(value, f)?,
+                    CaseWhen::Condition(condition) => visit_expression(condition, f)?,
+                }
+                visit_value(result, f)?;
+            }
+            match &mut case.default {
+                Some(default) => visit_value(default, f),
+                None => Ok(()),
+            }
+        }
+        ValueExpr::Function { args, .. } => args.iter_mut().try_for_each(|arg| visit_value(arg, f)),
+    }
+}
+
+fn visit_literal(literal: &mut Literal, f: &mut LiteralVisitor<'_>) -> Result<()> {
+    if let Literal::List(items) = literal {
+        for item in items {
+            visit_literal(item, f)?;
+        }
+        return Ok(());
+    }
+    f(literal)
+}
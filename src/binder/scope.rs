@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::catalog::NodeTableSchema;
+use crate::catalog::{NodeTableSchema, RelTableSchema};
 use crate::types::DataType;
 
 /// Variable scope for name resolution during binding.
@@ -86,6 +86,8 @@ pub struct BoundVariable {
     pub data_type: DataType,
     /// Schema if node/relationship.
     pub schema: Option<Arc<NodeTableSchema>>,
+    /// Relationship table schema if relationship.
+    pub rel_schema: Option<Arc<RelTableSchema>>,
 }
 
 impl BoundVariable {
@@ -97,6 +99,7 @@ impl BoundVariable {
             variable_type: VariableType::Node,
             data_type: DataType::Int64, // Node ID type
             schema: Some(schema),
+            rel_schema: None,
         }
     }
 
@@ -108,6 +111,19 @@ impl BoundVariable {
             variable_type: VariableType::Relationship,
             data_type,
             schema: None,
+            rel_schema: None,
+        }
+    }
+
+    /// Creates a new bound relationship variable with the schema of its table.
+    #[must_use]
+    pub fn rel(name: String, rel_schema: Arc<RelTableSchema>) -> Self {
+        BoundVariable {
+            name,
+            variable_type: VariableType::Relationship,
+            data_type: DataType::Int64, // Relationship ID type
+            schema: None,
+            rel_schema: Some(rel_schema),
         }
     }
 
@@ -119,6 +135,7 @@ impl BoundVariable {
             variable_type: VariableType::Property,
             data_type,
             schema: None,
+            rel_schema: None,
         }
     }
 
@@ -130,6 +147,7 @@ impl BoundVariable {
             variable_type: VariableType::Aggregate,
             data_type,
             schema: None,
+            rel_schema: None,
         }
     }
 }
@@ -1,12 +1,21 @@
 //! Semantic analysis and binding.
 
-use crate::catalog::Catalog;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::catalog::{Catalog, RelTableSchema};
 use crate::error::{Result, RuzuError};
-use crate::types::DataType;
+use crate::executor::literal_value;
+use crate::parser::ast::{
+    self, AggregateExpr, ArithmeticOp, AstAggregateFunction, CaseExpr, CaseWhen, Expression, Literal, NodeFilter, PathPattern, Projection,
+    ProjectionItem, RelDirection, ReturnItem, ValueExpr,
+};
+use crate::types::{DataType, Value};
 
-use super::expression::BoundExpression;
+use super::expression::{self, AggregateFunction, BoundExpression, ComparisonOp};
+use super::function::ScalarFunction;
 use super::query_graph::{BoundNode, BoundRelationship, Direction, QueryGraph};
-use super::scope::{BinderScope, BoundVariable};
+use super::scope::{BinderScope, BoundVariable, VariableType};
 
 /// Errors that can occur during binding.
 #[derive(Debug, Clone)]
@@ -30,6 +39,26 @@ pub enum BindError {
     InvalidPathBounds { min: usize, max: usize },
     /// Invalid property access on non-node/rel variable.
     InvalidPropertyAccess(String, String),
+    /// Expression projected by WITH without an `AS` alias.
+    MissingAlias(String),
+    /// Queries combined by UNION return different numbers of columns.
+    UnionColumnCount { expected: usize, actual: usize },
+    /// Query parameter (`$name`) without a value.
+    MissingParameter(String),
+    /// Call to a function that is not registered.
+    UnknownFunction(String),
+    /// Function called with the wrong number of arguments.
+    WrongArgumentCount { function: String, actual: usize },
+    /// EXISTS subquery or pattern predicate outside a MATCH WHERE clause.
+    SubqueryOutsideWhere,
+    /// Query parameter given a value of a type it cannot stand for.
+    ParameterType {
+        name: String,
+        expected: DataType,
+        actual: DataType,
+    },
+    /// SKIP or LIMIT parameter whose value is not a non-negative INT64.
+    InvalidRowCount(String),
 }
 
 impl std::fmt::Display for BindError {
@@ -53,6 +82,32 @@ impl std::fmt::Display for BindError {
             BindError::InvalidPropertyAccess(var, prop) => {
                 write!(f, "Invalid property access: {var}.{prop}")
             }
+            BindError::MissingAlias(expr) => {
+                write!(f, "Expression in WITH must be aliased (use AS): {expr}")
+            }
+            BindError::UnionColumnCount { expected, actual } => write!(
+                f,
+                "All queries in a UNION must return the same number of columns: \
+                 expected {expected}, got {actual}"
+            ),
+            BindError::MissingParameter(name) => write!(f, "Missing value for parameter: ${name}"),
+            BindError::UnknownFunction(name) => write!(f, "Unknown function: {name}"),
+            BindError::WrongArgumentCount { function, actual } => {
+                write!(f, "Wrong number of arguments to {function}: {actual}")
+            }
+            BindError::SubqueryOutsideWhere => write!(
+                f,
+                "EXISTS subqueries and pattern predicates are only allowed in the WHERE clause \
+                 of a MATCH"
+            ),
+            BindError::ParameterType {
+                name,
+                expected,
+                actual,
+            } => write!(f, "Parameter ${name} must be {expected:?}, got {actual:?}"),
+            BindError::InvalidRowCount(name) => {
+                write!(f, "Parameter ${name} of SKIP or LIMIT must be a non-negative Int64")
+            }
         }
     }
 }
@@ -202,12 +257,25 @@ impl BoundReturn {
     }
 }
 
+/// Column produced by a bound WITH or RETURN item.
+#[derive(Debug, Clone)]
+pub struct ProjectedColumn {
+    /// Output name: the alias, or the item's default column name.
+    pub name: String,
+    /// Node or relationship carried through, or a plain value.
+    pub variable_type: VariableType,
+    /// Data type of the value.
+    pub data_type: DataType,
+}
+
 /// Main binder for semantic analysis.
 pub struct Binder<'a> {
     /// Reference to database catalog.
     catalog: &'a Catalog,
     /// Current variable scope.
     scope: BinderScope,
+    /// Types expected of the `$name` parameters bound so far.
+    parameter_types: RefCell<HashMap<String, DataType>>,
 }
 
 impl<'a> Binder<'a> {
@@ -217,6 +285,28 @@ impl<'a> Binder<'a> {
         Binder {
             catalog,
             scope: BinderScope::new(),
+            parameter_types: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the types expected of the parameters bound so far.
+    ///
+    /// A parameter gets the type of the property, column or value it is
+    /// compared with or assigned to; one used only on its own has none.
+    #[must_use]
+    pub fn parameter_types(&self) -> HashMap<String, DataType> {
+        self.parameter_types.borrow().clone()
+    }
+
+    /// Records that `expr`, if it is a parameter, must be a `data_type` value.
+    ///
+    /// The first type recorded for a parameter wins.
+    pub fn expect_parameter(&self, expr: &ValueExpr, data_type: DataType) {
+        if let ValueExpr::Literal(Literal::Parameter(name)) = expr {
+            self.parameter_types
+                .borrow_mut()
+                .entry(name.clone())
+                .or_insert(data_type);
         }
     }
 
@@ -292,7 +382,7 @@ impl<'a> Binder<'a> {
 
         // Add variable to scope if present
         if let Some(var) = variable {
-            let bound_var = BoundVariable::relationship(var.to_string(), DataType::Int64);
+            let bound_var = BoundVariable::rel(var.to_string(), rel_schema.clone());
             self.scope.add_variable(bound_var);
         }
 
@@ -334,9 +424,903 @@ impl<'a> Binder<'a> {
             }
             return Err(BindError::UndefinedColumn(variable.to_string(), property.to_string()).into());
         }
+        if let Some(rel_schema) = &var.rel_schema {
+            for col in &rel_schema.columns {
+                if col.name == property {
+                    return Ok(col.data_type);
+                }
+            }
+            return Err(BindError::UndefinedColumn(variable.to_string(), property.to_string()).into());
+        }
 
         Err(BindError::InvalidPropertyAccess(variable.to_string(), property.to_string()).into())
     }
+
+    /// Binds the node and relationship variables of a MATCH path pattern into
+    /// a query graph.
+    ///
+    /// A node variable that is already in scope refers to the node bound by
+    /// an earlier clause or earlier in the same path; its label may be
+    /// omitted and is filled in from the scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a new variable has no label or is already defined
+    /// as something other than a node of that label, if a label or
+    /// relationship type does not exist, or if the endpoint labels of a hop
+    /// do not match its relationship table.
+    pub fn bind_pattern(&mut self, pattern: &mut PathPattern) -> Result<QueryGraph> {
+        let mut graph = QueryGraph::new();
+        graph.add_node(self.bind_pattern_node(&mut pattern.start)?);
+
+        let mut src_node = &pattern.start;
+        for hop in &mut pattern.hops {
+            for (_, value) in &hop.rel_filters {
+                self.validate_value_expr(value)?;
+            }
+            let dst = self.bind_pattern_node(&mut hop.node)?;
+            let rel_schema = self.catalog.get_rel_table(&hop.rel_type).ok_or_else(|| {
+                RuzuError::from(BindError::UndefinedTable(hop.rel_type.clone()))
+            })?;
+            for (key, value) in &hop.rel_filters {
+                let column = rel_schema.get_column(key).ok_or_else(|| {
+                    BindError::UndefinedColumn(hop.rel_type.clone(), key.clone())
+                })?;
+                self.expect_parameter(value, column.data_type);
+            }
+            hop.direction = Self::resolve_direction(
+                &rel_schema,
+                &src_node.label,
+                &hop.node.label,
+                hop.direction,
+            )?;
+            let rel = self.bind_relationship(
+                hop.rel_var.as_deref(),
+                &hop.rel_type,
+                &src_node.var,
+                &hop.node.var,
+                match hop.direction {
+                    RelDirection::Forward => Direction::Forward,
+                    RelDirection::Backward => Direction::Backward,
+                    RelDirection::Both => Direction::Both,
+                },
+            )?;
+            if graph.get_node(&dst.variable).is_none() {
+                graph.add_node(dst);
+            }
+            graph.add_relationship(rel);
+            src_node = &hop.node;
+        }
+        Ok(graph)
+    }
+
+    /// Resolves the direction in which a relationship of `rel_schema` is
+    /// traversed from a `src_label` node to a `dst_label` node.
+    ///
+    /// An undirected pattern can only run both ways when the relationship
+    /// connects a table to itself; otherwise the endpoint labels pick the one
+    /// orientation that fits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint labels do not match the relationship
+    /// table in the requested direction.
+    pub fn resolve_direction(
+        rel_schema: &RelTableSchema,
+        src_label: &str,
+        dst_label: &str,
+        direction: RelDirection,
+    ) -> Result<RelDirection> {
+        let forward = rel_schema.src_table == src_label && rel_schema.dst_table == dst_label;
+        let backward = rel_schema.src_table == dst_label && rel_schema.dst_table == src_label;
+        let resolved = match (direction, forward, backward) {
+            (RelDirection::Forward, true, _) | (RelDirection::Both, true, false) => {
+                Some(RelDirection::Forward)
+            }
+            (RelDirection::Backward, _, true) | (RelDirection::Both, false, true) => {
+                Some(RelDirection::Backward)
+            }
+            (RelDirection::Both, true, true) => Some(RelDirection::Both),
+            _ => None,
+        };
+
+        resolved.ok_or_else(|| {
+            let (from, to) = if direction == RelDirection::Backward {
+                (dst_label, src_label)
+            } else {
+                (src_label, dst_label)
+            };
+            RuzuError::SchemaError(format!(
+                "Relationship '{}' connects '{}' to '{}', not '{from}' to '{to}'",
+                rel_schema.name, rel_schema.src_table, rel_schema.dst_table
+            ))
+        })
+    }
+
+    /// Binds one node of a MATCH pattern, resolving references to nodes
+    /// bound by earlier clauses.
+    ///
+    /// Each inline property filter must name a column of the node's table.
+    fn bind_pattern_node(&mut self, node: &mut NodeFilter) -> Result<BoundNode> {
+        for (_, value) in &node.property_filters {
+            self.validate_value_expr(value)?;
+        }
+
+        let bound = match self.scope.lookup(&node.var) {
+            None if node.label.is_empty() => {
+                return Err(BindError::UndefinedVariable(node.var.clone()).into());
+            }
+            None => self.bind_node(&node.var, &node.label)?,
+            Some(bound) => match &bound.schema {
+                Some(schema) if node.label.is_empty() || node.label == schema.name => {
+                    node.label.clone_from(&schema.name);
+                    BoundNode::new(node.var.clone(), schema.clone())
+                }
+                _ => return Err(BindError::DuplicateVariable(node.var.clone()).into()),
+            },
+        };
+
+        for (key, value) in &node.property_filters {
+            let column = bound.table_schema.get_column(key).ok_or_else(|| {
+                BindError::UndefinedColumn(node.var.clone(), key.clone())
+            })?;
+            self.expect_parameter(value, column.data_type);
+        }
+        Ok(bound)
+    }
+
+    /// Validates the variables and properties referenced by a value expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is undefined or a property does not exist.
+    pub fn validate_value_expr(&self, expr: &ValueExpr) -> Result<()> {
+        self.bind_value_expr(expr).map(|_| ())
+    }
+
+    /// Infers the data type of a value expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is undefined or a property does not exist.
+    pub fn value_expr_type(&self, expr: &ValueExpr) -> Result<DataType> {
+        self.bind_value_expr(expr).map(|bound| bound.data_type())
+    }
+
+    /// Binds an aggregate call, resolving the variables and properties of its
+    /// input and percentile expressions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is undefined or a property does not exist.
+    pub fn bind_aggregate(&self, agg: &AggregateExpr) -> Result<BoundExpression> {
+        let input = agg.input.as_ref().map(|input| self.bind_value_expr(input)).transpose()?;
+        if let Some(percentile) = &agg.percentile {
+            self.bind_value_expr(percentile)?;
+        }
+        let function = aggregate_function(agg.function);
+        Ok(BoundExpression::Aggregate {
+            function,
+            data_type: function.output_type(input.as_ref().map(BoundExpression::data_type)),
+            input: input.map(Box::new),
+            distinct: agg.distinct,
+        })
+    }
+
+    /// Binds a value expression, resolving its variables and properties.
+    ///
+    /// Integer arithmetic stays INT64, `+` on two strings is a STRING
+    /// concatenation, and any other arithmetic is FLOAT64.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is undefined or a property does not exist.
+    pub fn bind_value_expr(&self, expr: &ValueExpr) -> Result<BoundExpression> {
+        Ok(match expr {
+            ValueExpr::Literal(literal) => BoundExpression::Literal {
+                value: literal_value(literal),
+                data_type: literal_type(literal).unwrap_or(DataType::String),
+            },
+            ValueExpr::Property { var, property } => BoundExpression::property_access(
+                var.clone(),
+                property.clone(),
+                self.validate_property(var, property)?,
+            ),
+            ValueExpr::Variable(name) => BoundExpression::VariableRef {
+                variable: name.clone(),
+                data_type: self.validate_variable(name)?.data_type,
+            },
+            ValueExpr::Arithmetic { left, op, right } => {
+                let left = self.bind_value_expr(left)?;
+                let right = self.bind_value_expr(right)?;
+                let data_type = match (left.data_type(), right.data_type()) {
+                    (DataType::Int64, DataType::Int64) => DataType::Int64,
+                    (DataType::Date, DataType::Date) if *op == ArithmeticOp::Sub => DataType::Int64,
+                    (DataType::Date, DataType::Int64)
+                        if matches!(op, ArithmeticOp::Add | ArithmeticOp::Sub) =>
+                    {
+                        DataType::Date
+                    }
+                    (DataType::Int64, DataType::Date) if *op == ArithmeticOp::Add => DataType::Date,
+                    (DataType::String, DataType::String) if *op == ArithmeticOp::Add => {
+                        DataType::String
+                    }
+                    _ => DataType::Float64,
+                };
+                BoundExpression::Arithmetic {
+                    left: Box::new(left),
+                    op: arithmetic_op(*op),
+                    right: Box::new(right),
+                    data_type,
+                }
+            }
+            ValueExpr::Negate(operand) => self.bind_negation(operand)?,
+            ValueExpr::Case(case) => self.bind_case(case)?,
+            ValueExpr::Function { name, args } => self.bind_function(name, args)?,
+        })
+    }
+
+    /// Binds unary minus as multiplication by -1, which keeps the type of an
+    /// INT64 operand and makes any other number FLOAT64.
+    fn bind_negation(&self, operand: &ValueExpr) -> Result<BoundExpression> {
+        let operand = self.bind_value_expr(operand)?;
+        let data_type = match operand.data_type() {
+            DataType::Int64 => DataType::Int64,
+            DataType::Float32 | DataType::Float64 => DataType::Float64,
+            _ if is_null_literal(&operand) => DataType::Int64,
+            actual => {
+                return Err(BindError::TypeMismatch {
+                    expected: DataType::Float64,
+                    actual,
+                }
+                .into())
+            }
+        };
+        Ok(BoundExpression::Arithmetic {
+            left: Box::new(BoundExpression::Literal {
+                value: Value::Int64(-1),
+                data_type: DataType::Int64,
+            }),
+            op: arithmetic_op(ArithmeticOp::Mul),
+            right: Box::new(operand),
+            data_type,
+        })
+    }
+
+    /// Binds a scalar function call, checking the number and types of its
+    /// arguments. NULL literals are accepted for any argument.
+    fn bind_function(&self, name: &str, args: &[ValueExpr]) -> Result<BoundExpression> {
+        let function = ScalarFunction::lookup(name)
+            .ok_or_else(|| BindError::UnknownFunction(name.to_string()))?;
+        if !function.accepts_arity(args.len()) {
+            return Err(BindError::WrongArgumentCount {
+                function: function.name().to_string(),
+                actual: args.len(),
+            }
+            .into());
+        }
+        let args = args
+            .iter()
+            .map(|arg| self.bind_value_expr(arg))
+            .collect::<Result<Vec<_>>>()?;
+        let data_type = function_type(function, &args)?;
+        Ok(BoundExpression::Function {
+            function,
+            args,
+            data_type,
+        })
+    }
+
+    /// Binds a CASE expression, inferring its type from the THEN and ELSE
+    /// results: integers and floats mix to FLOAT64, and NULL results are
+    /// skipped.
+    fn bind_case(&self, case: &CaseExpr) -> Result<BoundExpression> {
+        let operand = case
+            .operand
+            .as_ref()
+            .map(|operand| self.bind_value_expr(operand))
+            .transpose()?;
+        let mut result_type = None;
+        let mut unify = |result: &BoundExpression| -> Result<()> {
+            if !is_null_literal(result) {
+                result_type = Some(common_type(result_type, result.data_type())?);
+            }
+            Ok(())
+        };
+
+        let mut branches = Vec::with_capacity(case.branches.len());
+        for (when, result) in &case.branches {
+            let condition = match (when, &operand) {
+                (CaseWhen::Value(value), Some(operand)) => BoundExpression::comparison(
+                    operand.clone(),
+                    ComparisonOp::Eq,
+                    self.bind_value_expr(value)?,
+                ),
+                (CaseWhen::Value(value), None) => {
+                    self.bind_expression(&Expression::Value(value.clone()))?
+                }
+                (CaseWhen::Condition(condition), _) => self.bind_expression(condition)?,
+            };
+            let result = self.bind_value_expr(result)?;
+            unify(&result)?;
+            branches.push((condition, result));
+        }
+        let default = case
+            .default
+            .as_ref()
+            .map(|default| self.bind_value_expr(default))
+            .transpose()?;
+        if let Some(default) = &default {
+            unify(default)?;
+        }
+
+        Ok(BoundExpression::Case {
+            branches,
+            default: default.map(Box::new),
+            data_type: result_type.unwrap_or(DataType::String),
+        })
+    }
+
+    /// Binds a boolean WHERE expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is undefined, a property does not exist,
+    /// or a value used as a predicate is not a boolean.
+    pub fn bind_expression(&self, expr: &Expression) -> Result<BoundExpression> {
+        match expr {
+            Expression::Comparison { left, op, right } => {
+                let (left_expr, right_expr) = (left, right);
+                let left = self.bind_value_expr(left_expr)?;
+                let op = comparison_op(*op);
+                let right = self.bind_value_expr(right_expr)?;
+                if op.is_string_predicate() {
+                    self.expect_parameter(left_expr, DataType::String);
+                    self.expect_parameter(right_expr, DataType::String);
+                } else {
+                    if !is_null_literal(&right) {
+                        self.expect_parameter(left_expr, right.data_type());
+                    }
+                    if !is_null_literal(&left) {
+                        self.expect_parameter(right_expr, left.data_type());
+                    }
+                }
+                if op.is_string_predicate() {
+                    for operand in [&left, &right] {
+                        if operand.data_type() != DataType::String && !is_null_literal(operand) {
+                            return Err(BindError::TypeMismatch {
+                                expected: DataType::String,
+                                actual: operand.data_type(),
+                            }
+                            .into());
+                        }
+                    }
+                }
+                Ok(BoundExpression::comparison(left, op, right))
+            }
+            Expression::Value(value) => {
+                let bound = self.bind_value_expr(value)?;
+                if bound.data_type() != DataType::Bool && !is_null_literal(&bound) {
+                    return Err(BindError::TypeMismatch {
+                        expected: DataType::Bool,
+                        actual: bound.data_type(),
+                    }
+                    .into());
+                }
+                Ok(bound)
+            }
+            Expression::IsNull { operand, negated } => Ok(BoundExpression::IsNull {
+                operand: Box::new(self.bind_value_expr(operand)?),
+                negated: *negated,
+                data_type: DataType::Bool,
+            }),
+            Expression::In {
+                operand,
+                list,
+                negated,
+            } => {
+                let bound_operand = self.bind_value_expr(operand)?;
+                let bound_list = self.bind_value_expr(list)?;
+                self.expect_parameter(list, DataType::List);
+                if bound_list.data_type() != DataType::List && !is_null_literal(&bound_list) {
+                    return Err(BindError::TypeMismatch {
+                        expected: DataType::List,
+                        actual: bound_list.data_type(),
+                    }
+                    .into());
+                }
+                if let ValueExpr::Literal(Literal::List(items)) = list {
+                    let element_type = list_element_type(items)?;
+                    let operand_type = bound_operand.data_type();
+                    let compatible = element_type == operand_type
+                        || (element_type.is_numeric() && operand_type.is_numeric())
+                        || is_null_literal(&bound_operand)
+                        || items.iter().all(|item| literal_type(item).is_none());
+                    if !compatible {
+                        return Err(BindError::TypeMismatch {
+                            expected: operand_type,
+                            actual: element_type,
+                        }
+                        .into());
+                    }
+                }
+                Ok(BoundExpression::in_list(bound_operand, bound_list, *negated))
+            }
+            Expression::And(left, right) => Ok(BoundExpression::and(vec![
+                self.bind_expression(left)?,
+                self.bind_expression(right)?,
+            ])),
+            Expression::Or(left, right) => Ok(BoundExpression::or(vec![
+                self.bind_expression(left)?,
+                self.bind_expression(right)?,
+            ])),
+            Expression::Not(operand) => Ok(BoundExpression::not(self.bind_expression(operand)?)),
+            Expression::Exists { .. } => Err(BindError::SubqueryOutsideWhere.into()),
+        }
+    }
+
+    /// Binds the WHERE filter of a MATCH, which unlike other predicates may
+    /// hold EXISTS subqueries and pattern predicates.
+    ///
+    /// A subquery pattern is bound in a child scope: variables bound so far
+    /// correlate it with the outer row (and their labels are filled in), while
+    /// its new variables are local to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filter or a subquery fails to bind.
+    pub fn bind_where(&self, expr: &mut Expression) -> Result<BoundExpression> {
+        match expr {
+            Expression::Exists { pattern, filter } => {
+                let mut subquery = Binder {
+                    catalog: self.catalog,
+                    scope: self.scope.child(),
+                    parameter_types: RefCell::new(self.parameter_types()),
+                };
+                let query_graph = subquery.bind_pattern(pattern)?;
+                let predicate = match filter {
+                    Some(filter) => Some(Box::new(subquery.bind_where(filter)?)),
+                    None => None,
+                };
+                self.parameter_types.replace(subquery.parameter_types.into_inner());
+                Ok(BoundExpression::Exists {
+                    query_graph: Box::new(query_graph),
+                    predicate,
+                    data_type: DataType::Bool,
+                })
+            }
+            Expression::And(left, right) => Ok(BoundExpression::and(vec![
+                self.bind_where(left)?,
+                self.bind_where(right)?,
+            ])),
+            Expression::Or(left, right) => Ok(BoundExpression::or(vec![
+                self.bind_where(left)?,
+                self.bind_where(right)?,
+            ])),
+            Expression::Not(operand) => Ok(BoundExpression::not(self.bind_where(operand)?)),
+            other => self.bind_expression(other),
+        }
+    }
+
+    /// Binds an UNWIND clause, adding `alias` to the scope.
+    ///
+    /// The alias takes the element type of a list literal, or the type of
+    /// any other expression (a non-list value unwinds to itself).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list references an undefined variable or
+    /// property, the elements of a list literal have incompatible types, or
+    /// the alias is already defined.
+    pub fn bind_unwind(&mut self, list: &ValueExpr, alias: &str) -> Result<()> {
+        let data_type = match list {
+            ValueExpr::Literal(Literal::List(items)) => list_element_type(items)?,
+            other => self.value_expr_type(other)?,
+        };
+        self.expect_parameter(list, DataType::List);
+        if self.scope.contains(alias) {
+            return Err(BindError::DuplicateVariable(alias.to_string()).into());
+        }
+        self.scope
+            .add_variable(BoundVariable::property(alias.to_string(), data_type));
+        Ok(())
+    }
+
+    /// Combines the output columns of the queries of a UNION.
+    ///
+    /// Columns are matched by position and named after the first query. A
+    /// column mixing INT64 and FLOAT64 values becomes FLOAT64.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the queries return different numbers of columns or
+    /// a column has incompatible types across queries.
+    pub fn bind_union(queries: &[Vec<ProjectedColumn>]) -> Result<Vec<ProjectedColumn>> {
+        let Some((first, rest)) = queries.split_first() else {
+            return Ok(Vec::new());
+        };
+
+        let mut columns = first.clone();
+        for query in rest {
+            if query.len() != columns.len() {
+                return Err(BindError::UnionColumnCount {
+                    expected: columns.len(),
+                    actual: query.len(),
+                }
+                .into());
+            }
+            for (column, other) in columns.iter_mut().zip(query) {
+                if column.data_type != other.data_type {
+                    if !(column.data_type.is_numeric() && other.data_type.is_numeric()) {
+                        return Err(BindError::TypeMismatch {
+                            expected: column.data_type,
+                            actual: other.data_type,
+                        }
+                        .into());
+                    }
+                    column.data_type = DataType::Float64;
+                }
+            }
+        }
+        Ok(columns)
+    }
+
+    /// Binds a WITH or RETURN projection and returns its output columns.
+    ///
+    /// Afterwards only the projected variables remain in scope. The
+    /// projection's WHERE and ORDER BY may also refer to the incoming
+    /// variables unless it aggregates, in which case an ORDER BY key that
+    /// repeats a grouping item refers to its output column. With
+    /// `require_alias` (WITH), every item other than a bare variable must be
+    /// named with `AS`.
+    ///
+    /// A `*` is expanded into the variables in scope, in name order, ahead of
+    /// the listed items.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an item references an undefined variable or
+    /// property, an alias is missing or duplicated, or the WHERE / ORDER BY
+    /// clause references a variable that is not visible.
+    pub fn bind_projection(
+        &mut self,
+        projection: &mut Projection,
+        require_alias: bool,
+    ) -> Result<Vec<ProjectedColumn>> {
+        if std::mem::take(&mut projection.star) {
+            let mut names = self.scope.variable_names();
+            names.sort_unstable();
+            let expanded: Vec<ProjectionItem> = names
+                .into_iter()
+                .map(|name| ProjectionItem {
+                    item: ReturnItem::Variable(name.to_string()),
+                    alias: None,
+                })
+                .collect();
+            projection.items.splice(0..0, expanded);
+        }
+
+        let mut columns = Vec::with_capacity(projection.items.len());
+        let mut projected = BinderScope::new();
+
+        for item in &projection.items {
+            let (mut bound, default_name) = match &item.item {
+                ReturnItem::Variable(name) => (self.validate_variable(name)?.clone(), None),
+                ReturnItem::Projection { var, property } => {
+                    let data_type = self.validate_property(var, property)?;
+                    let name = item.item.column_name();
+                    (BoundVariable::property(name.clone(), data_type), Some(name))
+                }
+                ReturnItem::Expression(expr) => {
+                    let data_type = self.value_expr_type(expr)?;
+                    let name = item.item.column_name();
+                    (BoundVariable::property(name.clone(), data_type), Some(name))
+                }
+                ReturnItem::Aggregate(agg) => {
+                    let data_type = self.bind_aggregate(agg)?.data_type();
+                    let name = item.item.column_name();
+                    (BoundVariable::aggregate(name.clone(), data_type), Some(name))
+                }
+            };
+
+            if let Some(alias) = &item.alias {
+                bound.name.clone_from(alias);
+            } else if let (true, Some(name)) = (require_alias, default_name) {
+                return Err(BindError::MissingAlias(name).into());
+            }
+            if projected.variables().any(|v| v.name == bound.name) {
+                return Err(BindError::DuplicateVariable(bound.name).into());
+            }
+
+            columns.push(ProjectedColumn {
+                name: bound.name.clone(),
+                variable_type: bound.variable_type,
+                data_type: bound.data_type,
+            });
+            projected.add_variable(bound);
+        }
+
+        // WHERE and ORDER BY see the projected names, plus the incoming
+        // variables when rows are not collapsed by aggregation
+        let aggregating = projection
+            .items
+            .iter()
+            .any(|item| matches!(item.item, ReturnItem::Aggregate(_)));
+        let mut visible = if aggregating {
+            BinderScope::new()
+        } else {
+            self.scope.child()
+        };
+        for var in projected.variables() {
+            visible.add_variable(var.clone());
+        }
+
+        // After aggregation a grouping item or aggregate survives only as its
+        // output column, so ORDER BY refers to it by that name
+        if aggregating {
+            let grouping: Vec<(String, String)> = projection
+                .items
+                .iter()
+                .zip(&columns)
+                .filter(|(item, _)| !matches!(item.item, ReturnItem::Variable(_)))
+                .map(|(item, column)| (item.item.column_name(), column.name.clone()))
+                .collect();
+            for item in projection.order_by.iter_mut().flatten() {
+                resolve_grouping(&mut item.expr, &grouping);
+            }
+        }
+
+        self.scope = visible;
+        if let Some(filter) = &projection.filter {
+            self.bind_expression(filter)?;
+        }
+        for item in projection.order_by.iter().flatten() {
+            self.validate_value_expr(&item.expr)?;
+        }
+        self.scope = projected;
+
+        Ok(columns)
+    }
+}
+
+/// Replaces each part of `expr` that is a grouping item or aggregate of an
+/// aggregating projection with a reference to its output column. `grouping`
+/// pairs the text of each such item with the name of its column.
+fn resolve_grouping(expr: &mut ValueExpr, grouping: &[(String, String)]) {
+    let text = expr.to_string();
+    if let Some((_, column)) = grouping.iter().find(|(item, _)| *item == text) {
+        *expr = ValueExpr::Variable(column.clone());
+        return;
+    }
+    match expr {
+        ValueExpr::Arithmetic { left, right, .. } => {
+            resolve_grouping(left, grouping);
+            resolve_grouping(right, grouping);
+        }
+        ValueExpr::Negate(operand) => resolve_grouping(operand, grouping),
+        ValueExpr::Function { args, .. } => {
+            for arg in args {
+                resolve_grouping(arg, grouping);
+            }
+        }
+        ValueExpr::Case(case) => {
+            let results = case.branches.iter_mut().map(|(_, result)| result);
+            for value in case.operand.iter_mut().chain(results).chain(&mut case.default) {
+                resolve_grouping(value, grouping);
+            }
+        }
+        ValueExpr::Literal(_) | ValueExpr::Property { .. } | ValueExpr::Variable(_) => {}
+    }
+}
+
+/// Returns the data type of a literal, or `None` for NULL.
+fn literal_type(literal: &Literal) -> Option<DataType> {
+    match literal {
+        Literal::String(_) => Some(DataType::String),
+        Literal::Int64(_) => Some(DataType::Int64),
+        Literal::Float64(_) => Some(DataType::Float64),
+        Literal::Bool(_) => Some(DataType::Bool),
+        Literal::List(_) => Some(DataType::List),
+        Literal::Date(_) => Some(DataType::Date),
+        Literal::Timestamp(_) => Some(DataType::Timestamp),
+        Literal::Null | Literal::Parameter(_) => None,
+    }
+}
+
+/// Whether `expr` is a NULL literal, which type-checks as any type.
+fn is_null_literal(expr: &BoundExpression) -> bool {
+    matches!(expr, BoundExpression::Literal { value: Value::Null, .. })
+}
+
+/// Returns the common data type of the elements of a list literal.
+///
+/// Integers and floats mix to FLOAT64, and NULL elements are skipped. The
+/// element type of an empty list is immaterial, as it unwinds to no rows.
+fn list_element_type(items: &[Literal]) -> Result<DataType> {
+    let mut element_type = None;
+    for item in items {
+        if let Some(item_type) = literal_type(item) {
+            element_type = Some(common_type(element_type, item_type)?);
+        }
+    }
+    Ok(element_type.unwrap_or(DataType::Int64))
+}
+
+/// Argument types accepted by numeric functions.
+const NUMERIC: &[DataType] = &[DataType::Float64, DataType::Int64, DataType::Float32];
+/// Argument types `toFloat` converts.
+const CONVERTIBLE: &[DataType] = &[
+    DataType::String,
+    DataType::Int64,
+    DataType::Float32,
+    DataType::Float64,
+];
+/// Argument types `toInteger` and `toString` convert.
+const SCALAR: &[DataType] = &[
+    DataType::String,
+    DataType::Int64,
+    DataType::Float32,
+    DataType::Float64,
+    DataType::Bool,
+];
+/// Argument types of date part functions.
+const TEMPORAL: &[DataType] = &[DataType::Date, DataType::Timestamp];
+
+/// Type-checks the arguments of a scalar function and returns its result
+/// type.
+fn function_type(function: ScalarFunction, args: &[BoundExpression]) -> Result<DataType> {
+    // Checks argument `index` against the accepted types; the first is
+    // reported as expected on a mismatch
+    let check = |index: usize, accepted: &[DataType]| -> Result<DataType> {
+        let arg = &args[index];
+        if is_null_literal(arg) || accepted.contains(&arg.data_type()) {
+            Ok(arg.data_type())
+        } else {
+            Err(BindError::TypeMismatch {
+                expected: accepted[0],
+                actual: arg.data_type(),
+            }
+            .into())
+        }
+    };
+
+    Ok(match function {
+        ScalarFunction::Lower | ScalarFunction::Upper | ScalarFunction::Trim => {
+            check(0, &[DataType::String])?;
+            DataType::String
+        }
+        ScalarFunction::Substring => {
+            check(0, &[DataType::String])?;
+            for index in 1..args.len() {
+                check(index, &[DataType::Int64])?;
+            }
+            DataType::String
+        }
+        ScalarFunction::Replace => {
+            for index in 0..3 {
+                check(index, &[DataType::String])?;
+            }
+            DataType::String
+        }
+        ScalarFunction::Split => {
+            check(0, &[DataType::String])?;
+            check(1, &[DataType::String])?;
+            DataType::List
+        }
+        ScalarFunction::Size => {
+            check(0, &[DataType::String, DataType::List])?;
+            DataType::Int64
+        }
+        ScalarFunction::Abs => match check(0, NUMERIC)? {
+            data_type if data_type.is_numeric() => data_type,
+            _ => DataType::Int64,
+        },
+        ScalarFunction::Round => {
+            check(0, NUMERIC)?;
+            if args.len() > 1 {
+                check(1, &[DataType::Int64])?;
+            }
+            DataType::Float64
+        }
+        ScalarFunction::Floor | ScalarFunction::Ceil | ScalarFunction::Sqrt => {
+            check(0, NUMERIC)?;
+            DataType::Float64
+        }
+        ScalarFunction::Coalesce => {
+            let mut result_type = None;
+            for arg in args.iter().filter(|arg| !is_null_literal(arg)) {
+                result_type = Some(common_type(result_type, arg.data_type())?);
+            }
+            result_type.unwrap_or(DataType::String)
+        }
+        ScalarFunction::ToInteger => {
+            check(0, SCALAR)?;
+            DataType::Int64
+        }
+        ScalarFunction::ToFloat => {
+            check(0, CONVERTIBLE)?;
+            DataType::Float64
+        }
+        ScalarFunction::ToString => {
+            check(0, &[SCALAR, TEMPORAL].concat())?;
+            DataType::String
+        }
+        ScalarFunction::ToBoolean => {
+            check(0, &[DataType::String, DataType::Bool, DataType::Int64])?;
+            DataType::Bool
+        }
+        ScalarFunction::Date => {
+            check(0, &[DataType::String, DataType::Date, DataType::Timestamp])?;
+            DataType::Date
+        }
+        ScalarFunction::Timestamp => {
+            check(0, &[DataType::String, DataType::Timestamp, DataType::Date])?;
+            DataType::Timestamp
+        }
+        ScalarFunction::Year | ScalarFunction::Month | ScalarFunction::Day => {
+            check(0, TEMPORAL)?;
+            DataType::Int64
+        }
+    })
+}
+
+/// Unifies the type of values seen so far with that of the next value.
+///
+/// Integers and floats mix to FLOAT64; other differing types are an error.
+fn common_type(current: Option<DataType>, next: DataType) -> Result<DataType> {
+    match current {
+        None => Ok(next),
+        Some(current) if current == next => Ok(current),
+        Some(current) if current.is_numeric() && next.is_numeric() => Ok(DataType::Float64),
+        Some(current) => Err(BindError::TypeMismatch {
+            expected: current,
+            actual: next,
+        }
+        .into()),
+    }
+}
+
+/// Maps a parsed comparison operator to its bound counterpart.
+fn comparison_op(op: ast::ComparisonOp) -> ComparisonOp {
+    match op {
+        ast::ComparisonOp::Eq => ComparisonOp::Eq,
+        ast::ComparisonOp::Neq => ComparisonOp::Neq,
+        ast::ComparisonOp::Lt => ComparisonOp::Lt,
+        ast::ComparisonOp::Lte => ComparisonOp::Lte,
+        ast::ComparisonOp::Gt => ComparisonOp::Gt,
+        ast::ComparisonOp::Gte => ComparisonOp::Gte,
+        ast::ComparisonOp::StartsWith => ComparisonOp::StartsWith,
+        ast::ComparisonOp::EndsWith => ComparisonOp::EndsWith,
+        ast::ComparisonOp::Contains => ComparisonOp::Contains,
+        ast::ComparisonOp::RegexMatch => ComparisonOp::RegexMatch,
+    }
+}
+
+/// Maps a parsed arithmetic operator to its bound counterpart.
+fn arithmetic_op(op: ArithmeticOp) -> expression::ArithmeticOp {
+    match op {
+        ArithmeticOp::Add => expression::ArithmeticOp::Add,
+        ArithmeticOp::Sub => expression::ArithmeticOp::Sub,
+        ArithmeticOp::Mul => expression::ArithmeticOp::Mul,
+        ArithmeticOp::Div => expression::ArithmeticOp::Div,
+        ArithmeticOp::Mod => expression::ArithmeticOp::Mod,
+    }
+}
+
+/// Maps a parsed aggregate function to its bound counterpart.
+fn aggregate_function(function: AstAggregateFunction) -> AggregateFunction {
+    match function {
+        AstAggregateFunction::Count => AggregateFunction::Count,
+        AstAggregateFunction::Sum => AggregateFunction::Sum,
+        AstAggregateFunction::Avg => AggregateFunction::Avg,
+        AstAggregateFunction::Min => AggregateFunction::Min,
+        AstAggregateFunction::Max => AggregateFunction::Max,
+        AstAggregateFunction::Collect => AggregateFunction::Collect,
+        AstAggregateFunction::StDev => AggregateFunction::StDev,
+        AstAggregateFunction::StDevP => AggregateFunction::StDevP,
+        AstAggregateFunction::PercentileCont => AggregateFunction::PercentileCont,
+        AstAggregateFunction::PercentileDisc => AggregateFunction::PercentileDisc,
+    }
 }
 
 #[cfg(test)]
@@ -6,11 +6,13 @@ use arrow::datatypes::Schema;
 use datafusion::common::ScalarValue;
 use datafusion::error::Result as DfResult;
 use datafusion::logical_expr::Operator;
-use datafusion::physical_expr::expressions::{col, lit, BinaryExpr, IsNullExpr, NotExpr};
+use datafusion::physical_expr::expressions::{
+    case, cast, col, in_list, lit, BinaryExpr, IsNullExpr, LikeExpr, NotExpr,
+};
 use datafusion::physical_expr::PhysicalExpr;
 
 use crate::binder::{ArithmeticOp, BoundExpression, ComparisonOp, LogicalOp};
-use crate::types::Value;
+use crate::types::{DataType, Value};
 
 /// Converter from Cypher expressions to `DataFusion` physical expressions.
 pub struct CypherToDf;
@@ -35,6 +37,9 @@ impl CypherToDf {
                 col(&col_name, schema)
             }
             BoundExpression::VariableRef { variable, .. } => col(variable, schema),
+            BoundExpression::Comparison {
+                left, op, right, ..
+            } if op.is_string_predicate() => Self::string_predicate(left, *op, right, schema),
             BoundExpression::Comparison {
                 left, op, right, ..
             } => {
@@ -79,6 +84,41 @@ impl CypherToDf {
                     Ok(is_null)
                 }
             }
+            BoundExpression::InList {
+                operand,
+                list,
+                negated,
+                ..
+            } => {
+                let BoundExpression::Literal {
+                    value: Value::List(items),
+                    ..
+                } = list.as_ref()
+                else {
+                    return Err(datafusion::error::DataFusionError::NotImplemented(
+                        "IN requires a literal list".to_string(),
+                    ));
+                };
+                let inner = Self::to_physical_expr(operand, schema)?;
+                let items = items.iter().map(Self::value_to_scalar).collect();
+                in_list(inner, items, negated, schema)
+            }
+            BoundExpression::Case {
+                branches,
+                default,
+                data_type,
+            } => Self::case_expr(branches, default.as_deref(), *data_type, schema),
+            BoundExpression::Function { function, .. } => {
+                Err(datafusion::error::DataFusionError::NotImplemented(format!(
+                    "Function {} is evaluated by the vectorized evaluator",
+                    function.name()
+                )))
+            }
+            BoundExpression::Exists { .. } => {
+                Err(datafusion::error::DataFusionError::NotImplemented(
+                    "EXISTS subqueries are executed as semi-joins".to_string(),
+                ))
+            }
         }
     }
 
@@ -92,7 +132,9 @@ impl CypherToDf {
             Value::String(v) => lit(ScalarValue::Utf8(Some(v.clone()))),
             Value::Date(v) => lit(ScalarValue::Date32(Some(*v))),
             Value::Timestamp(v) => lit(ScalarValue::TimestampMicrosecond(Some(*v), None)),
-            Value::Null => lit(ScalarValue::Null),
+            // List values never come from table columns, so they are not
+            // pushed down to DataFusion
+            Value::List(_) | Value::Map(_) | Value::Null => lit(ScalarValue::Null),
         }
     }
 
@@ -105,9 +147,81 @@ impl CypherToDf {
             ComparisonOp::Lte => Operator::LtEq,
             ComparisonOp::Gt => Operator::Gt,
             ComparisonOp::Gte => Operator::GtEq,
+            ComparisonOp::StartsWith
+            | ComparisonOp::EndsWith
+            | ComparisonOp::Contains
+            | ComparisonOp::RegexMatch => unreachable!("string predicates are not binary operators"),
         }
     }
 
+    /// Converts a searched CASE to a `DataFusion` CASE expression.
+    fn case_expr(
+        branches: &[(BoundExpression, BoundExpression)],
+        default: Option<&BoundExpression>,
+        data_type: DataType,
+        schema: &Schema,
+    ) -> DfResult<Arc<dyn PhysicalExpr>> {
+        // Results are cast to the CASE type, as DataFusion does not
+        // unify INT64 and FLOAT64 branches itself
+        let result = |expr: &BoundExpression| {
+            cast(Self::to_physical_expr(expr, schema)?, schema, data_type.to_arrow())
+        };
+        let when_thens = branches
+            .iter()
+            .map(|(condition, then)| Ok((Self::to_physical_expr(condition, schema)?, result(then)?)))
+            .collect::<DfResult<Vec<_>>>()?;
+        let default = default.map(result).transpose()?;
+        case(None, when_thens, default)
+    }
+
+    /// Converts a string predicate with a literal pattern to a LIKE or regex
+    /// match expression.
+    fn string_predicate(
+        left: &BoundExpression,
+        op: ComparisonOp,
+        right: &BoundExpression,
+        schema: &Schema,
+    ) -> DfResult<Arc<dyn PhysicalExpr>> {
+        let BoundExpression::Literal {
+            value: Value::String(pattern),
+            ..
+        } = right
+        else {
+            return Err(datafusion::error::DataFusionError::NotImplemented(format!(
+                "{} requires a literal string pattern",
+                op.as_str()
+            )));
+        };
+        let left_expr = Self::to_physical_expr(left, schema)?;
+        let escaped: String = pattern
+            .chars()
+            .flat_map(|c| match c {
+                '\\' | '%' | '_' => vec!['\\', c],
+                _ => vec![c],
+            })
+            .collect();
+        let like_pattern = match op {
+            ComparisonOp::StartsWith => format!("{escaped}%"),
+            ComparisonOp::EndsWith => format!("%{escaped}"),
+            ComparisonOp::Contains => format!("%{escaped}%"),
+            _ => {
+                // Cypher's =~ must match the whole string
+                let anchored = lit(ScalarValue::Utf8(Some(format!("^(?:{pattern})$"))));
+                return Ok(Arc::new(BinaryExpr::new(
+                    left_expr,
+                    Operator::RegexMatch,
+                    anchored,
+                )));
+            }
+        };
+        Ok(Arc::new(LikeExpr::new(
+            false,
+            false,
+            left_expr,
+            lit(ScalarValue::Utf8(Some(like_pattern))),
+        )))
+    }
+
     /// Converts an arithmetic operator to `DataFusion` operator.
     fn arithmetic_to_df_op(op: ArithmeticOp) -> Operator {
         match op {
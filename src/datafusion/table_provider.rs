@@ -163,6 +163,7 @@ pub fn datatype_to_arrow(dt: DataType) -> ArrowDataType {
         DataType::String => ArrowDataType::Utf8,
         DataType::Date => ArrowDataType::Date32,
         DataType::Timestamp => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+        DataType::List | DataType::Map => dt.to_arrow(),
     }
 }
 
@@ -0,0 +1,245 @@
+//! Hash aggregate operator for aggregating RETURN and WITH projections.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{Result, RuzuError};
+use crate::executor::{evaluate_value, PhysicalOperator};
+use crate::parser::ast::{AggregateExpr, AstAggregateFunction, ValueExpr};
+use crate::types::{Row, Value};
+
+/// Hash aggregate operator implementing Cypher's implicit grouping.
+///
+/// Input rows are grouped on the values of the `group_by` expressions, in
+/// order of first appearance. Each group yields its first input row extended
+/// with the aggregate values under their output column names, so the grouping
+/// columns can be projected from it afterwards.
+///
+/// Without grouping expressions the whole input forms a single group, so
+/// aggregating zero rows still yields one row (e.g. `COUNT(*) = 0`).
+pub struct AggregateOperator<'a> {
+    child: Box<dyn PhysicalOperator + 'a>,
+    /// Grouping key expressions
+    group_by: Vec<ValueExpr>,
+    /// Aggregates as (output column, aggregate) pairs
+    aggregates: Vec<(String, AggregateExpr)>,
+    /// Aggregated rows, computed when the first row is requested
+    output: Option<std::vec::IntoIter<Row>>,
+}
+
+impl<'a> AggregateOperator<'a> {
+    /// Creates a new aggregate operator over `child`.
+    #[must_use]
+    pub fn new(
+        child: Box<dyn PhysicalOperator + 'a>,
+        group_by: Vec<ValueExpr>,
+        aggregates: Vec<(String, AggregateExpr)>,
+    ) -> Self {
+        AggregateOperator {
+            child,
+            group_by,
+            aggregates,
+            output: None,
+        }
+    }
+
+    /// Drains the child and computes one output row per group.
+    fn aggregate(&mut self) -> Result<Vec<Row>> {
+        let mut groups: Vec<Vec<Row>> = Vec::new();
+        let mut group_index: HashMap<Vec<Value>, usize> = HashMap::new();
+        while let Some(row) = self.child.next()? {
+            let key = self
+                .group_by
+                .iter()
+                .map(|expr| evaluate_value(expr, &row))
+                .collect::<Result<Vec<Value>>>()?;
+            match group_index.entry(key) {
+                Entry::Occupied(entry) => groups[*entry.get()].push(row),
+                Entry::Vacant(entry) => {
+                    entry.insert(groups.len());
+                    groups.push(vec![row]);
+                }
+            }
+        }
+        if groups.is_empty() && self.group_by.is_empty() {
+            groups.push(Vec::new());
+        }
+
+        let mut output_rows = Vec::with_capacity(groups.len());
+        for group in groups {
+            let group: Vec<&Row> = group.iter().collect();
+            let mut output = group.first().map(|&first| first.clone()).unwrap_or_default();
+            for (column, agg) in &self.aggregates {
+                output.set(column.clone(), aggregate_value(agg, &group)?);
+            }
+            output_rows.push(output);
+        }
+        Ok(output_rows)
+    }
+}
+
+impl PhysicalOperator for AggregateOperator<'_> {
+    fn next(&mut self) -> Result<Option<Row>> {
+        if self.output.is_none() {
+            self.output = Some(self.aggregate()?.into_iter());
+        }
+        Ok(self.output.as_mut().and_then(Iterator::next))
+    }
+}
+
+/// Computes an aggregate function over a group of rows.
+///
+/// NULL inputs are skipped by every function but `COUNT(*)`; with `DISTINCT`
+/// each remaining value is aggregated once.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be evaluated, a numeric aggregate
+/// sees a non-numeric value, or a percentile is not between 0 and 1.
+pub fn aggregate_value(agg: &AggregateExpr, rows: &[&Row]) -> Result<Value> {
+    let Some(input) = &agg.input else {
+        // COUNT(*)
+        return Ok(Value::Int64(i64::try_from(rows.len()).unwrap_or(i64::MAX)));
+    };
+
+    let mut values = Vec::with_capacity(rows.len());
+    let mut seen = HashSet::new();
+    for row in rows {
+        let value = evaluate_value(input, row)?;
+        if !value.is_null() && (!agg.distinct || seen.insert(value.clone())) {
+            values.push(value);
+        }
+    }
+
+    let value = match agg.function {
+        AstAggregateFunction::Count => {
+            Value::Int64(i64::try_from(values.len()).unwrap_or(i64::MAX))
+        }
+        AstAggregateFunction::Sum => {
+            if values.iter().all(|v| matches!(v, Value::Int64(_))) {
+                Value::Int64(values.iter().filter_map(Value::as_int64).sum())
+            } else {
+                Value::Float64(numeric_values(agg, &values)?.iter().sum())
+            }
+        }
+        AstAggregateFunction::Avg => {
+            let values = numeric_values(agg, &values)?;
+            if values.is_empty() {
+                Value::Null
+            } else {
+                Value::Float64(mean(&values))
+            }
+        }
+        AstAggregateFunction::Min => extreme(values, std::cmp::Ordering::Less),
+        AstAggregateFunction::Max => extreme(values, std::cmp::Ordering::Greater),
+        AstAggregateFunction::Collect => Value::List(values),
+        AstAggregateFunction::StDev | AstAggregateFunction::StDevP => {
+            let values = numeric_values(agg, &values)?;
+            // Fewer values than degrees of freedom have no spread
+            let dof = usize::from(agg.function == AstAggregateFunction::StDev);
+            if values.len() <= dof {
+                Value::Float64(0.0)
+            } else {
+                let mean = mean(&values);
+                let squares: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+                #[allow(clippy::cast_precision_loss)]
+                let variance = squares / (values.len() - dof) as f64;
+                Value::Float64(variance.sqrt())
+            }
+        }
+        AstAggregateFunction::PercentileCont => {
+            let percentile = percentile(agg)?;
+            let mut values = numeric_values(agg, &values)?;
+            values.sort_by(f64::total_cmp);
+            if values.is_empty() {
+                Value::Null
+            } else {
+                #[allow(clippy::cast_precision_loss)]
+                let position = percentile * (values.len() - 1) as f64;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+                #[allow(clippy::cast_precision_loss)]
+                let fraction = position - lower as f64;
+                Value::Float64(values[lower] + (values[upper] - values[lower]) * fraction)
+            }
+        }
+        AstAggregateFunction::PercentileDisc => {
+            let percentile = percentile(agg)?;
+            numeric_values(agg, &values)?;
+            values.sort_by(|a, b| a.compare(b).unwrap_or(std::cmp::Ordering::Equal));
+            if values.is_empty() {
+                Value::Null
+            } else {
+                // Smallest value whose cumulative share reaches the percentile
+                #[allow(
+                    clippy::cast_precision_loss,
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss
+                )]
+                let rank = (percentile * values.len() as f64).ceil() as usize;
+                values.swap_remove(rank.saturating_sub(1))
+            }
+        }
+    };
+    Ok(value)
+}
+
+/// Converts the non-null inputs of a numeric aggregate to `f64`.
+fn numeric_values(agg: &AggregateExpr, values: &[Value]) -> Result<Vec<f64>> {
+    values
+        .iter()
+        .map(|value| match value {
+            #[allow(clippy::cast_precision_loss)]
+            Value::Int64(n) => Ok(*n as f64),
+            Value::Float32(f) => Ok(f64::from(*f)),
+            Value::Float64(f) => Ok(*f),
+            other => Err(RuzuError::TypeError {
+                expected: format!("numeric input to {}", agg.function.name()),
+                actual: other.data_type().map_or("NULL", |t| t.name()).to_string(),
+            }),
+        })
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let count = values.len() as f64;
+    values.iter().sum::<f64>() / count
+}
+
+/// Returns the value that compares `ordering` to every other value, or NULL
+/// for no values.
+fn extreme(values: Vec<Value>, ordering: std::cmp::Ordering) -> Value {
+    let mut values = values.into_iter();
+    let Some(mut best) = values.next() else {
+        return Value::Null;
+    };
+    for value in values {
+        if value.compare(&best) == Some(ordering) {
+            best = value;
+        }
+    }
+    best
+}
+
+/// Evaluates the percentile argument, which must be a number from 0 to 1.
+fn percentile(agg: &AggregateExpr) -> Result<f64> {
+    let value = match &agg.percentile {
+        Some(expr) => evaluate_value(expr, &Row::new())?,
+        None => Value::Null,
+    };
+    let percentile = match value {
+        #[allow(clippy::cast_precision_loss)]
+        Value::Int64(n) => n as f64,
+        Value::Float64(f) => f,
+        _ => f64::NAN,
+    };
+    if (0.0..=1.0).contains(&percentile) {
+        Ok(percentile)
+    } else {
+        Err(RuzuError::ExecutionError(format!(
+            "{} percentile must be a number between 0 and 1",
+            agg.function.name()
+        )))
+    }
+}
@@ -7,17 +7,23 @@ use std::sync::Arc;
 
 use crate::catalog::{Direction, RelTableSchema};
 use crate::error::{Result, RuzuError};
-use crate::storage::RelTable;
+use crate::storage::{NodeTable, RelTable};
 use crate::types::{Row, Value};
 
 use super::PhysicalOperator;
 
 /// Extend operator for graph traversal.
-pub struct ExtendOperator {
+///
+/// If the destination variable is already bound in an input row (for
+/// example when a path closes a cycle), only edges leading to that node are
+/// followed; a NULL binding matches no edge.
+pub struct ExtendOperator<'a> {
     /// Input operator producing source rows.
-    input: Box<dyn PhysicalOperator>,
+    input: Box<dyn PhysicalOperator + 'a>,
     /// Relationship table to traverse.
-    rel_table: Arc<RelTable>,
+    rel_table: &'a RelTable,
+    /// Node table of the destination nodes, whose properties are bound when set.
+    dst_table: Option<&'a NodeTable>,
     /// Schema for the relationship table.
     rel_schema: Arc<RelTableSchema>,
     /// Variable name for the source node.
@@ -28,6 +34,9 @@ pub struct ExtendOperator {
     rel_variable: Option<String>,
     /// Direction of traversal.
     direction: Direction,
+    /// Columns holding IDs of relationships of the same table bound earlier
+    /// in the pattern, which must not be traversed again.
+    unique_rel_columns: Vec<String>,
     /// Current input row being processed.
     current_input_row: Option<Row>,
     /// Current edges for the current input row.
@@ -36,12 +45,12 @@ pub struct ExtendOperator {
     edge_index: usize,
 }
 
-impl ExtendOperator {
+impl<'a> ExtendOperator<'a> {
     /// Creates a new extend operator.
     #[must_use]
     pub fn new(
-        input: Box<dyn PhysicalOperator>,
-        rel_table: Arc<RelTable>,
+        input: Box<dyn PhysicalOperator + 'a>,
+        rel_table: &'a RelTable,
         rel_schema: Arc<RelTableSchema>,
         src_variable: String,
         dst_variable: String,
@@ -51,17 +60,51 @@ impl ExtendOperator {
         Self {
             input,
             rel_table,
+            dst_table: None,
             rel_schema,
             src_variable,
             dst_variable,
             rel_variable,
             direction,
+            unique_rel_columns: Vec::new(),
             current_input_row: None,
             current_edges: Vec::new(),
             edge_index: 0,
         }
     }
 
+    /// Also binds every property of the destination node as `dst.prop`.
+    #[must_use]
+    pub fn with_dst_table(mut self, dst_table: &'a NodeTable) -> Self {
+        self.dst_table = Some(dst_table);
+        self
+    }
+
+    /// Skips relationships whose ID is bound to any of `columns`, so that a
+    /// pattern never matches the same relationship twice.
+    #[must_use]
+    pub fn with_unique_rels(mut self, columns: Vec<String>) -> Self {
+        self.unique_rel_columns = columns;
+        self
+    }
+
+    /// Whether the relationship `rel_id` was already matched by the row.
+    fn rel_used(&self, row: &Row, rel_id: u64) -> bool {
+        self.unique_rel_columns.iter().any(|column| {
+            matches!(row.get(column), Some(Value::Int64(id)) if u64::try_from(*id) == Ok(rel_id))
+        })
+    }
+
+    /// Whether an edge to `dst_node_id` is compatible with the destination
+    /// binding already present in `row`.
+    fn dst_matches(&self, row: &Row, dst_node_id: u64) -> bool {
+        match row.get(&format!("{}._id", self.dst_variable)) {
+            None => true,
+            Some(Value::Int64(id)) => u64::try_from(*id).is_ok_and(|id| id == dst_node_id),
+            Some(_) => false,
+        }
+    }
+
     fn get_src_node_id(&self, row: &Row) -> Result<u64> {
         let id_col = format!("{}._id", self.src_variable);
         if let Some(Value::Int64(id)) = row.get(&id_col) {
@@ -94,18 +137,6 @@ impl ExtendOperator {
         )))
     }
 
-    fn fetch_edges(&self, node_id: u64) -> Vec<(u64, u64)> {
-        match self.direction {
-            Direction::Forward => self.rel_table.get_forward_edges(node_id),
-            Direction::Backward => self.rel_table.get_backward_edges(node_id),
-            Direction::Both => {
-                let mut edges = self.rel_table.get_forward_edges(node_id);
-                edges.extend(self.rel_table.get_backward_edges(node_id));
-                edges
-            }
-        }
-    }
-
     fn create_output_row(&self, input_row: &Row, dst_node_id: u64, rel_id: u64) -> Result<Row> {
         let mut output = input_row.clone();
         let dst_id_col = format!("{}._id", self.dst_variable);
@@ -114,6 +145,17 @@ impl ExtendOperator {
         })?;
         output.insert(dst_id_col, Value::Int64(dst_id_i64));
 
+        if let Some(dst_table) = self.dst_table {
+            let offset = usize::try_from(dst_node_id).map_err(|_| {
+                RuzuError::ExecutionError(format!("Node ID {dst_node_id} exceeds usize range"))
+            })?;
+            for (col_idx, col) in dst_table.schema().columns.iter().enumerate() {
+                if let Some(value) = dst_table.get_column(col_idx).and_then(|c| c.get(offset)) {
+                    output.insert(format!("{}.{}", self.dst_variable, col.name), value.clone());
+                }
+            }
+        }
+
         if let Some(ref rel_var) = self.rel_variable {
             let rel_id_col = format!("{rel_var}._id");
             let rel_id_i64 = i64::try_from(rel_id).map_err(|_| {
@@ -134,7 +176,7 @@ impl ExtendOperator {
     }
 }
 
-impl PhysicalOperator for ExtendOperator {
+impl PhysicalOperator for ExtendOperator<'_> {
     fn next(&mut self) -> Result<Option<Row>> {
         loop {
             if self.edge_index < self.current_edges.len() {
@@ -142,14 +184,18 @@ impl PhysicalOperator for ExtendOperator {
                 self.edge_index += 1;
 
                 if let Some(ref input_row) = self.current_input_row {
-                    return Ok(Some(self.create_output_row(input_row, dst_node_id, rel_id)?));
+                    if self.dst_matches(input_row, dst_node_id) && !self.rel_used(input_row, rel_id) {
+                        return Ok(Some(self.create_output_row(input_row, dst_node_id, rel_id)?));
+                    }
                 }
+                // Try the remaining edges of this row before the next one
+                continue;
             }
 
             match self.input.next()? {
                 Some(input_row) => {
                     let src_node_id = self.get_src_node_id(&input_row)?;
-                    self.current_edges = self.fetch_edges(src_node_id);
+                    self.current_edges = self.rel_table.get_edges(src_node_id, self.direction);
                     self.edge_index = 0;
                     self.current_input_row = Some(input_row);
                 }
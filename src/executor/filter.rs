@@ -6,20 +6,20 @@ use crate::parser::ast::Expression;
 use crate::types::Row;
 
 /// Filter operator for WHERE clause evaluation.
-pub struct FilterOperator {
-    child: Box<dyn PhysicalOperator>,
+pub struct FilterOperator<'a> {
+    child: Box<dyn PhysicalOperator + 'a>,
     predicate: Expression,
 }
 
-impl FilterOperator {
+impl<'a> FilterOperator<'a> {
     /// Creates a new filter operator with the given child and predicate.
     #[must_use]
-    pub fn new(child: Box<dyn PhysicalOperator>, predicate: Expression) -> Self {
+    pub fn new(child: Box<dyn PhysicalOperator + 'a>, predicate: Expression) -> Self {
         FilterOperator { child, predicate }
     }
 }
 
-impl PhysicalOperator for FilterOperator {
+impl PhysicalOperator for FilterOperator<'_> {
     fn next(&mut self) -> Result<Option<Row>> {
         while let Some(row) = self.child.next()? {
             if evaluate_expression(&self.predicate, &row)? {
@@ -0,0 +1,267 @@
+//! Row-at-a-time evaluation of scalar functions.
+//!
+//! The string and number helpers here are shared with the vectorized
+//! evaluator, so both paths agree on edge cases.
+
+use chrono::Datelike;
+
+use crate::binder::ScalarFunction;
+use crate::error::{Result, RuzuError};
+use crate::types::{temporal, Value};
+
+/// Applies a scalar function to its evaluated arguments.
+///
+/// A NULL argument yields NULL, except in `coalesce`, which skips NULLs.
+///
+/// # Errors
+///
+/// Returns an error if an argument has the wrong type, the number of
+/// arguments is wrong, or the function fails (e.g. a negative `substring`
+/// start or an overflowing `abs`).
+pub fn evaluate_function(function: ScalarFunction, args: &[Value]) -> Result<Value> {
+    if !function.accepts_arity(args.len()) {
+        return Err(RuzuError::ExecutionError(format!(
+            "Wrong number of arguments to {}: {}",
+            function.name(),
+            args.len()
+        )));
+    }
+    if function == ScalarFunction::Coalesce {
+        return Ok(args.iter().find(|arg| !arg.is_null()).cloned().unwrap_or(Value::Null));
+    }
+    if args.iter().any(Value::is_null) {
+        return Ok(Value::Null);
+    }
+
+    let string = |index: usize| match &args[index] {
+        Value::String(s) => Ok(s.as_str()),
+        other => Err(argument_error(function, "STRING", other)),
+    };
+    let integer = |index: usize| match &args[index] {
+        Value::Int64(n) => Ok(*n),
+        other => Err(argument_error(function, "INT64", other)),
+    };
+    let number = |index: usize| match &args[index] {
+        #[allow(clippy::cast_precision_loss)]
+        Value::Int64(n) => Ok(*n as f64),
+        Value::Float32(f) => Ok(f64::from(*f)),
+        Value::Float64(f) => Ok(*f),
+        other => Err(argument_error(function, "numeric", other)),
+    };
+
+    Ok(match function {
+        ScalarFunction::Lower => Value::String(string(0)?.to_lowercase()),
+        ScalarFunction::Upper => Value::String(string(0)?.to_uppercase()),
+        ScalarFunction::Trim => Value::String(string(0)?.trim().to_string()),
+        ScalarFunction::Substring => {
+            let length = if args.len() > 2 { Some(integer(2)?) } else { None };
+            Value::String(substring(string(0)?, integer(1)?, length)?)
+        }
+        ScalarFunction::Size => {
+            let size = match &args[0] {
+                Value::String(s) => s.chars().count(),
+                Value::List(items) => items.len(),
+                other => return Err(argument_error(function, "STRING or LIST", other)),
+            };
+            Value::Int64(i64::try_from(size).unwrap_or(i64::MAX))
+        }
+        ScalarFunction::Replace => Value::String(string(0)?.replace(string(1)?, string(2)?)),
+        ScalarFunction::Split => Value::List(
+            string(0)?
+                .split(string(1)?)
+                .map(|part| Value::String(part.to_string()))
+                .collect(),
+        ),
+        ScalarFunction::Abs => match &args[0] {
+            Value::Int64(n) => Value::Int64(checked_abs(*n)?),
+            Value::Float32(f) => Value::Float32(f.abs()),
+            Value::Float64(f) => Value::Float64(f.abs()),
+            other => return Err(argument_error(function, "numeric", other)),
+        },
+        ScalarFunction::Round => {
+            let precision = if args.len() > 1 { integer(1)? } else { 0 };
+            Value::Float64(round(number(0)?, precision))
+        }
+        ScalarFunction::Floor => Value::Float64(number(0)?.floor()),
+        ScalarFunction::Ceil => Value::Float64(number(0)?.ceil()),
+        ScalarFunction::Sqrt => Value::Float64(number(0)?.sqrt()),
+        ScalarFunction::ToInteger => match &args[0] {
+            Value::Int64(n) => Value::Int64(*n),
+            Value::Float32(_) | Value::Float64(_) => {
+                float_to_integer(number(0)?).map_or(Value::Null, Value::Int64)
+            }
+            Value::Bool(b) => Value::Int64(i64::from(*b)),
+            Value::String(s) => parse_integer(s).map_or(Value::Null, Value::Int64),
+            other => return Err(argument_error(function, "STRING, numeric or BOOL", other)),
+        },
+        ScalarFunction::ToFloat => match &args[0] {
+            Value::String(s) => parse_float(s).map_or(Value::Null, Value::Float64),
+            _ => Value::Float64(number(0)?),
+        },
+        ScalarFunction::ToString => Value::String(value_to_string(&args[0]).ok_or_else(|| {
+            argument_error(function, "STRING, numeric, BOOL or temporal", &args[0])
+        })?),
+        ScalarFunction::ToBoolean => match &args[0] {
+            Value::Bool(b) => Value::Bool(*b),
+            Value::Int64(n) => Value::Bool(*n != 0),
+            Value::String(s) => parse_boolean(s).map_or(Value::Null, Value::Bool),
+            other => return Err(argument_error(function, "STRING, INT64 or BOOL", other)),
+        },
+        ScalarFunction::Date
+        | ScalarFunction::Timestamp
+        | ScalarFunction::Year
+        | ScalarFunction::Month
+        | ScalarFunction::Day => temporal_function(function, &args[0])?,
+        ScalarFunction::Coalesce => unreachable!("coalesce is handled before NULL propagation"),
+    })
+}
+
+/// Formats a scalar value as `toString` does; None for lists and maps.
+fn value_to_string(value: &Value) -> Option<String> {
+    Some(match value {
+        Value::String(s) => s.clone(),
+        Value::Int64(n) => n.to_string(),
+        Value::Float32(f) => float_to_string(f64::from(*f)),
+        Value::Float64(f) => float_to_string(*f),
+        Value::Bool(b) => b.to_string(),
+        Value::Date(days) => temporal::format_date(*days),
+        Value::Timestamp(micros) => temporal::format_timestamp(*micros),
+        Value::List(_) | Value::Map(_) | Value::Null => return None,
+    })
+}
+
+/// Applies a DATE or TIMESTAMP conversion, or extracts a date part, from a
+/// non-NULL argument.
+fn temporal_function(function: ScalarFunction, arg: &Value) -> Result<Value> {
+    Ok(match (function, arg) {
+        (ScalarFunction::Date, Value::String(s)) => {
+            Value::Date(parse_temporal(function, s, temporal::parse_date)?)
+        }
+        (ScalarFunction::Date, Value::Date(days)) => Value::Date(*days),
+        (ScalarFunction::Date, Value::Timestamp(micros)) => {
+            Value::Date(temporal::timestamp_to_days(*micros))
+        }
+        (ScalarFunction::Timestamp, Value::String(s)) => {
+            Value::Timestamp(parse_temporal(function, s, temporal::parse_timestamp)?)
+        }
+        (ScalarFunction::Timestamp, Value::Timestamp(micros)) => Value::Timestamp(*micros),
+        (ScalarFunction::Timestamp, Value::Date(days)) => {
+            Value::Timestamp(temporal::days_to_timestamp(*days))
+        }
+        (ScalarFunction::Date | ScalarFunction::Timestamp, other) => {
+            return Err(argument_error(function, "STRING, DATE or TIMESTAMP", other))
+        }
+        (_, Value::Date(days)) => date_part(function, *days).map_or(Value::Null, Value::Int64),
+        (_, Value::Timestamp(micros)) => date_part(function, temporal::timestamp_to_days(*micros))
+            .map_or(Value::Null, Value::Int64),
+        (_, other) => return Err(argument_error(function, "DATE or TIMESTAMP", other)),
+    })
+}
+
+/// Parses a DATE or TIMESTAMP string with `parse`.
+///
+/// # Errors
+///
+/// Returns an error if the string is not a valid date or timestamp.
+pub(crate) fn parse_temporal<T>(
+    function: ScalarFunction,
+    s: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<T> {
+    parse(s).ok_or_else(|| {
+        RuzuError::ExecutionError(format!("Invalid {} string: '{s}'", function.name()))
+    })
+}
+
+/// Extracts the year, month or day of a date (days since the epoch).
+pub(crate) fn date_part(function: ScalarFunction, days: i32) -> Option<i64> {
+    let date = temporal::date_from_days(days)?;
+    match function {
+        ScalarFunction::Year => Some(i64::from(date.year())),
+        ScalarFunction::Month => Some(i64::from(date.month())),
+        ScalarFunction::Day => Some(i64::from(date.day())),
+        _ => None,
+    }
+}
+
+/// Error for an argument of the wrong type.
+pub(crate) fn argument_error(function: ScalarFunction, expected: &str, actual: &Value) -> RuzuError {
+    RuzuError::TypeError {
+        expected: format!("{expected} argument to {}", function.name()),
+        actual: actual.data_type().map_or("NULL", |t| t.name()).to_string(),
+    }
+}
+
+/// Returns up to `length` characters of `s` starting at character `start`
+/// (0-based); a start past the end yields an empty string.
+///
+/// # Errors
+///
+/// Returns an error if `start` or `length` is negative.
+pub(crate) fn substring(s: &str, start: i64, length: Option<i64>) -> Result<String> {
+    let start = usize::try_from(start).map_err(|_| {
+        RuzuError::ExecutionError(format!("substring start must not be negative: {start}"))
+    })?;
+    let chars = s.chars().skip(start);
+    match length {
+        Some(length) => {
+            let length = usize::try_from(length).map_err(|_| {
+                RuzuError::ExecutionError(format!("substring length must not be negative: {length}"))
+            })?;
+            Ok(chars.take(length).collect())
+        }
+        None => Ok(chars.collect()),
+    }
+}
+
+/// Absolute value of an integer.
+///
+/// # Errors
+///
+/// Returns an error for `i64::MIN`, whose absolute value overflows.
+pub(crate) fn checked_abs(n: i64) -> Result<i64> {
+    n.checked_abs()
+        .ok_or_else(|| RuzuError::ExecutionError("Integer overflow in abs".into()))
+}
+
+/// Rounds half away from zero to `precision` decimal places (negative
+/// precision rounds to tens, hundreds, ...).
+pub(crate) fn round(x: f64, precision: i64) -> f64 {
+    let exponent = i32::try_from(precision.clamp(-308, 308)).unwrap_or(0);
+    let factor = 10f64.powi(exponent);
+    (x * factor).round() / factor
+}
+
+/// Parses an integer, also accepting a decimal number, which is truncated.
+pub(crate) fn parse_integer(s: &str) -> Option<i64> {
+    let s = s.trim();
+    s.parse().ok().or_else(|| parse_float(s).and_then(float_to_integer))
+}
+
+/// Parses a finite floating-point number.
+pub(crate) fn parse_float(s: &str) -> Option<f64> {
+    s.trim().parse::<f64>().ok().filter(|f| f.is_finite())
+}
+
+/// Parses `true` or `false`, ignoring case.
+pub(crate) fn parse_boolean(s: &str) -> Option<bool> {
+    match s.trim() {
+        s if s.eq_ignore_ascii_case("true") => Some(true),
+        s if s.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    }
+}
+
+/// Truncates a float to an integer; None if it is not finite or out of range.
+pub(crate) fn float_to_integer(f: f64) -> Option<i64> {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    let in_range = f.is_finite() && f >= i64::MIN as f64 && f < i64::MAX as f64;
+    #[allow(clippy::cast_possible_truncation)]
+    in_range.then(|| f.trunc() as i64)
+}
+
+/// Formats a float the way Cypher prints it, always with a fraction or
+/// exponent (`1.0`, `2.5`).
+pub(crate) fn float_to_string(f: f64) -> String {
+    format!("{f:?}")
+}
@@ -0,0 +1,202 @@
+//! Join operators for combining patterns.
+//!
+//! The hash join materializes the rows of one side into a hash table keyed
+//! on the join key values and streams the rows of the other side through
+//! it. The mark join probes a pattern once per input row for EXISTS.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::error::Result;
+use crate::parser::ast::ValueExpr;
+use crate::types::{Row, Value};
+
+use super::{evaluate_value, promote_for_comparison, PhysicalOperator};
+
+/// Inner equi-join of a streamed probe side with materialized build rows.
+///
+/// Each output row is a probe row extended with the columns of a build row
+/// whose key values equal the probe row's, compared as `=` compares them:
+/// numbers of different types can match, and NULL keys match nothing.
+/// Without keys every probe row is paired with every build row.
+pub struct HashJoinOperator<'a> {
+    /// Input operator producing the probe rows.
+    probe: Box<dyn PhysicalOperator + 'a>,
+    /// Key expressions evaluated against probe rows.
+    probe_keys: Vec<ValueExpr>,
+    /// Build rows with their key values, grouped by hashed key.
+    table: HashMap<Vec<Value>, Vec<(Vec<Value>, Row)>>,
+    /// Current probe row, its key values and hashed key.
+    current: Option<(Row, Vec<Value>, Vec<Value>)>,
+    /// Index into the build rows of the current hashed key.
+    match_index: usize,
+}
+
+impl<'a> HashJoinOperator<'a> {
+    /// Creates a hash join, building its hash table from `build` rows keyed
+    /// on `build_keys`, which pair up with `probe_keys` by position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a build key cannot be evaluated.
+    pub fn new(
+        probe: Box<dyn PhysicalOperator + 'a>,
+        probe_keys: Vec<ValueExpr>,
+        build: Vec<Row>,
+        build_keys: &[ValueExpr],
+    ) -> Result<Self> {
+        let mut table: HashMap<Vec<Value>, Vec<(Vec<Value>, Row)>> = HashMap::new();
+        for row in build {
+            if let Some((keys, hashed)) = Self::keys(build_keys, &row)? {
+                table.entry(hashed).or_default().push((keys, row));
+            }
+        }
+        Ok(HashJoinOperator {
+            probe,
+            probe_keys,
+            table,
+            current: None,
+            match_index: 0,
+        })
+    }
+
+    /// Evaluates the key values of a row and their hashed form, or None if
+    /// a key is NULL.
+    ///
+    /// Values that compare equal must hash alike, so every number is hashed
+    /// as an `f32` (which may pair up some unequal numbers; candidates are
+    /// compared exactly before they are joined).
+    fn keys(exprs: &[ValueExpr], row: &Row) -> Result<Option<(Vec<Value>, Vec<Value>)>> {
+        let mut keys = Vec::with_capacity(exprs.len());
+        for expr in exprs {
+            let value = evaluate_value(expr, row)?;
+            if value.is_null() {
+                return Ok(None);
+            }
+            keys.push(value);
+        }
+        let hashed = keys.iter().map(hash_key).collect();
+        Ok(Some((keys, hashed)))
+    }
+}
+
+/// Maps a key value to a hashable form shared by all values equal to it.
+///
+/// Integers and integral floats hash as INT64, so `1 = 1.0` keys meet, and
+/// other floats by the bits of their FLOAT64 value.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn hash_key(value: &Value) -> Value {
+    let float = |f: f64| {
+        // i64::MAX rounds up to 2^63, which is out of range
+        if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+            Value::Int64(f as i64)
+        } else {
+            Value::Float64(f)
+        }
+    };
+    match value {
+        Value::Float64(f) => float(*f),
+        Value::Float32(f) => float(f64::from(*f)),
+        Value::List(items) => Value::List(items.iter().map(hash_key).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Whether two key values are equal under `=`.
+fn keys_equal(left: &[Value], right: &[Value]) -> bool {
+    left.iter().zip(right).all(|(left, right)| {
+        let (left, right) = promote_for_comparison(left.clone(), right.clone());
+        left.compare(&right) == Some(Ordering::Equal)
+    })
+}
+
+impl PhysicalOperator for HashJoinOperator<'_> {
+    fn next(&mut self) -> Result<Option<Row>> {
+        loop {
+            if let Some((probe_row, keys, hashed)) = &self.current {
+                let candidates = self.table.get(hashed).map_or(&[][..], Vec::as_slice);
+                while let Some((build_keys, build_row)) = candidates.get(self.match_index) {
+                    self.match_index += 1;
+                    if keys_equal(keys, build_keys) {
+                        let mut row = probe_row.clone();
+                        for (column, value) in build_row.iter() {
+                            row.set(column.clone(), value.clone());
+                        }
+                        return Ok(Some(row));
+                    }
+                }
+            }
+
+            let Some(row) = self.probe.next()? else {
+                return Ok(None);
+            };
+            self.current = Self::keys(&self.probe_keys, &row)?
+                .map(|(keys, hashed)| (row, keys, hashed));
+            self.match_index = 0;
+        }
+    }
+}
+
+/// Existence test run by a mark join against each input row.
+type ExistenceTest<'a> = Box<dyn Fn(&Row) -> Result<bool> + 'a>;
+
+/// Mark join of streamed input rows with a correlated existence test.
+///
+/// Each input row is passed through with `column` set to whether `exists`
+/// holds for it. A filter on the mark above this operator makes it a
+/// semi-join, and a filter on its negation an anti-join.
+pub struct MarkJoinOperator<'a> {
+    /// Input operator.
+    input: Box<dyn PhysicalOperator + 'a>,
+    /// Column receiving the mark.
+    column: String,
+    /// Existence test run against each input row.
+    exists: ExistenceTest<'a>,
+}
+
+impl<'a> MarkJoinOperator<'a> {
+    /// Creates a mark join setting `column` to the result of `exists`.
+    #[must_use]
+    pub fn new(
+        input: Box<dyn PhysicalOperator + 'a>,
+        column: String,
+        exists: ExistenceTest<'a>,
+    ) -> Self {
+        MarkJoinOperator {
+            input,
+            column,
+            exists,
+        }
+    }
+}
+
+impl PhysicalOperator for MarkJoinOperator<'_> {
+    fn next(&mut self) -> Result<Option<Row>> {
+        let Some(mut row) = self.input.next()? else {
+            return Ok(None);
+        };
+        let mark = (self.exists)(&row)?;
+        row.set(self.column.clone(), Value::Bool(mark));
+        Ok(Some(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_key_keeps_large_integers_apart() {
+        let id = 1_i64 << 40;
+        assert_ne!(hash_key(&Value::Int64(id)), hash_key(&Value::Int64(id + 1)));
+        assert_ne!(hash_key(&Value::Float64(0.1)), hash_key(&Value::Float64(0.1 + 1e-12)));
+    }
+
+    #[test]
+    fn test_hash_key_is_shared_by_equal_numbers() {
+        assert_eq!(hash_key(&Value::Int64(7)), hash_key(&Value::Float64(7.0)));
+        assert_eq!(hash_key(&Value::Int64(7)), hash_key(&Value::Float32(7.0)));
+        assert_eq!(hash_key(&Value::Float64(-0.0)), hash_key(&Value::Float64(0.0)));
+        assert_eq!(hash_key(&Value::Float32(0.5)), hash_key(&Value::Float64(0.5)));
+    }
+}
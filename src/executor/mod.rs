@@ -4,13 +4,19 @@
 //! It includes both traditional row-based operators and vectorized
 //! batch operators using Apache Arrow.
 
+mod aggregate;
 mod extend;
 mod filter;
+pub(crate) mod function;
+mod join;
 mod project;
 mod scan;
+mod values;
 pub mod vectorized;
 
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use arrow::record_batch::RecordBatch;
@@ -19,18 +25,26 @@ use datafusion::execution::runtime_env::RuntimeEnvBuilder;
 use datafusion::physical_plan::ExecutionPlan;
 use datafusion::prelude::SessionConfig;
 use futures::StreamExt;
+use regex::Regex;
 
+use crate::binder::ScalarFunction;
 use crate::error::{Result, RuzuError};
-use crate::parser::ast::{ComparisonOp, Expression, Literal};
+use crate::parser::ast::{
+    ArithmeticOp, CaseExpr, CaseWhen, ComparisonOp, Expression, Literal, ValueExpr,
+};
 use crate::planner::LogicalPlan;
 use crate::types::{Row, Value};
 
 use self::vectorized::DEFAULT_BATCH_SIZE;
 
+pub use aggregate::{aggregate_value, AggregateOperator};
 pub use extend::ExtendOperator;
 pub use filter::FilterOperator;
+pub use function::evaluate_function;
+pub use join::{HashJoinOperator, MarkJoinOperator};
 pub use project::ProjectOperator;
 pub use scan::ScanOperator;
+pub use values::ValuesOperator;
 
 /// Configuration for the query executor.
 #[derive(Debug, Clone)]
@@ -258,58 +272,390 @@ pub trait PhysicalOperator {
     fn next(&mut self) -> Result<Option<Row>>;
 }
 
-/// Promotes values for cross-type comparison (Int64 vs Float64).
-#[allow(clippy::cast_precision_loss)]
-fn promote_for_comparison(a: Value, b: Value) -> (Value, Value) {
+/// Promotes values for cross-type numeric comparison.
+///
+/// FLOAT32 against FLOAT64 compares at FLOAT32 precision, so a FLOAT32
+/// property equals the literal it was stored from; other mixes compare as
+/// FLOAT64.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(crate) fn promote_for_comparison(a: Value, b: Value) -> (Value, Value) {
     match (&a, &b) {
-        (Value::Int64(n), Value::Float64(_)) => (Value::Float64(*n as f64), b),
-        (Value::Float64(_), Value::Int64(n)) => (a, Value::Float64(*n as f64)),
+        (Value::Float32(_), Value::Float64(f)) => (a, Value::Float32(*f as f32)),
+        (Value::Float64(f), Value::Float32(_)) => (Value::Float32(*f as f32), b),
+        (Value::Int64(n), Value::Float64(_) | Value::Float32(_)) => (
+            Value::Float64(*n as f64),
+            Value::Float64(numeric_as_f64(&b).unwrap_or_default()),
+        ),
+        (Value::Float64(_) | Value::Float32(_), Value::Int64(n)) => (
+            Value::Float64(numeric_as_f64(&a).unwrap_or_default()),
+            Value::Float64(*n as f64),
+        ),
         _ => (a, b),
     }
 }
 
 /// Evaluates an expression against a row.
 ///
+/// Returns true only when the predicate holds; a predicate that evaluates to
+/// NULL (unknown) does not.
+///
 /// # Errors
 ///
-/// This function currently does not return errors, but the signature is
-/// designed for future extension with type checking.
+/// Returns an error if evaluating an operand fails, or if a value used as a
+/// predicate is not a boolean.
 pub fn evaluate_expression(expr: &Expression, row: &Row) -> Result<bool> {
-    // Get the value from the row using the full qualified name (var.property)
-    let column_name = format!("{}.{}", expr.var, expr.property);
-    let value = row.get(&column_name);
+    Ok(evaluate_predicate(expr, row)? == Some(true))
+}
 
-    let Some(value) = value else {
-        // Column not found, return false
-        return Ok(false);
+/// Evaluates an expression against a row under three-valued logic.
+///
+/// Comparisons involving NULL, a missing column or incomparable types are
+/// unknown (`None`), while `IS [NOT] NULL` is always true or false. `AND`,
+/// `OR` and `NOT` follow Kleene logic: `false AND unknown` is false, `true OR
+/// unknown` is true, and `NOT unknown` is unknown.
+///
+/// # Errors
+///
+/// Returns an error if evaluating an operand fails, or if a value used as a
+/// predicate is not a boolean.
+pub fn evaluate_predicate(expr: &Expression, row: &Row) -> Result<Option<bool>> {
+    match expr {
+        Expression::Comparison { left, op, right } => {
+            let left = evaluate_value(left, row)?;
+            let right = evaluate_value(right, row)?;
+            if let ComparisonOp::StartsWith
+            | ComparisonOp::EndsWith
+            | ComparisonOp::Contains
+            | ComparisonOp::RegexMatch = op
+            {
+                return string_predicate(*op, &left, &right);
+            }
+
+            // Promote for cross-type comparison (Int64 vs Float64)
+            let (left, right) = promote_for_comparison(left, right);
+            Ok(left.compare(&right).map(|ordering| compare_holds(*op, ordering)))
+        }
+        Expression::Value(value) => match evaluate_value(value, row)? {
+            Value::Bool(b) => Ok(Some(b)),
+            Value::Null => Ok(None),
+            other => Err(RuzuError::TypeError {
+                expected: "BOOL".into(),
+                actual: other.data_type().map_or("NULL", |t| t.name()).into(),
+            }),
+        },
+        Expression::IsNull { operand, negated } => {
+            Ok(Some(evaluate_value(operand, row)?.is_null() != *negated))
+        }
+        Expression::In {
+            operand,
+            list,
+            negated,
+        } => {
+            let value = evaluate_value(operand, row)?;
+            let found = match evaluate_value(list, row)? {
+                Value::List(items) => list_contains(&value, &items),
+                Value::Null => None,
+                other => {
+                    return Err(RuzuError::TypeError {
+                        expected: "LIST".into(),
+                        actual: other.data_type().map_or("NULL", |t| t.name()).into(),
+                    })
+                }
+            };
+            Ok(found.map(|found| found != *negated))
+        }
+        Expression::And(left, right) => match evaluate_predicate(left, row)? {
+            Some(false) => Ok(Some(false)),
+            left => Ok(match (left, evaluate_predicate(right, row)?) {
+                (_, Some(false)) => Some(false),
+                (Some(true), right) => right,
+                _ => None,
+            }),
+        },
+        Expression::Or(left, right) => match evaluate_predicate(left, row)? {
+            Some(true) => Ok(Some(true)),
+            left => Ok(match (left, evaluate_predicate(right, row)?) {
+                (_, Some(true)) => Some(true),
+                (Some(false), right) => right,
+                _ => None,
+            }),
+        },
+        Expression::Not(operand) => Ok(evaluate_predicate(operand, row)?.map(|b| !b)),
+        // Subqueries need the database; it runs them as semi-joins instead
+        Expression::Exists { .. } => Err(RuzuError::ExecutionError(format!(
+            "{expr} is only supported in the WHERE clause of a MATCH"
+        ))),
+    }
+}
+
+/// Whether `op` holds for two operands that compare as `ordering`.
+fn compare_holds(op: ComparisonOp, ordering: Ordering) -> bool {
+    match op {
+        ComparisonOp::Gt => ordering == Ordering::Greater,
+        ComparisonOp::Lt => ordering == Ordering::Less,
+        ComparisonOp::Eq => ordering == Ordering::Equal,
+        ComparisonOp::Gte => ordering != Ordering::Less,
+        ComparisonOp::Lte => ordering != Ordering::Greater,
+        ComparisonOp::Neq => ordering != Ordering::Equal,
+        ComparisonOp::StartsWith
+        | ComparisonOp::EndsWith
+        | ComparisonOp::Contains
+        | ComparisonOp::RegexMatch => unreachable!("string predicates are not orderings"),
+    }
+}
+
+/// Whether `items` contains `value`, under three-valued logic.
+///
+/// A match is true even if other elements are NULL; otherwise a NULL `value`
+/// or element makes the result unknown. Nothing is in an empty list.
+pub(crate) fn list_contains(value: &Value, items: &[Value]) -> Option<bool> {
+    let mut unknown = false;
+    for item in items {
+        if value.is_null() || item.is_null() {
+            unknown = true;
+            continue;
+        }
+        let (value, item) = promote_for_comparison(value.clone(), item.clone());
+        if value.compare(&item) == Some(Ordering::Equal) {
+            return Some(true);
+        }
+    }
+    (!unknown).then_some(false)
+}
+
+/// Evaluates `STARTS WITH`, `ENDS WITH`, `CONTAINS` or `=~`.
+///
+/// Unknown if either operand is NULL. A regular expression must match the
+/// whole string, as in Cypher.
+///
+/// # Errors
+///
+/// Returns an error if an operand is not a string, or if the pattern of `=~`
+/// is not a valid regular expression.
+fn string_predicate(op: ComparisonOp, left: &Value, right: &Value) -> Result<Option<bool>> {
+    let (text, pattern) = match (left, right) {
+        (Value::String(text), Value::String(pattern)) => (text, pattern),
+        (Value::Null, _) | (_, Value::Null) => return Ok(None),
+        (Value::String(_), other) | (other, _) => {
+            return Err(RuzuError::TypeError {
+                expected: "STRING".into(),
+                actual: other.data_type().map_or("NULL", |t| t.name()).into(),
+            })
+        }
     };
+    Ok(Some(match op {
+        ComparisonOp::StartsWith => text.starts_with(pattern.as_str()),
+        ComparisonOp::EndsWith => text.ends_with(pattern.as_str()),
+        ComparisonOp::Contains => text.contains(pattern.as_str()),
+        _ => full_match_regex(pattern)?.is_match(text),
+    }))
+}
+
+/// Maximum number of compiled patterns kept per thread.
+const REGEX_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    static REGEX_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+/// Compiles `pattern` anchored at both ends, reusing recent compilations so
+/// that a filter does not recompile its pattern for every row.
+///
+/// # Errors
+///
+/// Returns an error if `pattern` is not a valid regular expression.
+pub(crate) fn full_match_regex(pattern: &str) -> Result<Regex> {
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(regex) = cache.get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+            RuzuError::ExecutionError(format!("Invalid regular expression '{pattern}': {e}"))
+        })?;
+        if cache.len() >= REGEX_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    })
+}
 
-    // Convert the literal to a Value for comparison
-    let literal_value = match &expr.value {
+/// Converts a literal to a value.
+pub(crate) fn literal_value(literal: &Literal) -> Value {
+    match literal {
         Literal::Int64(n) => Value::Int64(*n),
         Literal::String(s) => Value::String(s.clone()),
         Literal::Float64(f) => Value::Float64(*f),
         Literal::Bool(b) => Value::Bool(*b),
-    };
+        Literal::List(items) => Value::List(items.iter().map(literal_value).collect()),
+        Literal::Date(days) => Value::Date(*days),
+        Literal::Timestamp(micros) => Value::Timestamp(*micros),
+        // Parameters are bound to their values before execution
+        Literal::Null | Literal::Parameter(_) => Value::Null,
+    }
+}
 
-    // Promote for cross-type comparison (Int64 vs Float64)
-    let (value, literal_value) = promote_for_comparison(value.clone(), literal_value);
-
-    // Compare based on the operator
-    let cmp = value.compare(&literal_value);
-
-    match cmp {
-        None => Ok(false), // Null or type mismatch
-        Some(ordering) => {
-            let result = match expr.op {
-                ComparisonOp::Gt => ordering == Ordering::Greater,
-                ComparisonOp::Lt => ordering == Ordering::Less,
-                ComparisonOp::Eq => ordering == Ordering::Equal,
-                ComparisonOp::Gte => ordering != Ordering::Less,
-                ComparisonOp::Lte => ordering != Ordering::Greater,
-                ComparisonOp::Neq => ordering != Ordering::Equal,
+/// Evaluates a value expression against a row.
+///
+/// Properties missing from the row evaluate to NULL, and arithmetic with a
+/// NULL operand yields NULL.
+///
+/// # Errors
+///
+/// Returns an error if an arithmetic operand or function argument has an
+/// unsupported type, if a function is unknown, on integer overflow, or on
+/// integer division by zero.
+pub fn evaluate_value(expr: &ValueExpr, row: &Row) -> Result<Value> {
+    match expr {
+        ValueExpr::Literal(literal) => Ok(literal_value(literal)),
+        ValueExpr::Property { var, property } => Ok(row
+            .get(&format!("{var}.{property}"))
+            .cloned()
+            .unwrap_or(Value::Null)),
+        // A node or relationship evaluates to its ID, which is NULL when
+        // OPTIONAL MATCH left it unbound
+        ValueExpr::Variable(name) => Ok(row
+            .get(name)
+            .or_else(|| row.get(&format!("{name}._id")))
+            .cloned()
+            .unwrap_or(Value::Null)),
+        ValueExpr::Arithmetic { left, op, right } => {
+            let left = evaluate_value(left, row)?;
+            let right = evaluate_value(right, row)?;
+            evaluate_arithmetic(*op, left, right)
+        }
+        // Negation is multiplication by -1, as bound by the binder
+        ValueExpr::Negate(operand) => {
+            evaluate_arithmetic(ArithmeticOp::Mul, Value::Int64(-1), evaluate_value(operand, row)?)
+        }
+        ValueExpr::Case(case) => evaluate_case(case, row),
+        ValueExpr::Function { name, args } => {
+            let function = ScalarFunction::lookup(name).ok_or_else(|| {
+                RuzuError::ExecutionError(format!("Unknown function: {name}"))
+            })?;
+            let args = args
+                .iter()
+                .map(|arg| evaluate_value(arg, row))
+                .collect::<Result<Vec<_>>>()?;
+            evaluate_function(function, &args)
+        }
+    }
+}
+
+/// Evaluates a CASE expression: the result of the first branch that
+/// matches, else the ELSE result or NULL.
+///
+/// A NULL operand matches no WHEN value, and a WHEN condition that is
+/// unknown does not hold.
+fn evaluate_case(case: &CaseExpr, row: &Row) -> Result<Value> {
+    let operand = case
+        .operand
+        .as_ref()
+        .map(|operand| evaluate_value(operand, row))
+        .transpose()?;
+    for (when, result) in &case.branches {
+        let matched = match when {
+            CaseWhen::Value(value) => {
+                let operand = operand.clone().unwrap_or(Value::Null);
+                let (left, right) = promote_for_comparison(operand, evaluate_value(value, row)?);
+                left.compare(&right) == Some(Ordering::Equal)
+            }
+            CaseWhen::Condition(condition) => evaluate_expression(condition, row)?,
+        };
+        if matched {
+            return evaluate_value(result, row);
+        }
+    }
+    case.default
+        .as_ref()
+        .map_or(Ok(Value::Null), |default| evaluate_value(default, row))
+}
+
+/// Applies an arithmetic operator to two values.
+///
+/// Integer operands produce an integer; mixing in a float produces a float.
+/// `+` also concatenates strings. Subtracting dates gives the difference in
+/// days, and adding or subtracting an integer shifts a date by that many days.
+fn evaluate_arithmetic(op: ArithmeticOp, left: Value, right: Value) -> Result<Value> {
+    if left.is_null() || right.is_null() {
+        return Ok(Value::Null);
+    }
+
+    match (left, right) {
+        (Value::Date(a), Value::Date(b)) if op == ArithmeticOp::Sub => {
+            Ok(Value::Int64(i64::from(a) - i64::from(b)))
+        }
+        (Value::Date(date), Value::Int64(days))
+            if matches!(op, ArithmeticOp::Add | ArithmeticOp::Sub) =>
+        {
+            let days = if op == ArithmeticOp::Sub {
+                days.checked_neg()
+            } else {
+                Some(days)
+            };
+            shift_date(date, days)
+        }
+        (Value::Int64(days), Value::Date(date)) if op == ArithmeticOp::Add => {
+            shift_date(date, Some(days))
+        }
+        (Value::Int64(a), Value::Int64(b)) => {
+            if b == 0 && matches!(op, ArithmeticOp::Div | ArithmeticOp::Mod) {
+                return Err(RuzuError::ExecutionError("Division by zero".into()));
+            }
+            let result = match op {
+                ArithmeticOp::Add => a.checked_add(b),
+                ArithmeticOp::Sub => a.checked_sub(b),
+                ArithmeticOp::Mul => a.checked_mul(b),
+                ArithmeticOp::Div => a.checked_div(b),
+                ArithmeticOp::Mod => a.checked_rem(b),
+            };
+            result
+                .map(Value::Int64)
+                .ok_or_else(|| RuzuError::ExecutionError("Integer overflow".into()))
+        }
+        (Value::String(a), Value::String(b)) if op == ArithmeticOp::Add => {
+            Ok(Value::String(a + &b))
+        }
+        (left, right) => {
+            let (Some(a), Some(b)) = (numeric_as_f64(&left), numeric_as_f64(&right)) else {
+                let offending = if numeric_as_f64(&left).is_none() { &left } else { &right };
+                return Err(RuzuError::TypeError {
+                    expected: "numeric".into(),
+                    actual: offending.data_type().map_or("NULL", |t| t.name()).into(),
+                });
             };
-            Ok(result)
+            let result = match op {
+                ArithmeticOp::Add => a + b,
+                ArithmeticOp::Sub => a - b,
+                ArithmeticOp::Mul => a * b,
+                ArithmeticOp::Div => a / b,
+                ArithmeticOp::Mod => a % b,
+            };
+            Ok(Value::Float64(result))
         }
     }
 }
+
+/// Adds a number of days to a date.
+///
+/// # Errors
+///
+/// Returns an error if the result is out of range.
+fn shift_date(date: i32, days: Option<i64>) -> Result<Value> {
+    days.and_then(|days| i64::from(date).checked_add(days))
+        .and_then(|shifted| i32::try_from(shifted).ok())
+        .map(Value::Date)
+        .ok_or_else(|| RuzuError::ExecutionError("Date out of range".into()))
+}
+
+/// Widens a numeric value to `f64`.
+#[allow(clippy::cast_precision_loss)]
+fn numeric_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int64(n) => Some(*n as f64),
+        Value::Float32(f) => Some(f64::from(*f)),
+        Value::Float64(f) => Some(*f),
+        _ => None,
+    }
+}
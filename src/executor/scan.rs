@@ -7,10 +7,11 @@ use crate::executor::PhysicalOperator;
 use crate::storage::NodeTable;
 use crate::types::Row;
 
-/// Scan operator for full table scans.
+/// Scan operator for full table scans, or for lookups of known rows.
 pub struct ScanOperator {
     table: Arc<NodeTable>,
     variable: String,
+    offsets: Option<Vec<usize>>,
     cursor: usize,
 }
 
@@ -21,16 +22,40 @@ impl ScanOperator {
         ScanOperator {
             table,
             variable,
+            offsets: None,
             cursor: 0,
         }
     }
+
+    /// Restricts the scan to the rows at `offsets`, such as the result of
+    /// primary key lookups. Deleted rows are still skipped.
+    #[must_use]
+    pub fn with_offsets(mut self, offsets: Vec<usize>) -> Self {
+        self.offsets = Some(offsets);
+        self
+    }
+
+    /// Advances to the next live row, returning its offset.
+    fn next_offset(&mut self) -> Option<usize> {
+        loop {
+            let offset = match &self.offsets {
+                Some(offsets) => *offsets.get(self.cursor)?,
+                None if self.cursor < self.table.row_count() => self.cursor,
+                None => return None,
+            };
+            self.cursor += 1;
+            if offset < self.table.row_count() && !self.table.is_deleted(offset) {
+                return Some(offset);
+            }
+        }
+    }
 }
 
 impl PhysicalOperator for ScanOperator {
     fn next(&mut self) -> Result<Option<Row>> {
-        if self.cursor >= self.table.row_count() {
+        let Some(offset) = self.next_offset() else {
             return Ok(None);
-        }
+        };
 
         let mut row = Row::new();
         let schema = self.table.schema();
@@ -38,14 +63,13 @@ impl PhysicalOperator for ScanOperator {
         // Build the row with fully qualified column names (var.column)
         for (col_idx, col_def) in schema.columns.iter().enumerate() {
             if let Some(column) = self.table.get_column(col_idx) {
-                if let Some(value) = column.get(self.cursor) {
+                if let Some(value) = column.get(offset) {
                     let full_name = format!("{}.{}", self.variable, col_def.name);
                     row.set(full_name, value.clone());
                 }
             }
         }
 
-        self.cursor += 1;
         Ok(Some(row))
     }
 }
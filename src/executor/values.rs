@@ -0,0 +1,27 @@
+//! Values operator for feeding materialized rows into a pipeline.
+
+use crate::error::Result;
+use crate::types::Row;
+
+use super::PhysicalOperator;
+
+/// Source operator that yields a fixed set of rows in order.
+pub struct ValuesOperator {
+    rows: std::vec::IntoIter<Row>,
+}
+
+impl ValuesOperator {
+    /// Creates a new values operator over the given rows.
+    #[must_use]
+    pub fn new(rows: Vec<Row>) -> Self {
+        ValuesOperator {
+            rows: rows.into_iter(),
+        }
+    }
+}
+
+impl PhysicalOperator for ValuesOperator {
+    fn next(&mut self) -> Result<Option<Row>> {
+        Ok(self.rows.next())
+    }
+}
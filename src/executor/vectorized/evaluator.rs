@@ -3,18 +3,25 @@
 use std::sync::Arc;
 
 use arrow::array::{
-    ArrayRef, BooleanArray, Float32Array, Float64Array, Int64Array, StringArray,
-    TimestampMicrosecondArray,
+    new_null_array, Array, ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array,
+    Int64Array, ListArray, ListBuilder, StringArray, StringBuilder, TimestampMicrosecondArray,
 };
-use arrow::compute::kernels::boolean::{and, not, or};
+use arrow::compute::kernels::boolean::{and, not, or, or_kleene};
 use arrow::compute::kernels::cmp::{eq, gt, gt_eq, lt, lt_eq, neq};
+use arrow::compute::kernels::comparison::{contains, ends_with, regexp_is_match, starts_with};
 use arrow::compute::kernels::numeric::{add, div, mul, rem, sub};
-use arrow::compute::is_null;
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, is_not_null, is_null};
 use arrow::datatypes::DataType as ArrowDataType;
 use arrow::error::Result as ArrowResult;
 
-use crate::binder::{ArithmeticOp, BoundExpression, ComparisonOp, LogicalOp};
-use crate::types::Value;
+use crate::binder::{ArithmeticOp, BoundExpression, ComparisonOp, LogicalOp, ScalarFunction};
+use crate::error::RuzuError;
+use crate::executor::function::{
+    checked_abs, date_part, float_to_integer, float_to_string, parse_boolean, parse_float,
+    parse_integer, parse_temporal, round, substring,
+};
+use crate::types::{temporal, DataType, Value};
 
 use super::batch::VectorizedBatch;
 
@@ -83,7 +90,376 @@ impl VectorizedEvaluator {
                     Ok(Arc::new(nulls))
                 }
             }
+            BoundExpression::InList {
+                operand,
+                list,
+                negated,
+                ..
+            } => {
+                let arr = Self::evaluate(operand, batch)?;
+                let found = Self::in_list(&arr, list)?;
+                if *negated {
+                    not(&found).map(|a| Arc::new(a) as ArrayRef)
+                } else {
+                    Ok(Arc::new(found))
+                }
+            }
+            BoundExpression::Case {
+                branches,
+                default,
+                data_type,
+            } => Self::evaluate_case(branches, default.as_deref(), *data_type, batch),
+            BoundExpression::Function {
+                function,
+                args,
+                data_type,
+            } => Self::evaluate_function(*function, args, *data_type, batch),
+            BoundExpression::Exists { .. } => Err(arrow::error::ArrowError::NotYetImplemented(
+                "EXISTS subqueries should be executed as semi-joins".to_string(),
+            )),
+        }
+    }
+
+    /// Evaluates a scalar function call, dispatching to the implementation
+    /// for its group of functions.
+    fn evaluate_function(
+        function: ScalarFunction,
+        args: &[BoundExpression],
+        data_type: DataType,
+        batch: &VectorizedBatch,
+    ) -> ArrowResult<ArrayRef> {
+        let args = args
+            .iter()
+            .map(|arg| Self::evaluate(arg, batch))
+            .collect::<ArrowResult<Vec<_>>>()?;
+        match function {
+            ScalarFunction::Lower
+            | ScalarFunction::Upper
+            | ScalarFunction::Trim
+            | ScalarFunction::Substring
+            | ScalarFunction::Size
+            | ScalarFunction::Replace
+            | ScalarFunction::Split => Self::string_function(function, &args),
+            ScalarFunction::Abs
+            | ScalarFunction::Round
+            | ScalarFunction::Floor
+            | ScalarFunction::Ceil
+            | ScalarFunction::Sqrt => Self::math_function(function, &args),
+            ScalarFunction::Coalesce => Self::coalesce(&args, data_type),
+            ScalarFunction::ToInteger
+            | ScalarFunction::ToFloat
+            | ScalarFunction::ToString
+            | ScalarFunction::ToBoolean => Self::conversion_function(function, &args[0]),
+            ScalarFunction::Date
+            | ScalarFunction::Timestamp
+            | ScalarFunction::Year
+            | ScalarFunction::Month
+            | ScalarFunction::Day => Self::temporal_function(function, &args[0]),
+        }
+    }
+
+    /// Evaluates a string function. Like the row implementation, a NULL
+    /// argument yields NULL.
+    fn string_function(function: ScalarFunction, args: &[ArrayRef]) -> ArrowResult<ArrayRef> {
+        if function == ScalarFunction::Size {
+            if let Some(lists) = args[0].as_any().downcast_ref::<ListArray>() {
+                let sizes: Int64Array = (0..lists.len())
+                    .map(|i| lists.is_valid(i).then(|| i64::from(lists.value_length(i))))
+                    .collect();
+                return Ok(Arc::new(sizes));
+            }
+        }
+        let strings = Self::strings(&args[0])?;
+        let result: ArrayRef = match function {
+            ScalarFunction::Lower => Arc::new(Self::map_strings(&strings, str::to_lowercase)),
+            ScalarFunction::Upper => Arc::new(Self::map_strings(&strings, str::to_uppercase)),
+            ScalarFunction::Trim => Arc::new(Self::map_strings(&strings, |s| s.trim().to_string())),
+            ScalarFunction::Size => Arc::new(
+                strings
+                    .iter()
+                    .map(|s| s.map(|s| i64::try_from(s.chars().count()).unwrap_or(i64::MAX)))
+                    .collect::<Int64Array>(),
+            ),
+            ScalarFunction::Substring => {
+                let starts = Self::integers(&args[1])?;
+                let lengths = args.get(2).map(Self::integers).transpose()?;
+                let mut result = StringBuilder::new();
+                for i in 0..strings.len() {
+                    let length = lengths.as_ref().map(|lengths| lengths.is_valid(i).then(|| lengths.value(i)));
+                    if strings.is_null(i) || starts.is_null(i) || length == Some(None) {
+                        result.append_null();
+                    } else {
+                        let value = substring(strings.value(i), starts.value(i), length.flatten())
+                            .map_err(|error| compute_error(&error))?;
+                        result.append_value(value);
+                    }
+                }
+                Arc::new(result.finish())
+            }
+            ScalarFunction::Replace => {
+                let searches = Self::strings(&args[1])?;
+                let replacements = Self::strings(&args[2])?;
+                Arc::new(
+                    strings
+                        .iter()
+                        .zip(searches.iter())
+                        .zip(replacements.iter())
+                        .map(|((s, search), replacement)| Some(s?.replace(search?, replacement?)))
+                        .collect::<StringArray>(),
+                )
+            }
+            ScalarFunction::Split => {
+                let delimiters = Self::strings(&args[1])?;
+                let mut result = ListBuilder::new(StringBuilder::new());
+                for (s, delimiter) in strings.iter().zip(delimiters.iter()) {
+                    match (s, delimiter) {
+                        (Some(s), Some(delimiter)) => {
+                            for part in s.split(delimiter) {
+                                result.values().append_value(part);
+                            }
+                            result.append(true);
+                        }
+                        _ => result.append(false),
+                    }
+                }
+                Arc::new(result.finish())
+            }
+            _ => return Err(not_applicable(function)),
+        };
+        Ok(result)
+    }
+
+    /// Evaluates a math function. `abs` keeps the input type; the others
+    /// compute over FLOAT64.
+    fn math_function(function: ScalarFunction, args: &[ArrayRef]) -> ArrowResult<ArrayRef> {
+        if function == ScalarFunction::Abs {
+            return match args[0].data_type() {
+                ArrowDataType::Float32 => {
+                    let values = args[0].as_any().downcast_ref::<Float32Array>().unwrap();
+                    Ok(Arc::new(values.unary::<_, arrow::datatypes::Float32Type>(f32::abs)))
+                }
+                ArrowDataType::Float64 => {
+                    let values = args[0].as_any().downcast_ref::<Float64Array>().unwrap();
+                    Ok(Arc::new(values.unary::<_, arrow::datatypes::Float64Type>(f64::abs)))
+                }
+                _ => {
+                    let values = Self::integers(&args[0])?;
+                    let result = values.try_unary::<_, arrow::datatypes::Int64Type, _>(|n| {
+                        checked_abs(n).map_err(|error| compute_error(&error))
+                    })?;
+                    Ok(Arc::new(result))
+                }
+            };
         }
+        let values = Self::floats(&args[0])?;
+        let result: Float64Array = match function {
+            ScalarFunction::Round => match args.get(1) {
+                Some(precisions) => Self::integers(precisions)?
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(precision, x)| Some(round(x?, precision?)))
+                    .collect(),
+                None => values.unary(|x| round(x, 0)),
+            },
+            ScalarFunction::Floor => values.unary(f64::floor),
+            ScalarFunction::Ceil => values.unary(f64::ceil),
+            ScalarFunction::Sqrt => values.unary(f64::sqrt),
+            _ => return Err(not_applicable(function)),
+        };
+        Ok(Arc::new(result))
+    }
+
+    /// Evaluates `coalesce` by folding its arguments from last to first, so
+    /// each row takes the first non-NULL value.
+    fn coalesce(args: &[ArrayRef], data_type: DataType) -> ArrowResult<ArrayRef> {
+        let target = data_type.to_arrow();
+        let (last, rest) = args.split_last().ok_or_else(|| not_applicable(ScalarFunction::Coalesce))?;
+        let mut result = cast(last, &target)?;
+        for arg in rest.iter().rev() {
+            let arg = cast(arg, &target)?;
+            result = zip(&is_not_null(&arg)?, &arg, &result)?;
+        }
+        Ok(result)
+    }
+
+    /// Evaluates a type conversion. Strings are parsed as by the row
+    /// implementation, with NULL for a string that does not convert.
+    fn conversion_function(function: ScalarFunction, arg: &ArrayRef) -> ArrowResult<ArrayRef> {
+        let is_string = arg.data_type() == &ArrowDataType::Utf8;
+        let is_float = matches!(arg.data_type(), ArrowDataType::Float32 | ArrowDataType::Float64);
+        let result: ArrayRef = match function {
+            ScalarFunction::ToInteger if is_string => Arc::new(
+                Self::strings(arg)?
+                    .iter()
+                    .map(|s| s.and_then(parse_integer))
+                    .collect::<Int64Array>(),
+            ),
+            ScalarFunction::ToInteger if is_float => Arc::new(
+                Self::floats(arg)?
+                    .iter()
+                    .map(|f| f.and_then(float_to_integer))
+                    .collect::<Int64Array>(),
+            ),
+            ScalarFunction::ToInteger => cast(arg, &ArrowDataType::Int64)?,
+            ScalarFunction::ToFloat if is_string => Arc::new(
+                Self::strings(arg)?
+                    .iter()
+                    .map(|s| s.and_then(parse_float))
+                    .collect::<Float64Array>(),
+            ),
+            ScalarFunction::ToFloat => cast(arg, &ArrowDataType::Float64)?,
+            ScalarFunction::ToString if is_float => Arc::new(
+                Self::floats(arg)?
+                    .iter()
+                    .map(|f| f.map(float_to_string))
+                    .collect::<StringArray>(),
+            ),
+            ScalarFunction::ToString if arg.data_type() == &ArrowDataType::Date32 => Arc::new(
+                Self::dates(arg)?
+                    .iter()
+                    .map(|days| days.map(temporal::format_date))
+                    .collect::<StringArray>(),
+            ),
+            ScalarFunction::ToString if matches!(arg.data_type(), ArrowDataType::Timestamp(..)) => {
+                Arc::new(
+                    Self::timestamps(arg)?
+                        .iter()
+                        .map(|micros| micros.map(temporal::format_timestamp))
+                        .collect::<StringArray>(),
+                )
+            }
+            ScalarFunction::ToString => cast(arg, &ArrowDataType::Utf8)?,
+            ScalarFunction::ToBoolean if is_string => Arc::new(
+                Self::strings(arg)?
+                    .iter()
+                    .map(|s| s.and_then(parse_boolean))
+                    .collect::<BooleanArray>(),
+            ),
+            ScalarFunction::ToBoolean => cast(arg, &ArrowDataType::Boolean)?,
+            _ => return Err(not_applicable(function)),
+        };
+        Ok(result)
+    }
+
+    /// Evaluates a DATE or TIMESTAMP conversion, or extracts a date part.
+    fn temporal_function(function: ScalarFunction, arg: &ArrayRef) -> ArrowResult<ArrayRef> {
+        let is_string = arg.data_type() == &ArrowDataType::Utf8;
+        let result: ArrayRef = match function {
+            ScalarFunction::Date if is_string => Arc::new(
+                Self::strings(arg)?
+                    .iter()
+                    .map(|s| {
+                        s.map(|s| parse_temporal(function, s, temporal::parse_date))
+                            .transpose()
+                    })
+                    .collect::<Result<Date32Array, _>>()
+                    .map_err(|error| compute_error(&error))?,
+            ),
+            ScalarFunction::Date => Arc::new(Self::dates(arg)?),
+            ScalarFunction::Timestamp if is_string => Arc::new(
+                Self::strings(arg)?
+                    .iter()
+                    .map(|s| {
+                        s.map(|s| parse_temporal(function, s, temporal::parse_timestamp))
+                            .transpose()
+                    })
+                    .collect::<Result<TimestampMicrosecondArray, _>>()
+                    .map_err(|error| compute_error(&error))?,
+            ),
+            ScalarFunction::Timestamp => Arc::new(Self::timestamps(arg)?),
+            _ => Arc::new(
+                Self::dates(arg)?
+                    .iter()
+                    .map(|days| days.and_then(|days| date_part(function, days)))
+                    .collect::<Int64Array>(),
+            ),
+        };
+        Ok(result)
+    }
+
+    /// Applies `f` to each non-NULL string.
+    fn map_strings(strings: &StringArray, f: impl Fn(&str) -> String) -> StringArray {
+        strings.iter().map(|s| s.map(&f)).collect()
+    }
+
+    /// Casts a function argument to strings; a NULL literal argument is an
+    /// all-NULL array of another type.
+    fn strings(arr: &ArrayRef) -> ArrowResult<StringArray> {
+        Ok(cast(arr, &ArrowDataType::Utf8)?
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .clone())
+    }
+
+    /// Casts a function argument to integers.
+    fn integers(arr: &ArrayRef) -> ArrowResult<Int64Array> {
+        Ok(cast(arr, &ArrowDataType::Int64)?
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .clone())
+    }
+
+    /// Casts a function argument to dates; a timestamp gives the day it
+    /// falls on.
+    fn dates(arr: &ArrayRef) -> ArrowResult<Date32Array> {
+        if let Some(timestamps) = arr.as_any().downcast_ref::<TimestampMicrosecondArray>() {
+            return Ok(timestamps
+                .iter()
+                .map(|micros| micros.map(temporal::timestamp_to_days))
+                .collect());
+        }
+        Ok(cast(arr, &ArrowDataType::Date32)?
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap()
+            .clone())
+    }
+
+    /// Casts a function argument to timestamps; a date gives its midnight.
+    fn timestamps(arr: &ArrayRef) -> ArrowResult<TimestampMicrosecondArray> {
+        Ok(cast(arr, &DataType::Timestamp.to_arrow())?
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap()
+            .clone())
+    }
+
+    /// Casts a function argument to floats.
+    fn floats(arr: &ArrayRef) -> ArrowResult<Float64Array> {
+        Ok(cast(arr, &ArrowDataType::Float64)?
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .clone())
+    }
+
+    /// Evaluates a CASE expression by folding its branches from last to
+    /// first, so each row takes the result of the first condition that holds.
+    fn evaluate_case(
+        branches: &[(BoundExpression, BoundExpression)],
+        default: Option<&BoundExpression>,
+        data_type: DataType,
+        batch: &VectorizedBatch,
+    ) -> ArrowResult<ArrayRef> {
+        let target = data_type.to_arrow();
+        let mut result = match default {
+            Some(default) => cast(&Self::evaluate(default, batch)?, &target)?,
+            None => new_null_array(&target, batch.num_rows()),
+        };
+        for (condition, then) in branches.iter().rev() {
+            let mask = Self::evaluate(condition, batch)?;
+            let mask = mask.as_any().downcast_ref::<BooleanArray>().ok_or_else(|| {
+                arrow::error::ArrowError::InvalidArgumentError(
+                    "CASE condition must be boolean".to_string(),
+                )
+            })?;
+            // A NULL condition selects the later branches, like false
+            let then = cast(&Self::evaluate(then, batch)?, &target)?;
+            result = zip(mask, &then, &result)?;
+        }
+        Ok(result)
     }
 
     /// Creates a literal array with the same value repeated.
@@ -96,8 +472,9 @@ impl VectorizedEvaluator {
             Value::String(v) => Arc::new(StringArray::from(vec![v.as_str(); len])),
             Value::Date(v) => Arc::new(arrow::array::Date32Array::from(vec![*v; len])),
             Value::Timestamp(v) => Arc::new(TimestampMicrosecondArray::from(vec![*v; len])),
-            Value::Null => {
-                // Create a null array of appropriate type (default to Int64)
+            Value::List(_) | Value::Map(_) | Value::Null => {
+                // Create a null array of appropriate type (default to Int64);
+                // list and map values have no columnar representation here
                 let arr = Int64Array::from(vec![None::<i64>; len]);
                 Arc::new(arr)
             }
@@ -106,6 +483,9 @@ impl VectorizedEvaluator {
 
     /// Compares two arrays using the given operator.
     fn compare(left: &ArrayRef, op: ComparisonOp, right: &ArrayRef) -> ArrowResult<ArrayRef> {
+        if let Some(target) = Self::comparison_type(left.data_type(), right.data_type()) {
+            return Self::compare(&cast(left, &target)?, op, &cast(right, &target)?);
+        }
         match (left.data_type(), right.data_type()) {
             (ArrowDataType::Int64, ArrowDataType::Int64) => {
                 let left = left.as_any().downcast_ref::<Int64Array>().unwrap();
@@ -117,6 +497,7 @@ impl VectorizedEvaluator {
                     ComparisonOp::Lte => lt_eq(left, right)?,
                     ComparisonOp::Gt => gt(left, right)?,
                     ComparisonOp::Gte => gt_eq(left, right)?,
+                    _ => return Err(Self::requires_strings(op)),
                 };
                 Ok(Arc::new(result))
             }
@@ -130,6 +511,7 @@ impl VectorizedEvaluator {
                     ComparisonOp::Lte => lt_eq(left, right)?,
                     ComparisonOp::Gt => gt(left, right)?,
                     ComparisonOp::Gte => gt_eq(left, right)?,
+                    _ => return Err(Self::requires_strings(op)),
                 };
                 Ok(Arc::new(result))
             }
@@ -143,6 +525,20 @@ impl VectorizedEvaluator {
                     ComparisonOp::Lte => lt_eq(left, right)?,
                     ComparisonOp::Gt => gt(left, right)?,
                     ComparisonOp::Gte => gt_eq(left, right)?,
+                    _ => return Err(Self::requires_strings(op)),
+                };
+                Ok(Arc::new(result))
+            }
+            (ArrowDataType::Date32, ArrowDataType::Date32)
+            | (ArrowDataType::Timestamp(..), ArrowDataType::Timestamp(..)) => {
+                let result = match op {
+                    ComparisonOp::Eq => eq(left, right)?,
+                    ComparisonOp::Neq => neq(left, right)?,
+                    ComparisonOp::Lt => lt(left, right)?,
+                    ComparisonOp::Lte => lt_eq(left, right)?,
+                    ComparisonOp::Gt => gt(left, right)?,
+                    ComparisonOp::Gte => gt_eq(left, right)?,
+                    _ => return Err(Self::requires_strings(op)),
                 };
                 Ok(Arc::new(result))
             }
@@ -156,6 +552,18 @@ impl VectorizedEvaluator {
                     ComparisonOp::Lte => lt_eq(left, right)?,
                     ComparisonOp::Gt => gt(left, right)?,
                     ComparisonOp::Gte => gt_eq(left, right)?,
+                    ComparisonOp::StartsWith => starts_with(left, right)?,
+                    ComparisonOp::EndsWith => ends_with(left, right)?,
+                    ComparisonOp::Contains => contains(left, right)?,
+                    ComparisonOp::RegexMatch => {
+                        // Cypher's =~ must match the whole string, while the
+                        // kernel finds a match anywhere
+                        let anchored: StringArray = right
+                            .iter()
+                            .map(|pattern| pattern.map(|p| format!("^(?:{p})$")))
+                            .collect();
+                        regexp_is_match(left, &anchored, None::<&StringArray>)?
+                    }
                 };
                 Ok(Arc::new(result))
             }
@@ -167,6 +575,85 @@ impl VectorizedEvaluator {
         }
     }
 
+    /// Adds (or, when `negate`, subtracts) a number of days to each date.
+    fn shift_dates(dates: &Date32Array, days: &Int64Array, negate: bool) -> ArrowResult<ArrayRef> {
+        let result = dates
+            .iter()
+            .zip(days.iter())
+            .map(|(date, days)| {
+                let (Some(date), Some(days)) = (date, days) else {
+                    return Ok(None);
+                };
+                let days = if negate {
+                    days.checked_neg()
+                } else {
+                    Some(days)
+                };
+                days.and_then(|days| i64::from(date).checked_add(days))
+                    .and_then(|shifted| i32::try_from(shifted).ok())
+                    .map(Some)
+                    .ok_or_else(|| {
+                        arrow::error::ArrowError::ComputeError("Date out of range".to_string())
+                    })
+            })
+            .collect::<ArrowResult<Date32Array>>()?;
+        Ok(Arc::new(result))
+    }
+
+    /// Returns the type mixed numeric operands are compared as, like the row
+    /// evaluator: FLOAT32 against FLOAT64 at FLOAT32 precision, other mixes
+    /// as FLOAT64. None if the operands need no cast.
+    fn comparison_type(left: &ArrowDataType, right: &ArrowDataType) -> Option<ArrowDataType> {
+        let numeric = |t: &ArrowDataType| {
+            matches!(
+                t,
+                ArrowDataType::Int64 | ArrowDataType::Float32 | ArrowDataType::Float64
+            )
+        };
+        match (left, right) {
+            _ if left == right || !numeric(left) || !numeric(right) => None,
+            (ArrowDataType::Float32, ArrowDataType::Float64)
+            | (ArrowDataType::Float64, ArrowDataType::Float32) => Some(ArrowDataType::Float32),
+            _ => Some(ArrowDataType::Float64),
+        }
+    }
+
+    /// Tests each value of `arr` for membership in a literal list.
+    ///
+    /// Equality tests against the elements are combined with Kleene OR, so a
+    /// NULL value or element makes a non-match NULL rather than false.
+    fn in_list(arr: &ArrayRef, list: &BoundExpression) -> ArrowResult<BooleanArray> {
+        let BoundExpression::Literal {
+            value: Value::List(items),
+            ..
+        } = list
+        else {
+            return Err(arrow::error::ArrowError::NotYetImplemented(
+                "IN requires a literal list".to_string(),
+            ));
+        };
+        let mut result = BooleanArray::from(vec![false; arr.len()]);
+        for item in items {
+            let matches = if item.is_null() {
+                BooleanArray::new_null(arr.len())
+            } else {
+                let item_arr = Self::create_literal_array(item, arr.len());
+                let eq = Self::compare(arr, ComparisonOp::Eq, &item_arr)?;
+                eq.as_any().downcast_ref::<BooleanArray>().unwrap().clone()
+            };
+            result = or_kleene(&result, &matches)?;
+        }
+        Ok(result)
+    }
+
+    /// Error for a string predicate applied to non-string arrays.
+    fn requires_strings(op: ComparisonOp) -> arrow::error::ArrowError {
+        arrow::error::ArrowError::ComputeError(format!(
+            "{} requires string operands",
+            op.as_str()
+        ))
+    }
+
     /// Evaluates a logical operation.
     fn evaluate_logical(
         op: LogicalOp,
@@ -240,6 +727,29 @@ impl VectorizedEvaluator {
     /// Performs arithmetic on two arrays.
     fn arithmetic(left: &ArrayRef, op: ArithmeticOp, right: &ArrayRef) -> ArrowResult<ArrayRef> {
         match (left.data_type(), right.data_type()) {
+            (ArrowDataType::Date32, ArrowDataType::Date32) if op == ArithmeticOp::Sub => {
+                let left = left.as_any().downcast_ref::<Date32Array>().unwrap();
+                let right = right.as_any().downcast_ref::<Date32Array>().unwrap();
+                let result: Int64Array = left
+                    .iter()
+                    .zip(right.iter())
+                    .map(|(a, b)| Some(i64::from(a?) - i64::from(b?)))
+                    .collect();
+                Ok(Arc::new(result))
+            }
+            (ArrowDataType::Date32, ArrowDataType::Int64)
+                if matches!(op, ArithmeticOp::Add | ArithmeticOp::Sub) =>
+            {
+                let dates = left.as_any().downcast_ref::<Date32Array>().unwrap();
+                let days = right.as_any().downcast_ref::<Int64Array>().unwrap();
+                let negate = op == ArithmeticOp::Sub;
+                Self::shift_dates(dates, days, negate)
+            }
+            (ArrowDataType::Int64, ArrowDataType::Date32) if op == ArithmeticOp::Add => {
+                let days = left.as_any().downcast_ref::<Int64Array>().unwrap();
+                let dates = right.as_any().downcast_ref::<Date32Array>().unwrap();
+                Self::shift_dates(dates, days, false)
+            }
             (ArrowDataType::Int64, ArrowDataType::Int64) => {
                 let left = left.as_any().downcast_ref::<Int64Array>().unwrap();
                 let right = right.as_any().downcast_ref::<Int64Array>().unwrap();
@@ -284,3 +794,16 @@ impl VectorizedEvaluator {
         }
     }
 }
+
+/// Converts a failure of a shared scalar function helper to an Arrow error.
+fn compute_error(error: &RuzuError) -> arrow::error::ArrowError {
+    arrow::error::ArrowError::ComputeError(error.to_string())
+}
+
+/// Error for a function dispatched to the wrong group of implementations.
+fn not_applicable(function: ScalarFunction) -> arrow::error::ArrowError {
+    arrow::error::ArrowError::ComputeError(format!(
+        "Unsupported arguments to {}",
+        function.name()
+    ))
+}
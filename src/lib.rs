@@ -13,6 +13,7 @@ pub mod storage;
 pub mod types;
 
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -34,13 +35,77 @@ struct RelPattern<'a> {
     src_node: &'a NodeFilter,
     rel_var: Option<&'a String>,
     rel_type: &'a str,
+    rel_filters: &'a [(String, ValueExpr)],
     dst_node: &'a NodeFilter,
     filter: Option<&'a parser::ast::Expression>,
     path_bounds: Option<(u32, u32)>,
+    direction: RelDirection,
 }
-use executor::{FilterOperator, PhysicalOperator, ProjectOperator, ScanOperator};
+
+/// Node or relationship removed by a DELETE variable.
+enum DeleteTarget<'a> {
+    /// A node of the named node table.
+    Node(&'a str),
+    /// A relationship of the named relationship table, located through the
+    /// variables bound to its endpoints.
+    Rel {
+        rel_type: &'a str,
+        src_var: &'a str,
+        dst_var: &'a str,
+    },
+}
+
+/// Storage location written by a SET assignment.
+struct SetTarget {
+    /// Node or relationship table name.
+    label: String,
+    /// Catalog table ID (used for WAL records).
+    table_id: u32,
+    /// Column index within the table schema.
+    col_idx: usize,
+    /// Declared column type, used to coerce assigned values.
+    data_type: DataType,
+    /// Whether the target is a relationship table.
+    is_rel: bool,
+}
+
+impl SetTarget {
+    /// Builds the WAL record describing a write of `value` to node offset or rel ID `id`.
+    fn wal_record(&self, id: u64, value: Value) -> (WalRecordType, WalPayload) {
+        let col_id = self.col_idx as u32;
+        if self.is_rel {
+            (
+                WalRecordType::RelUpdate,
+                WalPayload::RelUpdate {
+                    table_id: self.table_id,
+                    rel_id: id,
+                    col_id,
+                    value,
+                },
+            )
+        } else {
+            (
+                WalRecordType::NodeUpdate,
+                WalPayload::NodeUpdate {
+                    table_id: self.table_id,
+                    col_id,
+                    node_offset: id,
+                    value,
+                },
+            )
+        }
+    }
+}
+use executor::{
+    promote_for_comparison, AggregateOperator, ExtendOperator, FilterOperator, HashJoinOperator,
+    MarkJoinOperator, PhysicalOperator, ProjectOperator, ScanOperator, ValuesOperator,
+};
 pub use executor::{ExecutorConfig, QueryExecutor};
-use parser::ast::{CopyOptions, Literal, NodeFilter, ReturnItem, Statement};
+use binder::{BindError, Binder, ProjectedColumn, VariableType};
+use parser::ast::{
+    Clause, CopyOptions, Expression, Literal, MatchPattern, NodeFilter, OrderByItem, PathPattern,
+    Projection, RelDirection, ReturnItem, RowCount, SetItem, SingleQuery, Statement, ValueExpr,
+};
 use std::sync::atomic::{AtomicU64, Ordering};
 use storage::{
     BufferPool, Checkpointer, DatabaseHeader, DiskManager, NodeTable, PageRange, RelTable,
@@ -178,6 +243,32 @@ impl Default for DatabaseConfig {
     }
 }
 
+/// A query parsed and checked once by [`Database::prepare`] and run any
+/// number of times by [`Database::execute_prepared`] with different
+/// parameter values.
+///
+/// Only the parsed statement and the parameter types are kept, not a bound
+/// plan: every execution substitutes the values and binds the statement
+/// again.
+#[derive(Debug, Clone)]
+pub struct PreparedStatement {
+    /// Parsed statement, with its parameters still unbound.
+    statement: Statement,
+    /// Names of the parameters used by the statement.
+    parameters: Vec<String>,
+    /// Types the parameter values must have, where the statement implies one.
+    parameter_types: HashMap<String, DataType>,
+}
+
+impl PreparedStatement {
+    /// Returns the names of the `$name` parameters the query uses, in order
+    /// of first appearance.
+    #[must_use]
+    pub fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+}
+
 /// The main database struct that provides query execution.
 ///
 /// Can be used in two modes:
@@ -431,6 +522,12 @@ impl Database {
                     }
                 }
             }
+            WalPayload::NodeUpdate { .. }
+            | WalPayload::RelUpdate { .. }
+            | WalPayload::NodeDeletion { .. }
+            | WalPayload::RelDeletion { .. } => {
+                Self::apply_wal_modification(&record.payload, catalog, tables, rel_tables)?;
+            }
             // Other payload types are not applied during recovery (schema changes, etc.)
             // They would be persisted via catalog serialization
             _ => {}
@@ -439,6 +536,114 @@ impl Database {
         Ok(())
     }
 
+    /// Applies a committed update or deletion WAL record.
+    ///
+    /// A record whose row or relationship is missing or already deleted is
+    /// skipped: the WAL may still hold changes that a checkpoint saved before
+    /// it crashed ahead of truncating the log, so replay must be idempotent.
+    fn apply_wal_modification(
+        payload: &WalPayload,
+        catalog: &Catalog,
+        tables: &mut HashMap<String, Arc<NodeTable>>,
+        rel_tables: &mut HashMap<String, RelTable>,
+    ) -> Result<()> {
+        match payload {
+            WalPayload::NodeUpdate {
+                table_id,
+                col_id,
+                node_offset,
+                value,
+            } => {
+                if let Some(table_name) = catalog.table_name_by_id(*table_id) {
+                    if let Some(table) = tables.get_mut(&table_name) {
+                        let table = Arc::get_mut(table).ok_or_else(|| {
+                            RuzuError::ExecutionError("Cannot modify table during recovery".into())
+                        })?;
+                        if !Self::is_live_row(table, *node_offset) {
+                            return Ok(());
+                        }
+                        table
+                            .update(*node_offset as usize, *col_id as usize, value.clone())
+                            .map_err(|e| Self::replay_error(&table_name, &e))?;
+                    }
+                }
+            }
+            WalPayload::RelUpdate {
+                table_id,
+                rel_id,
+                col_id,
+                value,
+            } => {
+                if let Some(rel_table_name) = catalog.table_name_by_id(*table_id) {
+                    if let Some(rel_table) = rel_tables.get_mut(&rel_table_name) {
+                        if rel_table.get_properties(*rel_id).is_none() {
+                            return Ok(());
+                        }
+                        rel_table
+                            .update_property(*rel_id, *col_id as usize, value.clone())
+                            .map_err(|e| Self::replay_error(&rel_table_name, &e))?;
+                    }
+                }
+            }
+            WalPayload::NodeDeletion {
+                table_id,
+                node_offset,
+                ..
+            } => {
+                if let Some(table_name) = catalog.table_name_by_id(*table_id) {
+                    if let Some(table) = tables.get_mut(&table_name) {
+                        let table = Arc::get_mut(table).ok_or_else(|| {
+                            RuzuError::ExecutionError("Cannot modify table during recovery".into())
+                        })?;
+                        if !Self::is_live_row(table, *node_offset) {
+                            return Ok(());
+                        }
+                        table
+                            .delete(*node_offset as usize)
+                            .map_err(|e| Self::replay_error(&table_name, &e))?;
+                    }
+                }
+            }
+            WalPayload::RelDeletion {
+                table_id,
+                src,
+                dst,
+                rel_id,
+            } => {
+                if let Some(rel_table_name) = catalog.table_name_by_id(*table_id) {
+                    if let Some(rel_table) = rel_tables.get_mut(&rel_table_name) {
+                        let exists = rel_table
+                            .get_forward_edges(*src)
+                            .iter()
+                            .any(|&(to, id)| to == *dst && id == *rel_id);
+                        if !exists {
+                            return Ok(());
+                        }
+                        rel_table
+                            .delete(*src, *dst, *rel_id)
+                            .map_err(|e| Self::replay_error(&rel_table_name, &e))?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Whether `offset` holds a row of `table` that has not been deleted.
+    fn is_live_row(table: &NodeTable, offset: u64) -> bool {
+        usize::try_from(offset).is_ok_and(|offset| {
+            offset < table.row_count() && !table.is_deleted(offset)
+        })
+    }
+
+    /// Error for a committed WAL record that cannot be applied to `table`.
+    fn replay_error(table: &str, cause: &RuzuError) -> RuzuError {
+        RuzuError::ExecutionError(format!(
+            "WAL recovery failed to apply a change to table '{table}': {cause}"
+        ))
+    }
+
     /// Loads the database header and catalog from disk.
     ///
     /// Returns (catalog, header, `was_migrated`) where `was_migrated` is true if the database
@@ -844,8 +1049,106 @@ impl Database {
     /// Returns an error if parsing fails, the schema is invalid,
     /// or execution encounters an error.
     pub fn execute(&mut self, query: &str) -> Result<QueryResult> {
+        let mut statement = parser::parse_query(query)?;
+        binder::bind_parameters(&mut statement, &HashMap::new())?;
+        self.execute_statement(statement)
+    }
+
+    /// Executes a Cypher query whose `$name` parameters take their values
+    /// from `params`.
+    ///
+    /// This is [`Database::prepare`] followed by [`Database::execute_prepared`],
+    /// so a value of the wrong type for its parameter is an error. Parameter
+    /// values are never parsed as Cypher, so strings need no quoting or
+    /// escaping:
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use ruzu::{Database, Value};
+    /// let mut db = Database::new();
+    /// db.execute("CREATE NODE TABLE Person(name STRING, PRIMARY KEY(name))")?;
+    /// let params = HashMap::from([("name".to_string(), Value::String("O'Brien".into()))]);
+    /// db.execute_with_params("CREATE (:Person {name: $name})", &params)?;
+    /// # Ok::<(), ruzu::RuzuError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing fails, the statement does not bind, a
+    /// parameter has no value or a value of the wrong type, or execution
+    /// encounters an error.
+    pub fn execute_with_params<S: BuildHasher>(
+        &mut self,
+        query: &str,
+        params: &HashMap<String, Value, S>,
+    ) -> Result<QueryResult> {
+        let prepared = self.prepare(query)?;
+        self.execute_prepared(&prepared, params)
+    }
+
+    /// Parses and checks a query once for repeated execution with
+    /// [`Database::execute_prepared`], which saves parsing the query on every
+    /// run but still binds it each time.
+    ///
+    /// Every statement form is bound here, with its parameters standing for
+    /// NULL, so that undefined tables, variables and properties are reported
+    /// up front. The type each parameter must have is inferred from the
+    /// property, column or value it is compared with or assigned to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing fails or the statement does not bind.
+    pub fn prepare(&self, query: &str) -> Result<PreparedStatement> {
         let statement = parser::parse_query(query)?;
+        let parameters = binder::parameter_names(&statement);
+        let parameter_types = self.check_statement(&statement)?;
+        Ok(PreparedStatement {
+            statement,
+            parameters,
+            parameter_types,
+        })
+    }
+
+    /// Executes a prepared query with its parameters bound to `params`.
+    ///
+    /// Each value is substituted into the statement and checked against the
+    /// type inferred for its parameter (NULL fits any type, and integers and
+    /// floats mix). The substituted statement is then bound and executed as
+    /// [`Database::execute`] would, since the types of its output columns may
+    /// depend on the values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter has no value or a value of the wrong
+    /// type, the schema is invalid, or execution encounters an error.
+    pub fn execute_prepared<S: BuildHasher>(
+        &mut self,
+        prepared: &PreparedStatement,
+        params: &HashMap<String, Value, S>,
+    ) -> Result<QueryResult> {
+        let mut statement = prepared.statement.clone();
+        binder::bind_parameters(&mut statement, params)?;
+        for name in &prepared.parameters {
+            let expected = prepared.parameter_types.get(name);
+            let actual = params.get(name.as_str()).and_then(Value::data_type);
+            if let (Some(&expected), Some(actual)) = (expected, actual) {
+                let numeric = expected.is_numeric() && actual.is_numeric();
+                if expected != actual && !numeric {
+                    return Err(BindError::ParameterType {
+                        name: name.clone(),
+                        expected,
+                        actual,
+                    }
+                    .into());
+                }
+            }
+        }
+        self.execute_statement(statement)
+    }
 
+    /// Executes a parsed statement whose parameters have been bound.
+    #[allow(clippy::too_many_lines)]
+    fn execute_statement(&mut self, statement: Statement) -> Result<QueryResult> {
         match statement {
             Statement::CreateNodeTable {
                 table_name,
@@ -868,8 +1171,8 @@ impl Database {
             } => self.execute_match(&var, &label, filter, &QueryModifiers {
                 projections: &projections,
                 order_by: order_by.as_ref(),
-                skip,
-                limit,
+                skip: Self::row_count(skip.as_ref())?,
+                limit: Self::row_count(limit.as_ref())?,
             }),
 
             Statement::CreateRelTable {
@@ -894,6 +1197,7 @@ impl Database {
                 src_node,
                 rel_var,
                 rel_type,
+                rel_filters,
                 dst_node,
                 filter,
                 projections,
@@ -901,23 +1205,90 @@ impl Database {
                 skip,
                 limit,
                 path_bounds,
+                direction,
             } => self.execute_match_rel(
                 &RelPattern {
                     src_node: &src_node,
                     rel_var: rel_var.as_ref(),
                     rel_type: &rel_type,
+                    rel_filters: &rel_filters,
                     dst_node: &dst_node,
                     filter: filter.as_ref(),
                     path_bounds,
+                    direction,
                 },
                 &QueryModifiers {
                     projections: &projections,
                     order_by: order_by.as_ref(),
-                    skip,
-                    limit,
+                    skip: Self::row_count(skip.as_ref())?,
+                    limit: Self::row_count(limit.as_ref())?,
                 },
             ),
 
+            Statement::Merge {
+                var,
+                label,
+                properties,
+                on_create,
+                on_match,
+            } => {
+                let properties: Vec<(String, Value)> = properties
+                    .iter()
+                    .map(|(key, literal)| (key.clone(), literal_to_value(literal)))
+                    .collect();
+                self.execute_merge(&var, &label, &properties, &on_create, &on_match)
+            }
+
+            Statement::UnwindWrite {
+                list,
+                alias,
+                node,
+                merge,
+                on_create,
+                on_match,
+            } => self.execute_unwind_write(&list, &alias, &node, merge, &on_create, &on_match),
+
+            Statement::MatchMerge {
+                src_node,
+                dst_node,
+                rel_var,
+                rel_type,
+                rel_props,
+                src_var,
+                dst_var,
+                on_create,
+                on_match,
+            } => self.execute_match_merge(
+                &src_node,
+                &dst_node,
+                rel_var.as_deref(),
+                &rel_type,
+                &rel_props,
+                &src_var,
+                &dst_var,
+                &on_create,
+                &on_match,
+            ),
+
+            Statement::MatchSet {
+                pattern,
+                filter,
+                items,
+            } => self.execute_match_set(&pattern, filter.as_ref(), &items),
+
+            Statement::MatchDelete {
+                pattern,
+                filter,
+                vars,
+                detach,
+            } => self.execute_match_delete(&pattern, filter.as_ref(), &vars, detach),
+
+            Statement::Query {
+                clauses,
+                projection,
+            } => self.execute_query(clauses, projection),
+            Statement::Union { queries, all } => self.execute_union(queries, all),
+
             Statement::Copy {
                 table_name,
                 file_path,
@@ -960,12 +1331,14 @@ impl Database {
                                 format!("{var}.{property}")
                             }
                             parser::ast::ReturnItem::Aggregate(agg) => {
-                                if let Some((var, prop)) = &agg.input {
-                                    format!("{:?}({}.{})", agg.function, var, prop)
+                                if let Some(input) = &agg.input {
+                                    format!("{:?}({input})", agg.function)
                                 } else {
                                     format!("{:?}(*)", agg.function)
                                 }
                             }
+                            parser::ast::ReturnItem::Variable(_)
+                            | parser::ast::ReturnItem::Expression(_) => p.column_name(),
                         }
                     }).collect();
                     plan_text.push_str(&proj_names.join(", "));
@@ -980,6 +1353,7 @@ impl Database {
                 dst_node,
                 filter,
                 projections,
+                direction,
                 ..
             } => {
                 let mut plan_text = String::new();
@@ -993,7 +1367,11 @@ impl Database {
                 plan_text.push_str(&rel_type);
                 plan_text.push_str(" (");
                 plan_text.push_str(&src_node.var);
-                plan_text.push_str(" -> ");
+                plan_text.push_str(match direction {
+                    RelDirection::Forward => " -> ",
+                    RelDirection::Backward => " <- ",
+                    RelDirection::Both => " - ",
+                });
                 plan_text.push_str(&dst_node.var);
                 plan_text.push_str(")\n");
 
@@ -1009,12 +1387,14 @@ impl Database {
                                 format!("{var}.{property}")
                             }
                             parser::ast::ReturnItem::Aggregate(agg) => {
-                                if let Some((var, prop)) = &agg.input {
-                                    format!("{:?}({}.{})", agg.function, var, prop)
+                                if let Some(input) = &agg.input {
+                                    format!("{:?}({input})", agg.function)
                                 } else {
                                     format!("{:?}(*)", agg.function)
                                 }
                             }
+                            parser::ast::ReturnItem::Variable(_)
+                            | parser::ast::ReturnItem::Expression(_) => p.column_name(),
                         }
                     }).collect();
                     plan_text.push_str(&proj_names.join(", "));
@@ -1041,17 +1421,9 @@ impl Database {
         let column_defs: Vec<ColumnDef> = columns
             .into_iter()
             .map(|(name, type_str)| {
-                let data_type = match type_str.to_uppercase().as_str() {
-                    "INT64" => DataType::Int64,
-                    "STRING" => DataType::String,
-                    "FLOAT64" => DataType::Float64,
-                    "BOOL" => DataType::Bool,
-                    _ => {
-                        return Err(RuzuError::SchemaError(format!(
-                            "Unknown data type: {type_str}"
-                        )))
-                    }
-                };
+                let data_type = DataType::from_name(&type_str).ok_or_else(|| {
+                    RuzuError::SchemaError(format!("Unknown data type: {type_str}"))
+                })?;
                 ColumnDef::new(name, data_type)
             })
             .collect::<Result<Vec<_>>>()?;
@@ -1077,6 +1449,19 @@ impl Database {
         label: &str,
         properties: &[(String, Literal)],
     ) -> Result<QueryResult> {
+        let values = properties
+            .iter()
+            .map(|(name, literal)| (name.clone(), literal_to_value(literal)))
+            .collect();
+        self.insert_node(label, values)?;
+
+        Ok(QueryResult::empty())
+    }
+
+    /// Inserts a single node, WAL-logging it first.
+    ///
+    /// Values are promoted to FLOAT64 where the column requires it.
+    fn insert_node(&mut self, label: &str, values: HashMap<String, Value>) -> Result<()> {
         // Get table schema for table_id and column ordering
         let schema = self
             .catalog
@@ -1084,32 +1469,18 @@ impl Database {
             .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
         let table_id = schema.table_id;
 
-        // Get the table
-        let table = self
-            .tables
-            .get_mut(label)
-            .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
+        if !self.tables.contains_key(label) {
+            return Err(RuzuError::SchemaError(format!("Table '{label}' does not exist")));
+        }
 
         // Convert properties to a row, with type promotion for FLOAT64 columns
         let mut row: HashMap<String, Value> = HashMap::new();
-        for (name, literal) in properties {
-            let value = literal_to_value(literal);
-            // Promote Int64 to Float64 if the column type is FLOAT64
-            #[allow(clippy::cast_precision_loss)]
-            let value = if let Value::Int64(n) = &value {
-                if let Some(col) = schema.columns.iter().find(|c| c.name == *name) {
-                    if col.data_type == DataType::Float64 {
-                        Value::Float64(*n as f64)
-                    } else {
-                        value
-                    }
-                } else {
-                    value
-                }
-            } else {
-                value
+        for (name, value) in values {
+            let value = match schema.get_column(&name) {
+                Some(col) => coerce_to_column_type(value, col.data_type),
+                None => value,
             };
-            row.insert(name.clone(), value);
+            row.insert(name, value);
         }
 
         // Build row values in schema column order for WAL
@@ -1123,42 +1494,20 @@ impl Database {
         }
 
         // Write WAL record BEFORE modifying data (Write-Ahead Logging principle)
-        if let Some(ref mut wal_writer) = self.wal_writer {
-            let tx_id = self.next_tx_id.fetch_add(1, Ordering::Relaxed);
-
-            // Begin transaction
-            let begin_lsn = wal_writer.next_lsn();
-            let begin_record = WalRecord::begin_transaction(tx_id, begin_lsn);
-            wal_writer.append(&begin_record)?;
-
-            // Table insertion record
-            let insert_lsn = wal_writer.next_lsn();
-            let insert_record = WalRecord::new(
-                WalRecordType::TableInsertion,
-                tx_id,
-                insert_lsn,
-                WalPayload::TableInsertion {
-                    table_id,
-                    rows: vec![row_values],
-                },
-            );
-            wal_writer.append(&insert_record)?;
-
-            // Commit transaction
-            let commit_lsn = wal_writer.next_lsn();
-            let commit_record = WalRecord::commit(tx_id, commit_lsn);
-            wal_writer.append(&commit_record)?;
-
-            // Flush WAL to ensure durability
-            if self.config.wal_sync {
-                wal_writer.sync()?;
-            } else {
-                wal_writer.flush()?;
-            }
-        }
+        self.log_transaction(vec![(
+            WalRecordType::TableInsertion,
+            WalPayload::TableInsertion {
+                table_id,
+                rows: vec![row_values],
+            },
+        )])?;
 
         // Need to get mutable access to the table
         // Since we're using Arc, we need to get inner mutable reference
+        let table = self
+            .tables
+            .get_mut(label)
+            .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
         let table = Arc::get_mut(table).ok_or_else(|| {
             RuzuError::ExecutionError("Cannot modify table: multiple references exist".into())
         })?;
@@ -1169,7 +1518,7 @@ impl Database {
         // Mark database as dirty
         self.dirty = true;
 
-        Ok(QueryResult::empty())
+        Ok(())
     }
 
     fn execute_match(
@@ -1202,11 +1551,20 @@ impl Database {
                 ReturnItem::Aggregate(_) => {
                     has_aggregates = true;
                 }
+                // Not produced by the single-stage MATCH grammar
+                ReturnItem::Variable(_) | ReturnItem::Expression(_) => {}
             }
         }
 
-        // Build the execution pipeline
-        let scan = ScanOperator::new(Arc::clone(table), var.to_string());
+        // Build the execution pipeline, looking up `var.pk IN [...]` keys
+        // instead of scanning the whole table
+        let mut scan = ScanOperator::new(Arc::clone(table), var.to_string());
+        if let Some(offsets) = filter
+            .as_ref()
+            .and_then(|expr| Self::pk_in_list_offsets(table, var, expr))
+        {
+            scan = scan.with_offsets(offsets);
+        }
         let mut operator: Box<dyn PhysicalOperator> = Box::new(scan);
 
         // Add filter if present
@@ -1214,15 +1572,18 @@ impl Database {
             operator = Box::new(FilterOperator::new(operator, expr));
         }
 
-        // If we have aggregates, handle them specially
+        // Aggregates group on the non-aggregate items, and ORDER BY, SKIP and
+        // LIMIT apply to the groups
         if has_aggregates {
-            // Collect all rows first for aggregation
-            let mut rows: Vec<Row> = Vec::new();
-            while let Some(row) = operator.next()? {
-                rows.push(row);
-            }
-
-            return Self::compute_aggregates(&rows, projections);
+            let rows = Self::aggregate_return_items(operator, projections)?;
+            let output_columns = projections.iter().map(ReturnItem::column_name).collect();
+            return Ok(Self::apply_modifiers_and_build_result(
+                rows,
+                output_columns,
+                order_by,
+                skip,
+                limit,
+            ));
         }
 
         // Add projection for non-aggregate queries
@@ -1239,6 +1600,15 @@ impl Database {
         Ok(Self::apply_modifiers_and_build_result(rows, output_columns, order_by, skip, limit))
     }
 
+    /// Returns the number of rows of a SKIP or LIMIT clause.
+    fn row_count(count: Option<&RowCount>) -> Result<Option<i64>> {
+        match count {
+            None => Ok(None),
+            Some(RowCount::Count(n)) => Ok(Some(*n)),
+            Some(RowCount::Parameter(name)) => Err(BindError::MissingParameter(name.clone()).into()),
+        }
+    }
+
     /// Applies ORDER BY sorting, SKIP, LIMIT, and builds the final `QueryResult`.
     fn apply_modifiers_and_build_result(
         mut rows: Vec<Row>,
@@ -1249,29 +1619,20 @@ impl Database {
     ) -> QueryResult {
         // Apply ORDER BY if present
         if let Some(order_items) = order_by {
-            rows.sort_by(|a, b| {
-                for order_item in order_items {
-                    let col_name = format!("{}.{}", order_item.var, order_item.property);
-                    let val_a = a.get(&col_name);
-                    let val_b = b.get(&col_name);
-
-                    let ordering = match (val_a, val_b) {
-                        (Some(va), Some(vb)) => va.compare(vb).unwrap_or(std::cmp::Ordering::Equal),
-                        (None, Some(_)) => std::cmp::Ordering::Greater, // NULLs last
-                        (Some(_), None) => std::cmp::Ordering::Less,
-                        (None, None) => std::cmp::Ordering::Equal,
-                    };
-
-                    if ordering != std::cmp::Ordering::Equal {
-                        return if order_item.ascending {
-                            ordering
-                        } else {
-                            ordering.reverse()
-                        };
-                    }
-                }
-                std::cmp::Ordering::Equal
-            });
+            let mut keyed: Vec<(Row, Vec<Value>)> = rows
+                .into_iter()
+                .map(|row| {
+                    let keys = order_items
+                        .iter()
+                        .map(|item| {
+                            executor::evaluate_value(&item.expr, &row).unwrap_or(Value::Null)
+                        })
+                        .collect();
+                    (row, keys)
+                })
+                .collect();
+            Self::sort_by_keys(&mut keyed, order_items);
+            rows = keyed.into_iter().map(|(row, _)| row).collect();
         }
 
         // Apply SKIP
@@ -1294,303 +1655,2316 @@ impl Database {
         result
     }
 
-    #[allow(clippy::too_many_lines)]
-    fn compute_aggregates(
-        rows: &[Row],
-        projections: &[ReturnItem],
-    ) -> Result<QueryResult> {
-        use parser::ast::AstAggregateFunction;
+    /// Sorts rows by their precomputed ORDER BY key values.
+    ///
+    /// NULLs sort after all other values in ascending order, and therefore
+    /// first in descending order.
+    fn sort_by_keys(rows: &mut [(Row, Vec<Value>)], order_by: &[parser::ast::OrderByItem]) {
+        rows.sort_by(|(_, a), (_, b)| {
+            for ((order_item, val_a), val_b) in order_by.iter().zip(a).zip(b) {
+                let ordering = match (val_a.is_null(), val_b.is_null()) {
+                    (false, false) => val_a.compare(val_b).unwrap_or(std::cmp::Ordering::Equal),
+                    (true, false) => std::cmp::Ordering::Greater, // NULLs last
+                    (false, true) => std::cmp::Ordering::Less,
+                    (true, true) => std::cmp::Ordering::Equal,
+                };
 
-        let mut result_row = Row::new();
-        let mut output_columns = Vec::new();
+                if ordering != std::cmp::Ordering::Equal {
+                    return if order_item.ascending {
+                        ordering
+                    } else {
+                        ordering.reverse()
+                    };
+                }
+            }
+            std::cmp::Ordering::Equal
+        });
+    }
 
+    /// Groups the rows of `operator` on the non-aggregate items of
+    /// `projections` and computes the aggregates of each group, producing one
+    /// row of RETURN columns per group.
+    fn aggregate_return_items(
+        operator: Box<dyn PhysicalOperator + '_>,
+        projections: &[ReturnItem],
+    ) -> Result<Vec<Row>> {
+        let mut group_by = Vec::new();
+        let mut aggregates = Vec::new();
         for item in projections {
             match item {
-                ReturnItem::Projection { var: v, property } => {
-                    let col_name = format!("{v}.{property}");
-                    output_columns.push(col_name.clone());
-                    // For non-aggregates with aggregates, use the first row's value (if any)
-                    if let Some(first_row) = rows.first() {
-                        if let Some(val) = first_row.get(&col_name) {
-                            result_row.set(col_name, val.clone());
-                        }
-                    }
-                }
-                ReturnItem::Aggregate(agg) => {
-                    let agg_name = match agg.function {
-                        AstAggregateFunction::Count => "COUNT",
-                        AstAggregateFunction::Sum => "SUM",
-                        AstAggregateFunction::Avg => "AVG",
-                        AstAggregateFunction::Min => "MIN",
-                        AstAggregateFunction::Max => "MAX",
-                    };
+                ReturnItem::Projection { var, property } => group_by.push(ValueExpr::Property {
+                    var: var.clone(),
+                    property: property.clone(),
+                }),
+                ReturnItem::Aggregate(agg) => aggregates.push((item.column_name(), agg.clone())),
+                ReturnItem::Variable(name) => group_by.push(ValueExpr::Variable(name.clone())),
+                ReturnItem::Expression(expr) => group_by.push(expr.clone()),
+            }
+        }
 
-                    let col_name = if let Some((v, p)) = &agg.input {
-                        format!("{agg_name}({v}.{p})")
-                    } else {
-                        format!("{agg_name}(*)")
-                    };
-                    output_columns.push(col_name.clone());
-
-                    // Compute aggregate value
-                    let agg_value = match agg.function {
-                        AstAggregateFunction::Count => {
-                            if agg.input.is_none() {
-                                // COUNT(*)
-                                Value::Int64(i64::try_from(rows.len()).unwrap_or(i64::MAX))
-                            } else {
-                                // COUNT(property) - count non-null values
-                                let (v, p) = agg.input.as_ref().unwrap();
-                                let prop_name = format!("{v}.{p}");
-                                let count = rows.iter()
-                                    .filter(|r| r.get(&prop_name).is_some() && !matches!(r.get(&prop_name), Some(Value::Null)))
-                                    .count();
-                                Value::Int64(i64::try_from(count).unwrap_or(i64::MAX))
-                            }
-                        }
-                        AstAggregateFunction::Sum => {
-                            let (v, p) = agg.input.as_ref().ok_or_else(|| {
-                                RuzuError::ExecutionError("SUM requires an argument".into())
-                            })?;
-                            let prop_name = format!("{v}.{p}");
-                            let sum: i64 = rows.iter()
-                                .filter_map(|r| r.get(&prop_name))
-                                .filter_map(|v| match v {
-                                    Value::Int64(n) => Some(*n),
-                                    _ => None,
-                                })
-                                .sum();
-                            Value::Int64(sum)
-                        }
-                        AstAggregateFunction::Avg => {
-                            let (v, p) = agg.input.as_ref().ok_or_else(|| {
-                                RuzuError::ExecutionError("AVG requires an argument".into())
-                            })?;
-                            let prop_name = format!("{v}.{p}");
-                            let values: Vec<i64> = rows.iter()
-                                .filter_map(|r| r.get(&prop_name))
-                                .filter_map(|v| match v {
-                                    Value::Int64(n) => Some(*n),
-                                    _ => None,
-                                })
-                                .collect();
-                            if values.is_empty() {
-                                Value::Null
-                            } else {
-                                let sum: i64 = values.iter().sum();
-                                #[allow(clippy::cast_precision_loss)]
-                                let avg = sum as f64 / values.len() as f64;
-                                Value::Float64(avg)
-                            }
-                        }
-                        AstAggregateFunction::Min => {
-                            let (v, p) = agg.input.as_ref().ok_or_else(|| {
-                                RuzuError::ExecutionError("MIN requires an argument".into())
-                            })?;
-                            let prop_name = format!("{v}.{p}");
-                            let values: Vec<&Value> = rows.iter()
-                                .filter_map(|r| r.get(&prop_name))
-                                .filter(|v| !v.is_null())
-                                .collect();
-                            if values.is_empty() {
-                                Value::Null
-                            } else {
-                                let mut min_val = values[0].clone();
-                                for v in &values[1..] {
-                                    if let Some(std::cmp::Ordering::Less) = (*v).compare(&min_val) {
-                                        min_val = (*v).clone();
-                                    }
-                                }
-                                min_val
-                            }
-                        }
-                        AstAggregateFunction::Max => {
-                            let (v, p) = agg.input.as_ref().ok_or_else(|| {
-                                RuzuError::ExecutionError("MAX requires an argument".into())
-                            })?;
-                            let prop_name = format!("{v}.{p}");
-                            let values: Vec<&Value> = rows.iter()
-                                .filter_map(|r| r.get(&prop_name))
-                                .filter(|v| !v.is_null())
-                                .collect();
-                            if values.is_empty() {
-                                Value::Null
-                            } else {
-                                let mut max_val = values[0].clone();
-                                for v in &values[1..] {
-                                    if let Some(std::cmp::Ordering::Greater) = (*v).compare(&max_val) {
-                                        max_val = (*v).clone();
-                                    }
-                                }
-                                max_val
-                            }
-                        }
-                    };
-                    result_row.set(col_name, agg_value);
-                }
+        let mut aggregate = AggregateOperator::new(operator, group_by, aggregates);
+        let mut rows = Vec::new();
+        while let Some(group) = aggregate.next()? {
+            let mut row = Row::new();
+            for item in projections {
+                let column = item.column_name();
+                let value = match item {
+                    ReturnItem::Expression(expr) => executor::evaluate_value(expr, &group)?,
+                    _ => group.get(&column).cloned().unwrap_or(Value::Null),
+                };
+                row.set(column, value);
             }
+            rows.push(row);
         }
+        Ok(rows)
+    }
 
-        let mut result = QueryResult::new(output_columns);
-        result.add_row(result_row);
+    /// Executes a multi-stage query of MATCH, OPTIONAL MATCH, WITH and UNWIND
+    /// clauses ending in RETURN.
+    fn execute_query(&self, mut clauses: Vec<Clause>, mut projection: Projection) -> Result<QueryResult> {
+        let (clause_columns, columns) = self.bind_query(&mut clauses, &mut projection)?;
+        let rows = self.run_query(&clauses, &clause_columns, &projection, &columns)?;
+
+        let mut result = QueryResult::new(columns.into_iter().map(|c| c.name).collect());
+        for row in rows {
+            result.add_row(row);
+        }
         Ok(result)
     }
 
-    fn execute_create_rel_table(
-        &mut self,
-        table_name: String,
-        src_table: String,
-        dst_table: String,
-        columns: Vec<(String, String)>,
-    ) -> Result<QueryResult> {
-        // Validate source table exists
-        if !self.catalog.table_exists(&src_table) {
-            return Err(RuzuError::SchemaError(format!(
-                "Source table '{src_table}' does not exist"
-            )));
+    /// Executes read queries combined with UNION or UNION ALL.
+    ///
+    /// Every query is bound before any of them runs. Output columns are
+    /// matched by position and named after the first query; plain UNION
+    /// drops duplicate rows.
+    fn execute_union(&self, mut queries: Vec<SingleQuery>, all: bool) -> Result<QueryResult> {
+        let mut bound = Vec::with_capacity(queries.len());
+        for query in &mut queries {
+            bound.push(self.bind_query(&mut query.clauses, &mut query.projection)?);
         }
+        let query_columns: Vec<Vec<ProjectedColumn>> =
+            bound.iter().map(|(_, columns)| columns.clone()).collect();
+        let columns = Binder::bind_union(&query_columns)?;
+
+        let mut result = QueryResult::new(columns.iter().map(|c| c.name.clone()).collect());
+        let mut seen = std::collections::HashSet::new();
+        for (query, (clause_columns, own_columns)) in queries.iter().zip(&bound) {
+            let rows = self.run_query(&query.clauses, clause_columns, &query.projection, own_columns)?;
+            for row in rows {
+                let values: Vec<Value> = own_columns
+                    .iter()
+                    .zip(&columns)
+                    .map(|(own, column)| {
+                        let value = row.get(&own.name).cloned().unwrap_or(Value::Null);
+                        coerce_to_column_type(value, column.data_type)
+                    })
+                    .collect();
+                if !all && !seen.insert(values.clone()) {
+                    continue;
+                }
+
+                let mut output = Row::new();
+                for (column, value) in columns.iter().zip(values) {
+                    output.set(column.name.clone(), value);
+                }
+                result.add_row(output);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Binds a parsed statement without running it, so that undefined
+    /// tables, variables and properties are reported before any data is read
+    /// or written.
+    ///
+    /// Returns the types expected of the statement's parameters, which bind
+    /// as NULL here.
+    #[allow(clippy::too_many_lines)]
+    fn check_statement(&self, statement: &Statement) -> Result<HashMap<String, DataType>> {
+        let mut binder = Binder::new(&self.catalog);
+        match statement.clone() {
+            Statement::CreateNodeTable { .. } | Statement::CreateRelTable { .. } => {}
+            Statement::Copy { table_name, .. } => {
+                if !self.catalog.table_exists(&table_name)
+                    && !self.catalog.rel_table_exists(&table_name)
+                {
+                    return Err(BindError::UndefinedTable(table_name).into());
+                }
+            }
+            Statement::CreateNode { label, properties } => {
+                let schema = self
+                    .catalog
+                    .get_table(&label)
+                    .ok_or_else(|| RuzuError::from(BindError::UndefinedTable(label.clone())))?;
+                for (key, literal) in properties {
+                    let column = schema
+                        .get_column(&key)
+                        .ok_or_else(|| BindError::UndefinedColumn(label.clone(), key.clone()))?;
+                    binder.expect_parameter(&ValueExpr::Literal(literal), column.data_type);
+                }
+            }
+            Statement::MatchCreate {
+                src_node,
+                dst_node,
+                rel_type,
+                rel_props,
+                ..
+            } => {
+                Self::check_nodes(&mut binder, [src_node, dst_node])?;
+                self.check_rel_properties(&binder, &rel_type, rel_props)?;
+            }
+            Statement::Match {
+                var,
+                label,
+                mut filter,
+                projections,
+                order_by,
+                ..
+            } => {
+                let start = NodeFilter {
+                    var,
+                    label,
+                    property_filters: Vec::new(),
+                };
+                Self::check_nodes(&mut binder, [start])?;
+                Self::check_legacy_match(&binder, filter.as_mut(), &projections, order_by)?;
+            }
+            Statement::MatchRel {
+                src_node,
+                rel_var,
+                rel_type,
+                rel_filters,
+                dst_node,
+                mut filter,
+                projections,
+                order_by,
+                direction,
+                ..
+            } => {
+                let pattern = MatchPattern::Rel {
+                    src_node,
+                    rel_var,
+                    rel_type,
+                    rel_filters,
+                    dst_node,
+                    direction,
+                };
+                binder.bind_pattern(&mut PathPattern::from(pattern))?;
+                Self::check_legacy_match(&binder, filter.as_mut(), &projections, order_by)?;
+            }
+            Statement::Merge {
+                var,
+                label,
+                properties,
+                on_create,
+                on_match,
+            } => {
+                let node = NodeFilter {
+                    var,
+                    label,
+                    property_filters: properties
+                        .into_iter()
+                        .map(|(key, literal)| (key, ValueExpr::Literal(literal)))
+                        .collect(),
+                };
+                Self::check_nodes(&mut binder, [node.clone()])?;
+                let items = on_create.iter().chain(&on_match);
+                self.check_set_items(&binder, &MatchPattern::Node(node), items)?;
+            }
+            Statement::UnwindWrite {
+                list,
+                alias,
+                node,
+                on_create,
+                on_match,
+                ..
+            } => {
+                binder.bind_unwind(&list, &alias)?;
+                Self::check_nodes(&mut binder, [node.clone()])?;
+                let items = on_create.iter().chain(&on_match);
+                self.check_set_items(&binder, &MatchPattern::Node(node), items)?;
+            }
+            Statement::MatchMerge {
+                src_node,
+                dst_node,
+                rel_var,
+                rel_type,
+                rel_props,
+                src_var,
+                dst_var,
+                on_create,
+                on_match,
+            } => {
+                let find_node = |var: &str| {
+                    [&src_node, &dst_node]
+                        .into_iter()
+                        .find(|node| node.var == var)
+                        .cloned()
+                        .ok_or_else(|| {
+                            RuzuError::from(BindError::UndefinedVariable(var.to_string()))
+                        })
+                };
+                let pattern = MatchPattern::Rel {
+                    src_node: find_node(&src_var)?,
+                    rel_var,
+                    rel_type: rel_type.clone(),
+                    rel_filters: Vec::new(),
+                    dst_node: find_node(&dst_var)?,
+                    direction: RelDirection::Forward,
+                };
+                binder.bind_pattern(&mut PathPattern::from(pattern.clone()))?;
+                self.check_rel_properties(&binder, &rel_type, rel_props)?;
+                self.check_set_items(&binder, &pattern, on_create.iter().chain(&on_match))?;
+            }
+            Statement::MatchSet {
+                pattern,
+                mut filter,
+                items,
+            } => {
+                binder.bind_pattern(&mut PathPattern::from(pattern.clone()))?;
+                if let Some(expr) = &mut filter {
+                    binder.bind_where(expr)?;
+                }
+                self.check_set_items(&binder, &pattern, items.iter())?;
+            }
+            Statement::MatchDelete {
+                pattern,
+                mut filter,
+                vars,
+                ..
+            } => {
+                binder.bind_pattern(&mut PathPattern::from(pattern))?;
+                if let Some(expr) = &mut filter {
+                    binder.bind_where(expr)?;
+                }
+                for var in &vars {
+                    binder.validate_variable(var)?;
+                }
+            }
+            Statement::Query {
+                mut clauses,
+                mut projection,
+            } => {
+                Self::bind_query_with(&mut binder, &mut clauses, &mut projection)?;
+            }
+            Statement::Union { queries, .. } => {
+                let mut types = HashMap::new();
+                for mut query in queries {
+                    let mut binder = Binder::new(&self.catalog);
+                    Self::bind_query_with(&mut binder, &mut query.clauses, &mut query.projection)?;
+                    for (name, data_type) in binder.parameter_types() {
+                        types.entry(name).or_insert(data_type);
+                    }
+                }
+                return Ok(types);
+            }
+            Statement::Explain { inner } => return self.check_statement(&inner),
+        }
+        Ok(binder.parameter_types())
+    }
+
+    /// Binds standalone node patterns, such as the matched endpoints of a
+    /// CREATE or MERGE of a relationship.
+    fn check_nodes(
+        binder: &mut Binder<'_>,
+        nodes: impl IntoIterator<Item = NodeFilter>,
+    ) -> Result<()> {
+        for node in nodes {
+            binder.bind_pattern(&mut PathPattern::from(MatchPattern::Node(node)))?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the properties given to a created or merged relationship
+    /// are columns of its table.
+    fn check_rel_properties(
+        &self,
+        binder: &Binder<'_>,
+        rel_type: &str,
+        properties: Vec<(String, Literal)>,
+    ) -> Result<()> {
+        let schema = self
+            .catalog
+            .get_rel_table(rel_type)
+            .ok_or_else(|| RuzuError::from(BindError::UndefinedTable(rel_type.to_string())))?;
+        for (key, literal) in properties {
+            let column = schema
+                .get_column(&key)
+                .ok_or_else(|| BindError::UndefinedColumn(rel_type.to_string(), key.clone()))?;
+            binder.expect_parameter(&ValueExpr::Literal(literal), column.data_type);
+        }
+        Ok(())
+    }
+
+    /// Checks the targets and values of SET assignments on `pattern`, whose
+    /// variables `binder` has bound.
+    fn check_set_items<'i>(
+        &self,
+        binder: &Binder<'_>,
+        pattern: &MatchPattern,
+        items: impl Iterator<Item = &'i SetItem>,
+    ) -> Result<()> {
+        for item in items {
+            let target = self.resolve_set_target(pattern, item)?;
+            binder.validate_value_expr(&item.value)?;
+            binder.expect_parameter(&item.value, target.data_type);
+        }
+        Ok(())
+    }
+
+    /// Checks the WHERE, RETURN and ORDER BY of a single-pattern MATCH whose
+    /// pattern `binder` has bound.
+    fn check_legacy_match(
+        binder: &Binder<'_>,
+        filter: Option<&mut Expression>,
+        projections: &[ReturnItem],
+        order_by: Option<Vec<OrderByItem>>,
+    ) -> Result<()> {
+        if let Some(expr) = filter {
+            binder.bind_where(expr)?;
+        }
+        for item in projections {
+            match item {
+                ReturnItem::Projection { var, property } => {
+                    binder.validate_property(var, property)?;
+                }
+                ReturnItem::Variable(name) => {
+                    binder.validate_variable(name)?;
+                }
+                ReturnItem::Aggregate(agg) => {
+                    binder.bind_aggregate(agg)?;
+                }
+                ReturnItem::Expression(expr) => binder.validate_value_expr(expr)?,
+            }
+        }
+        for item in order_by.into_iter().flatten() {
+            binder.validate_value_expr(&item.expr)?;
+        }
+        Ok(())
+    }
+
+    /// Binds the clauses and RETURN of a multi-stage query, returning the
+    /// columns projected by each clause (empty for MATCH and UNWIND) and by
+    /// the RETURN. Nodes and relationships returned whole are MAP columns.
+    ///
+    /// All clauses are bound before any data is read, so undefined variables
+    /// and properties are reported up front.
+    fn bind_query(
+        &self,
+        clauses: &mut [Clause],
+        projection: &mut Projection,
+    ) -> Result<(Vec<Vec<ProjectedColumn>>, Vec<ProjectedColumn>)> {
+        Self::bind_query_with(&mut Binder::new(&self.catalog), clauses, projection)
+    }
+
+    /// Binds a multi-stage query with `binder`, as [`Database::bind_query`].
+    fn bind_query_with(
+        binder: &mut Binder<'_>,
+        clauses: &mut [Clause],
+        projection: &mut Projection,
+    ) -> Result<(Vec<Vec<ProjectedColumn>>, Vec<ProjectedColumn>)> {
+        let mut clause_columns = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            match clause {
+                Clause::Match {
+                    patterns, filter, ..
+                } => {
+                    for pattern in patterns.iter_mut() {
+                        binder.bind_pattern(pattern)?;
+                    }
+                    if let Some(expr) = filter {
+                        binder.bind_where(expr)?;
+                    }
+                    clause_columns.push(Vec::new());
+                }
+                Clause::With(with) => clause_columns.push(binder.bind_projection(with, true)?),
+                Clause::Unwind { list, alias } => {
+                    binder.bind_unwind(list, alias)?;
+                    clause_columns.push(Vec::new());
+                }
+            }
+        }
+        let mut columns = binder.bind_projection(projection, false)?;
+        for column in columns.iter_mut().filter(|c| Self::is_entity(c)) {
+            column.data_type = DataType::Map;
+        }
+        Ok((clause_columns, columns))
+    }
+
+    /// Runs the bound clauses of a multi-stage query and its RETURN.
+    ///
+    /// Rows flow through the clauses in order: MATCH clauses extend each row
+    /// with the matches of their patterns, UNWIND expands it into one row per
+    /// list element, and each WITH and the final RETURN project, filter, sort
+    /// and truncate them.
+    fn run_query(
+        &self,
+        clauses: &[Clause],
+        clause_columns: &[Vec<ProjectedColumn>],
+        projection: &Projection,
+        columns: &[ProjectedColumn],
+    ) -> Result<Vec<Row>> {
+        let mut rows = vec![Row::new()];
+        for (clause, clause_columns) in clauses.iter().zip(clause_columns) {
+            rows = match clause {
+                Clause::Match {
+                    patterns,
+                    filter,
+                    optional: false,
+                } => self.match_patterns(rows, patterns, filter.as_ref())?,
+                Clause::Match {
+                    patterns,
+                    filter,
+                    optional: true,
+                } => self.optional_match_patterns(&rows, patterns, filter.as_ref())?,
+                Clause::With(with) => Self::project_rows(rows, with, clause_columns)?,
+                Clause::Unwind { list, alias } => Self::unwind_rows(rows, list, alias)?,
+            };
+        }
+        let mut rows = Self::project_rows(rows, projection, columns)?;
+        for column in columns.iter().filter(|c| Self::is_entity(c)) {
+            for row in &mut rows {
+                Self::collect_entity(row, &column.name);
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Replaces the `name.prop` columns of a node or relationship carried by
+    /// a projection with a single `name` column holding its properties as a
+    /// map. An entity left unbound by OPTIONAL MATCH becomes NULL.
+    fn collect_entity(row: &mut Row, name: &str) {
+        let prefix = format!("{name}.");
+        let keys: Vec<String> = row
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect();
+        let mut properties = std::collections::BTreeMap::new();
+        let mut bound = false;
+        for key in keys {
+            let value = row.remove(&key).unwrap_or(Value::Null);
+            match &key[prefix.len()..] {
+                "_id" => bound = !value.is_null(),
+                property => {
+                    properties.insert(property.to_string(), value);
+                }
+            }
+        }
+        let value = if bound { Value::Map(properties) } else { Value::Null };
+        row.set(name.to_string(), value);
+    }
+
+    /// Expands each row into one row per element of `list`, bound to `alias`.
+    ///
+    /// A NULL list produces no rows and a non-list value a single row.
+    fn unwind_rows(rows: Vec<Row>, list: &ValueExpr, alias: &str) -> Result<Vec<Row>> {
+        let mut unwound = Vec::with_capacity(rows.len());
+        for input in rows {
+            match executor::evaluate_value(list, &input)? {
+                Value::List(items) => {
+                    for item in items {
+                        let mut row = input.clone();
+                        row.set(alias.to_string(), item);
+                        unwound.push(row);
+                    }
+                }
+                Value::Null => {}
+                value => {
+                    let mut row = input;
+                    row.set(alias.to_string(), value);
+                    unwound.push(row);
+                }
+            }
+        }
+        Ok(unwound)
+    }
+
+    /// Copies `input` for an OPTIONAL MATCH without matches, binding the
+    /// patterns' new variables to NULL.
+    fn null_extend(input: &Row, patterns: &[PathPattern]) -> Row {
+        let mut row = input.clone();
+        for var in patterns.iter().flat_map(PathPattern::variables) {
+            let id_column = format!("{var}._id");
+            if !row.contains_key(&id_column) {
+                row.set(id_column, Value::Null);
+            }
+        }
+        row
+    }
+
+    /// Whether a projected column carries a whole node or relationship.
+    fn is_entity(column: &ProjectedColumn) -> bool {
+        matches!(
+            column.variable_type,
+            VariableType::Node | VariableType::Relationship
+        )
+    }
+
+    /// Applies a WITH or RETURN projection to `rows`.
+    ///
+    /// Aggregating projections group rows on the values of their
+    /// non-aggregate items, and DISTINCT keeps the first of each set of equal
+    /// projected rows. WHERE and ORDER BY are evaluated against the projected
+    /// values, which are layered over the incoming row unless the projection
+    /// aggregates.
+    fn project_rows(
+        rows: Vec<Row>,
+        projection: &Projection,
+        columns: &[ProjectedColumn],
+    ) -> Result<Vec<Row>> {
+        let aggregating = projection
+            .items
+            .iter()
+            .any(|item| matches!(item.item, ReturnItem::Aggregate(_)));
+
+        let mut projected: Vec<(Row, Option<Row>)> = if aggregating {
+            Self::aggregate_rows(rows, projection, columns)?
+                .into_iter()
+                .map(|row| (row, None))
+                .collect()
+        } else {
+            rows.into_iter()
+                .map(|row| Ok((Self::project_row(&row, projection, columns)?, Some(row))))
+                .collect::<Result<_>>()?
+        };
+        if projection.distinct {
+            let mut seen = std::collections::HashSet::new();
+            projected.retain(|(output, _)| {
+                let mut values: Vec<(&String, &Value)> = output.iter().collect();
+                values.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                seen.insert(
+                    values
+                        .into_iter()
+                        .map(|(column, value)| (column.clone(), value.clone()))
+                        .collect::<Vec<_>>(),
+                )
+            });
+        }
+
+        let order_by = projection.order_by.as_deref().unwrap_or_default();
+        let mut keyed: Vec<(Row, Vec<Value>)> = Vec::with_capacity(projected.len());
+        for (output, input) in projected {
+            if projection.filter.is_none() && order_by.is_empty() {
+                keyed.push((output, Vec::new()));
+                continue;
+            }
+
+            let mut view = input.unwrap_or_default();
+            for (column, value) in output.iter() {
+                view.set(column.clone(), value.clone());
+            }
+            if let Some(expr) = &projection.filter {
+                if !executor::evaluate_expression(expr, &view)? {
+                    continue;
+                }
+            }
+            let keys = order_by
+                .iter()
+                .map(|item| executor::evaluate_value(&item.expr, &view))
+                .collect::<Result<Vec<_>>>()?;
+            keyed.push((output, keys));
+        }
+        Self::sort_by_keys(&mut keyed, order_by);
+
+        let skip = Self::row_count(projection.skip.as_ref())?.unwrap_or(0);
+        let skip = usize::try_from(skip.max(0)).unwrap_or(0);
+        let limit = Self::row_count(projection.limit.as_ref())?
+            .map_or(usize::MAX, |n| usize::try_from(n.max(0)).unwrap_or(0));
+        Ok(keyed
+            .into_iter()
+            .skip(skip)
+            .take(limit)
+            .map(|(row, _)| row)
+            .collect())
+    }
+
+    /// Projects the non-aggregate items of `projection` from a single row.
+    ///
+    /// Nodes and relationships are carried over with all their `var.prop`
+    /// columns (and `_id`), renamed to the alias if one is given.
+    fn project_row(
+        row: &Row,
+        projection: &Projection,
+        columns: &[ProjectedColumn],
+    ) -> Result<Row> {
+        let mut output = Row::new();
+        for (item, column) in projection.items.iter().zip(columns) {
+            match &item.item {
+                ReturnItem::Variable(var) if Self::is_entity(column) => {
+                    let prefix = format!("{var}.");
+                    for (key, value) in row.iter() {
+                        if let Some(property) = key.strip_prefix(&prefix) {
+                            output.set(format!("{}.{property}", column.name), value.clone());
+                        }
+                    }
+                }
+                ReturnItem::Variable(var) => {
+                    let value = row.get(var).cloned().unwrap_or(Value::Null);
+                    output.set(column.name.clone(), value);
+                }
+                ReturnItem::Projection { .. } => {
+                    let value = row.get(&item.item.column_name()).cloned().unwrap_or(Value::Null);
+                    output.set(column.name.clone(), value);
+                }
+                // Cast to the bound column type, so that a CASE mixing INT64
+                // and FLOAT64 results yields FLOAT64 in every row
+                ReturnItem::Expression(expr) => {
+                    let value = executor::evaluate_value(expr, row)?;
+                    output.set(column.name.clone(), coerce_to_column_type(value, column.data_type));
+                }
+                ReturnItem::Aggregate(_) => {}
+            }
+        }
+        Ok(output)
+    }
+
+    /// Groups `rows` on the non-aggregate items of `projection` and computes
+    /// the aggregates of each group, producing one row per group.
+    ///
+    /// Without grouping items the whole input forms a single group, so
+    /// aggregating zero rows still yields one row (e.g. `COUNT(*) = 0`).
+    fn aggregate_rows(
+        rows: Vec<Row>,
+        projection: &Projection,
+        columns: &[ProjectedColumn],
+    ) -> Result<Vec<Row>> {
+        let mut group_by = Vec::new();
+        let mut aggregates = Vec::new();
+        for (item, column) in projection.items.iter().map(|item| &item.item).zip(columns) {
+            match item {
+                ReturnItem::Aggregate(agg) => aggregates.push((column.name.clone(), agg.clone())),
+                // Nodes and relationships are grouped by identity
+                ReturnItem::Variable(var) => group_by.push(ValueExpr::Variable(var.clone())),
+                ReturnItem::Projection { var, property } => group_by.push(ValueExpr::Property {
+                    var: var.clone(),
+                    property: property.clone(),
+                }),
+                ReturnItem::Expression(expr) => group_by.push(expr.clone()),
+            }
+        }
+
+        let mut aggregate =
+            AggregateOperator::new(Box::new(ValuesOperator::new(rows)), group_by, aggregates);
+        let mut output_rows = Vec::new();
+        while let Some(group) = aggregate.next()? {
+            let mut output = if group.is_empty() {
+                Row::new()
+            } else {
+                Self::project_row(&group, projection, columns)?
+            };
+            for (item, column) in projection.items.iter().zip(columns) {
+                if matches!(item.item, ReturnItem::Aggregate(_)) {
+                    output.set(column.name.clone(), group.get(&column.name).cloned().unwrap_or(Value::Null));
+                }
+            }
+            output_rows.push(output);
+        }
+        Ok(output_rows)
+    }
+
+    fn execute_create_rel_table(
+        &mut self,
+        table_name: String,
+        src_table: String,
+        dst_table: String,
+        columns: Vec<(String, String)>,
+    ) -> Result<QueryResult> {
+        // Validate source table exists
+        if !self.catalog.table_exists(&src_table) {
+            return Err(RuzuError::SchemaError(format!(
+                "Source table '{src_table}' does not exist"
+            )));
+        }
+
+        // Validate destination table exists
+        if !self.catalog.table_exists(&dst_table) {
+            return Err(RuzuError::SchemaError(format!(
+                "Destination table '{dst_table}' does not exist"
+            )));
+        }
+
+        // Convert column definitions
+        let column_defs: Vec<ColumnDef> = columns
+            .into_iter()
+            .map(|(name, type_str)| {
+                let data_type = DataType::from_name(&type_str).ok_or_else(|| {
+                    RuzuError::SchemaError(format!("Unknown data type: {type_str}"))
+                })?;
+                ColumnDef::new(name, data_type)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Create relationship table schema
+        let schema = RelTableSchema::new(
+            table_name.clone(),
+            src_table,
+            dst_table,
+            column_defs,
+            Direction::Both,
+        )?;
+
+        // Register in catalog
+        self.catalog.create_rel_table(schema.clone())?;
+
+        // Create storage table
+        let rel_table = RelTable::new(Arc::new(schema));
+        self.rel_tables.insert(table_name, rel_table);
+
+        // Mark database as dirty
+        self.dirty = true;
+
+        Ok(QueryResult::empty())
+    }
+
+    fn execute_match_create(
+        &mut self,
+        src_node: &NodeFilter,
+        dst_node: &NodeFilter,
+        rel_type: &str,
+        rel_props: Vec<(String, Literal)>,
+        _src_var: String,
+        _dst_var: String,
+    ) -> Result<QueryResult> {
+        // Validate relationship table exists
+        let rel_schema = self.catalog.get_rel_table(rel_type).ok_or_else(|| {
+            RuzuError::SchemaError(format!("Relationship table '{rel_type}' does not exist"))
+        })?;
+        let rel_table_id = rel_schema.table_id;
+
+        // Find source nodes
+        let src_table = self.tables.get(&src_node.label).ok_or_else(|| {
+            RuzuError::SchemaError(format!("Table '{}' does not exist", src_node.label))
+        })?;
+
+        let src_offsets = Self::candidate_offsets(src_table, src_node, &Row::new())?;
+
+        // Find destination nodes
+        let dst_table = self.tables.get(&dst_node.label).ok_or_else(|| {
+            RuzuError::SchemaError(format!("Table '{}' does not exist", dst_node.label))
+        })?;
+
+        let dst_offsets = Self::candidate_offsets(dst_table, dst_node, &Row::new())?;
+
+        // Convert relationship properties to schema column order; omitted ones are
+        // NULL, as in MERGE
+        let mut props = vec![Value::Null; rel_schema.columns.len()];
+        for (key, literal) in rel_props {
+            let col_idx = rel_schema
+                .get_column_index(&key)
+                .ok_or_else(|| BindError::UndefinedColumn(rel_type.to_string(), key.clone()))?;
+            props[col_idx] =
+                coerce_to_column_type(literal_into_value(literal), rel_schema.columns[col_idx].data_type);
+        }
+
+        // One relationship per matched pair; none if either side matched nothing
+        for &src_offset in &src_offsets {
+            for &dst_offset in &dst_offsets {
+                self.insert_rel(
+                    rel_type,
+                    rel_table_id,
+                    src_offset as u64,
+                    dst_offset as u64,
+                    props.clone(),
+                )?;
+            }
+        }
+
+        Ok(QueryResult::empty())
+    }
+
+    /// Inserts a single relationship and WAL-logs it, returning its ID.
+    fn insert_rel(
+        &mut self,
+        rel_type: &str,
+        rel_table_id: u32,
+        src_offset: u64,
+        dst_offset: u64,
+        props: Vec<Value>,
+    ) -> Result<u64> {
+        if !self.rel_tables.contains_key(rel_type) {
+            return Err(RuzuError::ExecutionError(format!(
+                "Relationship table '{rel_type}' not found in storage"
+            )));
+        }
+
+        // Write WAL record before touching memory
+        self.log_transaction(vec![(
+            WalRecordType::RelInsertion,
+            WalPayload::RelInsertion {
+                table_id: rel_table_id,
+                src: src_offset,
+                dst: dst_offset,
+                props: props.clone(),
+            },
+        )])?;
+
+        // Insert the relationship
+        let rel_table = self.rel_tables.get_mut(rel_type).ok_or_else(|| {
+            RuzuError::ExecutionError(format!(
+                "Relationship table '{rel_type}' not found in storage"
+            ))
+        })?;
+        let rel_id = rel_table.insert(src_offset, dst_offset, props)?;
+
+        self.dirty = true;
+
+        Ok(rel_id)
+    }
+
+    /// Appends `records` to the WAL as a single committed transaction.
+    ///
+    /// The WAL is synced or flushed according to `DatabaseConfig::wal_sync`.
+    /// Does nothing for in-memory databases.
+    fn log_transaction(&mut self, records: Vec<(WalRecordType, WalPayload)>) -> Result<()> {
+        if let Some(ref mut wal_writer) = self.wal_writer {
+            let tx_id = self.next_tx_id.fetch_add(1, Ordering::Relaxed);
+
+            let begin_lsn = wal_writer.next_lsn();
+            wal_writer.append(&WalRecord::begin_transaction(tx_id, begin_lsn))?;
+
+            for (record_type, payload) in records {
+                let lsn = wal_writer.next_lsn();
+                wal_writer.append(&WalRecord::new(record_type, tx_id, lsn, payload))?;
+            }
+
+            let commit_lsn = wal_writer.next_lsn();
+            wal_writer.append(&WalRecord::commit(tx_id, commit_lsn))?;
+
+            if self.config.wal_sync {
+                wal_writer.sync()?;
+            } else {
+                wal_writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the offsets of the nodes in `table` that satisfy the inline
+    /// property filters of `node`, using the primary key index when possible.
+    fn candidate_offsets(table: &NodeTable, node: &NodeFilter, row: &Row) -> Result<Vec<usize>> {
+        Ok(Self::matching_offsets(table, &Self::filter_props(&node.property_filters, row)?))
+    }
+
+    /// Evaluates inline property filters against `row`.
+    fn filter_props(filters: &[(String, ValueExpr)], row: &Row) -> Result<Vec<(String, Value)>> {
+        filters
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), executor::evaluate_value(value, row)?)))
+            .collect()
+    }
+
+    /// Returns the offsets of the live nodes in `table` whose properties equal
+    /// all of `props`, using the primary key index when they cover every
+    /// primary key column.
+    fn matching_offsets(table: &NodeTable, props: &[(String, Value)]) -> Vec<usize> {
+        let matches = |offset: usize| Self::has_properties(table, offset, props);
+
+        let schema = table.schema();
+        let key: Option<Vec<Value>> = schema
+            .primary_key
+            .iter()
+            .map(|column| {
+                let (_, value) = props.iter().find(|(key, _)| key == column)?;
+                let data_type = schema.get_column(column)?.data_type;
+                Some(coerce_to_column_type(value.clone(), data_type))
+            })
+            .collect();
+        if let Some(key) = key {
+            return table
+                .find_by_key(&key)
+                .into_iter()
+                .filter(|&offset| matches(offset))
+                .collect();
+        }
+
+        table.live_offsets().filter(|&offset| matches(offset)).collect()
+    }
+
+    /// Whether the node at `offset` equals all of `props`; NULL equals nothing.
+    fn has_properties(table: &NodeTable, offset: usize, props: &[(String, Value)]) -> bool {
+        props.iter().all(|(key, expected)| {
+            table.get(offset, key).is_some_and(|actual| {
+                let (actual, expected) = promote_for_comparison(actual, expected.clone());
+                actual.compare(&expected) == Some(std::cmp::Ordering::Equal)
+            })
+        })
+    }
+
+    /// Whether the relationship `rel_id` has all of `props`; NULL equals nothing.
+    fn rel_has_properties(rel_table: &RelTable, rel_id: u64, props: &[(String, Value)]) -> bool {
+        if props.is_empty() {
+            return true;
+        }
+        let Some(stored) = rel_table.get_properties(rel_id) else {
+            return false;
+        };
+        props.iter().all(|(key, expected)| {
+            rel_table
+                .schema()
+                .get_column_index(key)
+                .and_then(|idx| stored.get(idx))
+                .is_some_and(|actual| {
+                    let (actual, expected) =
+                        promote_for_comparison(actual.clone(), expected.clone());
+                    actual.compare(&expected) == Some(std::cmp::Ordering::Equal)
+                })
+        })
+    }
+
+    /// Returns true if the `var.prop` columns of `row` equal all of `props`.
+    fn row_has_properties(row: &Row, var: &str, props: &[(String, Value)]) -> bool {
+        props.iter().all(|(key, expected)| {
+            row.get(&format!("{var}.{key}")).is_some_and(|actual| {
+                let (actual, expected) = promote_for_comparison(actual.clone(), expected.clone());
+                actual.compare(&expected) == Some(std::cmp::Ordering::Equal)
+            })
+        })
+    }
+
+    /// Adds every property of a node to `row` as `var.prop`, plus its offset as `var._id`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn bind_node(row: &mut Row, var: &str, table: &NodeTable, offset: usize) {
+        for (col_idx, col) in table.schema().columns.iter().enumerate() {
+            if let Some(value) = table.get_column(col_idx).and_then(|c| c.get(offset)) {
+                row.set(format!("{var}.{}", col.name), value.clone());
+            }
+        }
+        row.set(format!("{var}._id"), Value::Int64(offset as i64));
+    }
+
+    /// Adds every property of a relationship to `row` as `var.prop`, plus its ID as `var._id`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn bind_rel(row: &mut Row, var: &str, rel_table: &RelTable, rel_id: u64) {
+        if let Some(props) = rel_table.get_properties(rel_id) {
+            for (col, value) in rel_table.schema().columns.iter().zip(props) {
+                row.set(format!("{var}.{}", col.name), value.clone());
+            }
+        }
+        row.set(format!("{var}._id"), Value::Int64(rel_id as i64));
+    }
+
+    /// Reads the `var._id` column written by `bind_node` / `bind_rel`.
+    fn bound_id(row: &Row, var: &str) -> Result<u64> {
+        match row.get(&format!("{var}._id")) {
+            Some(Value::Int64(id)) => u64::try_from(*id).map_err(|_| {
+                RuzuError::ExecutionError(format!("Negative ID {id} for variable '{var}'"))
+            }),
+            _ => Err(RuzuError::ExecutionError(format!(
+                "Variable '{var}' is not bound to a node or relationship"
+            ))),
+        }
+    }
+
+    /// Extends `rows` with every joint match of the comma-separated
+    /// `patterns` of a MATCH clause that satisfies `filter`.
+    ///
+    /// A pattern that shares a variable with the rows matched so far, or
+    /// whose inline filters use one, is matched once per row, as is any
+    /// pattern when there is only one row. Any other pattern is matched once and hash joined to the rows on the
+    /// `left.prop = right.prop` conjuncts of the filter that relate the two,
+    /// or paired with every row when there are none.
+    fn match_patterns(
+        &self,
+        mut rows: Vec<Row>,
+        patterns: &[PathPattern],
+        filter: Option<&parser::ast::Expression>,
+    ) -> Result<Vec<Row>> {
+        let mut pending_filter = filter;
+        for (i, pattern) in patterns.iter().enumerate() {
+            let Some(first) = rows.first() else {
+                return Ok(rows);
+            };
+            if rows.len() == 1 || Self::depends_on_row(pattern, first) {
+                // Once the last pattern is matched every variable of the
+                // filter is bound, so it can narrow and filter that match
+                let filter = if i + 1 == patterns.len() {
+                    pending_filter.take()
+                } else {
+                    None
+                };
+                let mut matched = Vec::new();
+                for input in &rows {
+                    matched.extend(self.match_pattern(pattern, filter, input)?);
+                }
+                rows = matched;
+            } else {
+                let (probe_keys, build_keys) =
+                    filter.map_or_else(Default::default, |f| Self::join_keys(f, first, pattern));
+                let build = self.match_pattern(pattern, None, &Row::new())?;
+                let mut join = HashJoinOperator::new(
+                    Box::new(ValuesOperator::new(rows)),
+                    probe_keys,
+                    build,
+                    &build_keys,
+                )?;
+                rows = Vec::new();
+                while let Some(row) = join.next()? {
+                    rows.push(row);
+                }
+            }
+        }
+
+        let Some(filter) = pending_filter else {
+            return Ok(rows);
+        };
+        let (mut operator, marks) =
+            self.filter_operator(Box::new(ValuesOperator::new(rows)), filter);
+        let mut filtered = Vec::new();
+        while let Some(mut row) = operator.next()? {
+            for mark in &marks {
+                row.remove(mark);
+            }
+            filtered.push(row);
+        }
+        Ok(filtered)
+    }
+
+    /// Applies a MATCH WHERE filter to the rows of `input`, returning the
+    /// filtering operator and the internal mark columns it adds to them.
+    ///
+    /// Top-level conjuncts without subqueries are applied first. Each EXISTS
+    /// subquery or pattern predicate then runs as a [`MarkJoinOperator`],
+    /// whose mark stands in for it in the rest of the filter: `EXISTS`
+    /// becomes a semi-join and `NOT EXISTS` an anti-join.
+    fn filter_operator<'a>(
+        &'a self,
+        input: Box<dyn PhysicalOperator + 'a>,
+        filter: &parser::ast::Expression,
+    ) -> (Box<dyn PhysicalOperator + 'a>, Vec<String>) {
+        use parser::ast::Expression;
+
+        let mut conjuncts = Vec::new();
+        let mut pending = vec![filter.clone()];
+        while let Some(expr) = pending.pop() {
+            match expr {
+                Expression::And(left, right) => {
+                    pending.push(*right);
+                    pending.push(*left);
+                }
+                expr => conjuncts.push(expr),
+            }
+        }
+        let (correlated, plain): (Vec<_>, Vec<_>) =
+            conjuncts.into_iter().partition(Expression::has_subquery);
+        let and = |exprs: Vec<Expression>| {
+            exprs
+                .into_iter()
+                .reduce(|left, right| Expression::And(Box::new(left), Box::new(right)))
+        };
+
+        let mut operator = input;
+        if let Some(expr) = and(plain) {
+            operator = Box::new(FilterOperator::new(operator, expr));
+        }
+        let Some(mut rest) = and(correlated) else {
+            return (operator, Vec::new());
+        };
+        let mut subqueries = Vec::new();
+        Self::take_subqueries(&mut rest, &mut subqueries);
+        let mut marks = Vec::with_capacity(subqueries.len());
+        for (i, (pattern, filter)) in subqueries.into_iter().enumerate() {
+            let mark = Self::subquery_mark(i);
+            let exists = move |row: &Row| self.pattern_exists(&pattern, filter.as_deref(), row);
+            operator = Box::new(MarkJoinOperator::new(operator, mark.clone(), Box::new(exists)));
+            marks.push(mark);
+        }
+        (Box::new(FilterOperator::new(operator, rest)), marks)
+    }
+
+    /// Internal column (not a valid identifier) holding the result of the
+    /// `i`th subquery of a filter.
+    fn subquery_mark(i: usize) -> String {
+        format!("#exists{i}")
+    }
+
+    /// Moves the subqueries of `expr` into `found`, replacing each with a
+    /// reference to its mark column.
+    fn take_subqueries(
+        expr: &mut parser::ast::Expression,
+        found: &mut Vec<(PathPattern, Option<Box<parser::ast::Expression>>)>,
+    ) {
+        use parser::ast::Expression;
+
+        match expr {
+            Expression::And(left, right) | Expression::Or(left, right) => {
+                Self::take_subqueries(left, found);
+                Self::take_subqueries(right, found);
+            }
+            Expression::Not(operand) => Self::take_subqueries(operand, found),
+            Expression::Exists { .. } => {
+                let mark = Expression::Value(ValueExpr::Variable(Self::subquery_mark(found.len())));
+                if let Expression::Exists { pattern, filter } = std::mem::replace(expr, mark) {
+                    found.push((*pattern, filter));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `pattern` has a match extending `row` that satisfies `filter`.
+    ///
+    /// The pattern is expanded from whichever end `row` binds, preferring
+    /// its start, and the expansion stops at the first match.
+    fn pattern_exists(
+        &self,
+        pattern: &PathPattern,
+        filter: Option<&parser::ast::Expression>,
+        row: &Row,
+    ) -> Result<bool> {
+        let bound = |node: &NodeFilter| row.contains_key(&format!("{}._id", node.var));
+        let last = pattern.hops.last().map_or(&pattern.start, |hop| &hop.node);
+        let matches = if !bound(&pattern.start) && bound(last) {
+            self.match_pattern_up_to(&pattern.reversed(), filter, row, 1)?
+        } else {
+            self.match_pattern_up_to(pattern, filter, row, 1)?
+        };
+        Ok(!matches.is_empty())
+    }
+
+    /// Whether matching `pattern` depends on the variables bound in `row`:
+    /// it binds one of them again, or an inline filter takes its value from
+    /// a variable rather than a literal.
+    fn depends_on_row(pattern: &PathPattern, row: &Row) -> bool {
+        let nodes = std::iter::once(&pattern.start).chain(pattern.hops.iter().map(|hop| &hop.node));
+        let mut filters = nodes
+            .flat_map(|node| &node.property_filters)
+            .chain(pattern.hops.iter().flat_map(|hop| &hop.rel_filters));
+        pattern.variables().any(|var| row.contains_key(&format!("{var}._id")))
+            || filters.any(|(_, value)| !matches!(value, ValueExpr::Literal(_)))
+    }
+
+    /// Runs an OPTIONAL MATCH: as [`Self::match_patterns`], but a row
+    /// without any match is kept with the new variables bound to NULL.
+    fn optional_match_patterns(
+        &self,
+        rows: &[Row],
+        patterns: &[PathPattern],
+        filter: Option<&parser::ast::Expression>,
+    ) -> Result<Vec<Row>> {
+        // Matches are traced back to their input row through an internal
+        // column (not a valid identifier)
+        const INPUT_COLUMN: &str = "#input";
+        let tagged = rows
+            .iter()
+            .zip(0_i64..)
+            .map(|(row, index)| {
+                let mut row = row.clone();
+                row.set(INPUT_COLUMN.to_string(), Value::Int64(index));
+                row
+            })
+            .collect();
+        let mut found = vec![Vec::new(); rows.len()];
+        for mut row in self.match_patterns(tagged, patterns, filter)? {
+            let index = row.remove(INPUT_COLUMN).and_then(|v| v.as_int64());
+            if let Some(matches) = index.and_then(|i| found.get_mut(usize::try_from(i).ok()?)) {
+                matches.push(row);
+            }
+        }
+
+        let mut matched = Vec::new();
+        for (input, found) in rows.iter().zip(found) {
+            if found.is_empty() {
+                matched.push(Self::null_extend(input, patterns));
+            } else {
+                matched.extend(found);
+            }
+        }
+        Ok(matched)
+    }
+
+    /// Splits the `left.prop = right.prop` conjuncts of `filter` that compare
+    /// a property of a variable bound in `left` with one of a variable of
+    /// `pattern` into the keys of each side.
+    fn join_keys(
+        filter: &parser::ast::Expression,
+        left: &Row,
+        pattern: &PathPattern,
+    ) -> (Vec<ValueExpr>, Vec<ValueExpr>) {
+        // Whether an operand is a property of a pattern variable (true) or
+        // of a variable bound in `left` (false)
+        let side = |operand: &ValueExpr| match operand {
+            ValueExpr::Property { var, .. } if pattern.variables().any(|v| v == var) => Some(true),
+            ValueExpr::Property { var, .. } if left.contains_key(&format!("{var}._id")) => {
+                Some(false)
+            }
+            _ => None,
+        };
+
+        let mut keys = (Vec::new(), Vec::new());
+        let mut conjuncts = vec![filter];
+        while let Some(conjunct) = conjuncts.pop() {
+            match conjunct {
+                parser::ast::Expression::And(left, right) => {
+                    conjuncts.push(right);
+                    conjuncts.push(left);
+                }
+                parser::ast::Expression::Comparison {
+                    left,
+                    op: parser::ast::ComparisonOp::Eq,
+                    right,
+                } => match (side(left), side(right)) {
+                    (Some(false), Some(true)) => {
+                        keys.0.push(left.clone());
+                        keys.1.push(right.clone());
+                    }
+                    (Some(true), Some(false)) => {
+                        keys.0.push(right.clone());
+                        keys.1.push(left.clone());
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        keys
+    }
+
+    /// Collects one row per match of an updating MATCH pattern.
+    ///
+    /// Each row holds all properties of the bound variables together with their
+    /// `_id` columns, so that SET and DELETE can locate the storage slots to modify.
+    ///
+    /// A filter holding subqueries is bound first, which resolves the labels
+    /// of the variables their patterns share with `pattern`.
+    fn collect_pattern_rows(
+        &self,
+        pattern: &MatchPattern,
+        filter: Option<&parser::ast::Expression>,
+    ) -> Result<Vec<Row>> {
+        let mut pattern = PathPattern::from(pattern.clone());
+        match filter {
+            Some(filter) if filter.has_subquery() => {
+                let mut filter = filter.clone();
+                let mut binder = Binder::new(&self.catalog);
+                binder.bind_pattern(&mut pattern)?;
+                binder.bind_where(&mut filter)?;
+                self.match_pattern(&pattern, Some(&filter), &Row::new())
+            }
+            _ => self.match_pattern(&pattern, filter, &Row::new()),
+        }
+    }
+
+    /// Extends `input` with every match of `pattern`.
+    ///
+    /// The start node is looked up directly; each hop then runs as an
+    /// `ExtendOperator` over the rows of the previous one, followed by the
+    /// WHERE filter and the inline property filters of the hop nodes.
+    ///
+    /// Variables that already have an `_id` column in `input` were bound by an
+    /// earlier clause and must match that node; a NULL `_id` (left by an
+    /// OPTIONAL MATCH that found nothing) matches no node at all.
+    fn match_pattern(
+        &self,
+        pattern: &PathPattern,
+        filter: Option<&parser::ast::Expression>,
+        input: &Row,
+    ) -> Result<Vec<Row>> {
+        self.match_pattern_up_to(pattern, filter, input, usize::MAX)
+    }
+
+    /// As [`Self::match_pattern`], but stops after `limit` matches.
+    fn match_pattern_up_to(
+        &self,
+        pattern: &PathPattern,
+        filter: Option<&parser::ast::Expression>,
+        input: &Row,
+        limit: usize,
+    ) -> Result<Vec<Row>> {
+        let start = &pattern.start;
+        let start_table = self.node_table(&start.label)?;
+        let mut start_rows = Vec::new();
+        for offset in Self::node_offsets(start_table, start, filter, input)? {
+            let mut row = input.clone();
+            Self::bind_node(&mut row, &start.var, start_table, offset);
+            start_rows.push(row);
+        }
+
+        // A relationship is matched at most once per path. Anonymous
+        // relationships whose type recurs in the path, or that have inline
+        // property filters, get an internal variable (not a valid identifier)
+        // so that their IDs and properties can be checked
+        let rel_vars: Vec<Option<String>> = pattern
+            .hops
+            .iter()
+            .enumerate()
+            .map(|(i, hop)| {
+                hop.rel_var.clone().or_else(|| {
+                    let recurs =
+                        pattern.hops.iter().filter(|h| h.rel_type == hop.rel_type).count() > 1;
+                    (recurs || !hop.rel_filters.is_empty()).then(|| format!("#hop{i}"))
+                })
+            })
+            .collect();
+
+        let mut operator: Box<dyn PhysicalOperator + '_> =
+            Box::new(ValuesOperator::new(start_rows));
+        let mut src_node = start;
+        for (i, hop) in pattern.hops.iter().enumerate() {
+            let rel_table = self.rel_tables.get(&hop.rel_type).ok_or_else(|| {
+                RuzuError::SchemaError(format!(
+                    "Relationship table '{}' does not exist",
+                    hop.rel_type
+                ))
+            })?;
+            let direction = Binder::resolve_direction(
+                rel_table.schema(),
+                &src_node.label,
+                &hop.node.label,
+                hop.direction,
+            )?;
+            let earlier_rels = pattern.hops[..i]
+                .iter()
+                .zip(&rel_vars)
+                .filter(|(earlier, _)| earlier.rel_type == hop.rel_type)
+                .filter_map(|(_, var)| var.as_ref().map(|var| format!("{var}._id")))
+                .collect();
+            let extend = ExtendOperator::new(
+                operator,
+                rel_table,
+                Arc::new(rel_table.schema().clone()),
+                src_node.var.clone(),
+                hop.node.var.clone(),
+                rel_vars[i].clone(),
+                traversal_direction(direction),
+            );
+            operator = Box::new(
+                extend
+                    .with_dst_table(self.node_table(&hop.node.label)?)
+                    .with_unique_rels(earlier_rels),
+            );
+            src_node = &hop.node;
+        }
+        let mut marks = Vec::new();
+        if let Some(expr) = filter {
+            (operator, marks) = self.filter_operator(operator, expr);
+        }
+
+        let mut rows = Vec::new();
+        while rows.len() < limit {
+            let Some(mut row) = operator.next()? else {
+                break;
+            };
+            if !self.hop_filters_match(pattern, &rel_vars, &row)? {
+                continue;
+            }
+            for (hop, var) in pattern.hops.iter().zip(&rel_vars) {
+                if let (None, Some(var)) = (&hop.rel_var, var) {
+                    row.remove(&format!("{var}._id"));
+                    for col in &self.rel_table_schema(&hop.rel_type)?.columns {
+                        row.remove(&format!("{var}.{}", col.name));
+                    }
+                }
+            }
+            for mark in &marks {
+                row.remove(mark);
+            }
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    /// Whether the relationships and nodes reached by the hops of `pattern`
+    /// in `row` have the properties of their inline filters.
+    fn hop_filters_match(
+        &self,
+        pattern: &PathPattern,
+        rel_vars: &[Option<String>],
+        row: &Row,
+    ) -> Result<bool> {
+        for (hop, var) in pattern.hops.iter().zip(rel_vars) {
+            if !hop.rel_filters.is_empty() {
+                let props = Self::filter_props(&hop.rel_filters, row)?;
+                let matches = var
+                    .as_ref()
+                    .is_some_and(|var| Self::row_has_properties(row, var, &props));
+                if !matches {
+                    return Ok(false);
+                }
+            }
+            let node = &hop.node;
+            if node.property_filters.is_empty() {
+                continue;
+            }
+            let offset = usize::try_from(Self::bound_id(row, &node.var)?).map_err(|_| {
+                RuzuError::ExecutionError(format!("Node ID of '{}' out of range", node.var))
+            })?;
+            let props = Self::filter_props(&node.property_filters, row)?;
+            if !Self::has_properties(self.node_table(&node.label)?, offset, &props) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Looks up the schema of the relationship table `rel_type`.
+    fn rel_table_schema(&self, rel_type: &str) -> Result<&RelTableSchema> {
+        self.rel_tables.get(rel_type).map(RelTable::schema).ok_or_else(|| {
+            RuzuError::SchemaError(format!("Relationship table '{rel_type}' does not exist"))
+        })
+    }
+
+    /// Looks up the node table for `label`.
+    fn node_table(&self, label: &str) -> Result<&NodeTable> {
+        self.tables
+            .get(label)
+            .map(AsRef::as_ref)
+            .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))
+    }
+
+    /// Returns the offsets a pattern node can bind to: the node already bound
+    /// to its variable in `input`, or otherwise the candidates matching its
+    /// inline property filter, narrowed by primary key lookups when the WHERE
+    /// `filter` requires `node.pk IN [...]`.
+    fn node_offsets(
+        table: &NodeTable,
+        node: &NodeFilter,
+        filter: Option<&parser::ast::Expression>,
+        input: &Row,
+    ) -> Result<Vec<usize>> {
+        match input.get(&format!("{}._id", node.var)) {
+            Some(Value::Int64(id)) => {
+                let props = Self::filter_props(&node.property_filters, input)?;
+                Ok(usize::try_from(*id)
+                    .ok()
+                    .filter(|&offset| Self::has_properties(table, offset, &props))
+                    .into_iter()
+                    .collect())
+            }
+            Some(_) => Ok(Vec::new()),
+            None => match filter.and_then(|f| Self::pk_in_list_offsets(table, &node.var, f)) {
+                Some(offsets) => {
+                    let props = Self::filter_props(&node.property_filters, input)?;
+                    Ok(offsets
+                        .into_iter()
+                        .filter(|&offset| Self::has_properties(table, offset, &props))
+                        .collect())
+                }
+                None => Self::candidate_offsets(table, node, input),
+            },
+        }
+    }
+
+    /// Looks up the nodes of `table` that `filter` can hold for when one of
+    /// its top-level conjuncts is `var.pk IN [...]` on a single-column primary
+    /// key. The filter itself must still be applied to the result.
+    ///
+    /// Returns `None` when there is no such conjunct, or when a list element
+    /// has a different type than the key and could still compare equal.
+    fn pk_in_list_offsets(
+        table: &NodeTable,
+        var: &str,
+        filter: &parser::ast::Expression,
+    ) -> Option<Vec<usize>> {
+        let [pk] = table.schema().primary_key.as_slice() else {
+            return None;
+        };
+        match filter {
+            parser::ast::Expression::And(left, right) => {
+                Self::pk_in_list_offsets(table, var, left)
+                    .or_else(|| Self::pk_in_list_offsets(table, var, right))
+            }
+            parser::ast::Expression::In {
+                operand: ValueExpr::Property { var: v, property },
+                list: ValueExpr::Literal(Literal::List(items)),
+                negated: false,
+            } if v == var && property == pk => {
+                let pk_type = table.schema().get_column(pk)?.data_type;
+                let keys: Vec<Value> = items
+                    .iter()
+                    .map(|item| coerce_to_column_type(literal_to_value(item), pk_type))
+                    .collect();
+                keys.iter()
+                    .all(|key| key.data_type() == Some(pk_type))
+                    .then(|| table.find_by_pks(pk, &keys))
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolves a SET assignment to the table and column it writes to.
+    fn resolve_set_target(&self, pattern: &MatchPattern, item: &SetItem) -> Result<SetTarget> {
+        Self::check_set_value(&item.value)?;
+
+        let node_label = match pattern {
+            MatchPattern::Node(node) => (node.var == item.var).then_some(&node.label),
+            MatchPattern::Rel {
+                src_node, dst_node, ..
+            } => [src_node, dst_node]
+                .into_iter()
+                .find(|node| node.var == item.var)
+                .map(|node| &node.label),
+        };
+
+        if let Some(label) = node_label {
+            let schema = self
+                .catalog
+                .get_table(label)
+                .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
+            let col_idx = schema.get_column_index(&item.property).ok_or_else(|| {
+                BindError::UndefinedColumn(label.clone(), item.property.clone())
+            })?;
+            return Ok(SetTarget {
+                label: label.clone(),
+                table_id: schema.table_id,
+                col_idx,
+                data_type: schema.columns[col_idx].data_type,
+                is_rel: false,
+            });
+        }
+
+        if let MatchPattern::Rel {
+            rel_var: Some(rel_var),
+            rel_type,
+            ..
+        } = pattern
+        {
+            if *rel_var == item.var {
+                let schema = self.catalog.get_rel_table(rel_type).ok_or_else(|| {
+                    RuzuError::SchemaError(format!(
+                        "Relationship table '{rel_type}' does not exist"
+                    ))
+                })?;
+                let col_idx = schema.get_column_index(&item.property).ok_or_else(|| {
+                    BindError::UndefinedColumn(rel_type.clone(), item.property.clone())
+                })?;
+                return Ok(SetTarget {
+                    label: rel_type.clone(),
+                    table_id: schema.table_id,
+                    col_idx,
+                    data_type: schema.columns[col_idx].data_type,
+                    is_rel: true,
+                });
+            }
+        }
+
+        Err(BindError::UndefinedVariable(item.var.clone()).into())
+    }
+
+    /// Rejects bare variables in a SET value: the updated pattern binds only
+    /// nodes and relationships, which are not values.
+    fn check_set_value(expr: &ValueExpr) -> Result<()> {
+        match expr {
+            ValueExpr::Variable(name) => Err(BindError::UndefinedVariable(name.clone()).into()),
+            ValueExpr::Arithmetic { left, right, .. } => {
+                Self::check_set_value(left)?;
+                Self::check_set_value(right)
+            }
+            ValueExpr::Negate(operand) => Self::check_set_value(operand),
+            ValueExpr::Case(case) => case.operands().into_iter().try_for_each(Self::check_set_value),
+            ValueExpr::Function { args, .. } => args.iter().try_for_each(Self::check_set_value),
+            ValueExpr::Literal(_) | ValueExpr::Property { .. } => Ok(()),
+        }
+    }
+
+    /// Writes a single property value to a node or relationship table,
+    /// returning the value it replaced.
+    fn write_property(&mut self, target: &SetTarget, id: u64, value: Value) -> Result<Value> {
+        if target.is_rel {
+            let rel_table = self.rel_tables.get_mut(&target.label).ok_or_else(|| {
+                RuzuError::ExecutionError(format!(
+                    "Relationship table '{}' not found in storage",
+                    target.label
+                ))
+            })?;
+            let old = rel_table
+                .get_properties(id)
+                .and_then(|props| props.get(target.col_idx).cloned())
+                .unwrap_or(Value::Null);
+            rel_table.update_property(id, target.col_idx, value)?;
+            Ok(old)
+        } else {
+            let table = self.tables.get_mut(&target.label).ok_or_else(|| {
+                RuzuError::SchemaError(format!("Table '{}' does not exist", target.label))
+            })?;
+            let table = Arc::get_mut(table).ok_or_else(|| {
+                RuzuError::ExecutionError("Cannot modify table: multiple references exist".into())
+            })?;
+            let offset = id as usize;
+            let old = table
+                .get_column(target.col_idx)
+                .and_then(|c| c.get(offset).cloned())
+                .unwrap_or(Value::Null);
+            table.update(offset, target.col_idx, value)?;
+            Ok(old)
+        }
+    }
+
+    fn execute_match_set(
+        &mut self,
+        pattern: &MatchPattern,
+        filter: Option<&parser::ast::Expression>,
+        items: &[SetItem],
+    ) -> Result<QueryResult> {
+        let rows = self.collect_pattern_rows(pattern, filter)?;
+        self.apply_set_items(pattern, &rows, items)?;
+
+        Ok(QueryResult::empty())
+    }
+
+    /// Applies SET assignments to the variables bound in each of `rows`.
+    ///
+    /// Assignments are evaluated in order against each row, so later items see
+    /// the values written by earlier ones. The whole update is all-or-nothing
+    /// and is logged as a single WAL transaction.
+    ///
+    /// The updates are checked (column types, NULL and duplicate primary
+    /// keys, also between the updates themselves) by writing and then undoing
+    /// them, so that the tables only change once the WAL record is written.
+    fn apply_set_items(
+        &mut self,
+        pattern: &MatchPattern,
+        rows: &[Row],
+        items: &[SetItem],
+    ) -> Result<()> {
+        let (targets, updates) = self.set_item_updates(pattern, rows, items)?;
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        self.check_updates(&targets, &updates)?;
+        let wal_records = updates
+            .iter()
+            .map(|(item_idx, id, value)| targets[*item_idx].wal_record(*id, value.clone()))
+            .collect();
+        self.log_transaction(wal_records)?;
+        self.write_properties(&targets, updates)?;
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    /// Resolves the targets of SET assignments and expands them into one
+    /// `(target index, id, value)` update per (row, item), without writing
+    /// anything.
+    #[allow(clippy::type_complexity)]
+    fn set_item_updates(
+        &self,
+        pattern: &MatchPattern,
+        rows: &[Row],
+        items: &[SetItem],
+    ) -> Result<(Vec<SetTarget>, Vec<(usize, u64, Value)>)> {
+        let targets = items
+            .iter()
+            .map(|item| self.resolve_set_target(pattern, item))
+            .collect::<Result<Vec<_>>>()?;
+
+        // A node reached through several matches is only written once per
+        // assignment
+        let mut seen = std::collections::HashSet::new();
+        let mut updates: Vec<(usize, u64, Value)> = Vec::new();
+        for row in rows {
+            let mut row = row.clone();
+            for (item_idx, (item, target)) in items.iter().zip(&targets).enumerate() {
+                let id = Self::bound_id(&row, &item.var)?;
+                let value = coerce_to_column_type(
+                    executor::evaluate_value(&item.value, &row)?,
+                    target.data_type,
+                );
+                row.set(format!("{}.{}", item.var, item.property), value.clone());
+                if seen.insert((item_idx, id)) {
+                    updates.push((item_idx, id, value));
+                }
+            }
+        }
+
+        Ok((targets, updates))
+    }
+
+    /// Checks that `updates` can be written by writing and then undoing them.
+    fn check_updates(
+        &mut self,
+        targets: &[SetTarget],
+        updates: &[(usize, u64, Value)],
+    ) -> Result<()> {
+        let old_values = self.write_properties(targets, updates.to_vec())?;
+        self.write_properties(targets, old_values.into_iter().rev().collect())?;
+        Ok(())
+    }
+
+    /// Writes `(target index, id, value)` updates in order, returning the
+    /// values they replaced in the same form.
+    ///
+    /// If an update fails (e.g. a duplicate primary key), the ones already
+    /// written are undone before the error is returned.
+    fn write_properties(
+        &mut self,
+        targets: &[SetTarget],
+        updates: Vec<(usize, u64, Value)>,
+    ) -> Result<Vec<(usize, u64, Value)>> {
+        let mut applied: Vec<(usize, u64, Value)> = Vec::with_capacity(updates.len());
+        for (item_idx, id, value) in updates {
+            match self.write_property(&targets[item_idx], id, value) {
+                Ok(old) => applied.push((item_idx, id, old)),
+                Err(e) => {
+                    for (item_idx, id, old) in applied.into_iter().rev() {
+                        let _ = self.write_property(&targets[item_idx], id, old);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Creates or merges `node` once for each element of `list`, bound to
+    /// `alias` in its property values.
+    ///
+    /// Elements are written one after another, as if by separate statements,
+    /// so a MERGE matches nodes created for earlier elements.
+    fn execute_unwind_write(
+        &mut self,
+        list: &ValueExpr,
+        alias: &str,
+        node: &NodeFilter,
+        merge: bool,
+        on_create: &[SetItem],
+        on_match: &[SetItem],
+    ) -> Result<QueryResult> {
+        for row in Self::unwind_rows(vec![Row::new()], list, alias)? {
+            let properties = node
+                .property_filters
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), executor::evaluate_value(value, &row)?)))
+                .collect::<Result<Vec<_>>>()?;
+            if merge {
+                self.execute_merge(&node.var, &node.label, &properties, on_create, on_match)?;
+            } else {
+                self.insert_node(&node.label, properties.into_iter().collect())?;
+            }
+        }
+        Ok(QueryResult::empty())
+    }
 
-        // Validate destination table exists
-        if !self.catalog.table_exists(&dst_table) {
-            return Err(RuzuError::SchemaError(format!(
-                "Destination table '{dst_table}' does not exist"
-            )));
+    fn execute_merge(
+        &mut self,
+        var: &str,
+        label: &str,
+        properties: &[(String, Value)],
+        on_create: &[SetItem],
+        on_match: &[SetItem],
+    ) -> Result<QueryResult> {
+        let schema = self
+            .catalog
+            .get_table(label)
+            .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
+        let pattern = MatchPattern::Node(NodeFilter {
+            var: var.to_string(),
+            label: label.to_string(),
+            property_filters: Vec::new(),
+        });
+        for item in on_create.iter().chain(on_match) {
+            self.resolve_set_target(&pattern, item)?;
         }
 
-        // Convert column definitions
-        let column_defs: Vec<ColumnDef> = columns
-            .into_iter()
-            .map(|(name, type_str)| {
-                let data_type = match type_str.to_uppercase().as_str() {
-                    "INT64" => DataType::Int64,
-                    "STRING" => DataType::String,
-                    "FLOAT64" => DataType::Float64,
-                    "BOOL" => DataType::Bool,
-                    _ => {
-                        return Err(RuzuError::SchemaError(format!(
-                            "Unknown data type: {type_str}"
-                        )))
-                    }
-                };
-                ColumnDef::new(name, data_type)
+        let props = properties
+            .iter()
+            .map(|(key, value)| {
+                let col = schema
+                    .get_column(key)
+                    .ok_or_else(|| BindError::UndefinedColumn(label.to_string(), key.clone()))?;
+                Ok((key.clone(), coerce_to_column_type(value.clone(), col.data_type)))
             })
             .collect::<Result<Vec<_>>>()?;
 
-        // Create relationship table schema
-        let schema = RelTableSchema::new(
-            table_name.clone(),
-            src_table,
-            dst_table,
-            column_defs,
-            Direction::Both,
-        )?;
+        let table = self
+            .tables
+            .get(label)
+            .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
+        let matched: Vec<Row> = Self::matching_offsets(table, &props)
+            .into_iter()
+            .map(|offset| {
+                let mut row = Row::new();
+                Self::bind_node(&mut row, var, table, offset);
+                row
+            })
+            .collect();
 
-        // Register in catalog
-        self.catalog.create_rel_table(schema.clone())?;
+        if !matched.is_empty() {
+            self.apply_set_items(&pattern, &matched, on_match)?;
+            return Ok(QueryResult::empty());
+        }
 
-        // Create storage table
-        let rel_table = RelTable::new(Arc::new(schema));
-        self.rel_tables.insert(table_name, rel_table);
+        // No match: build the new node from the pattern, then apply ON CREATE
+        // assignments to it before it is inserted
+        let mut row = Row::new();
+        for col in &schema.columns {
+            row.set(format!("{var}.{}", col.name), Value::Null);
+        }
+        for (key, value) in props {
+            row.set(format!("{var}.{key}"), value);
+        }
+        for item in on_create {
+            let target = self.resolve_set_target(&pattern, item)?;
+            let value = coerce_to_column_type(
+                executor::evaluate_value(&item.value, &row)?,
+                target.data_type,
+            );
+            row.set(format!("{var}.{}", item.property), value);
+        }
 
-        // Mark database as dirty
-        self.dirty = true;
+        let values = schema
+            .columns
+            .iter()
+            .map(|col| {
+                let value = row.get(&format!("{var}.{}", col.name)).cloned().unwrap_or(Value::Null);
+                (col.name.clone(), value)
+            })
+            .collect();
+        self.insert_node(label, values)?;
 
         Ok(QueryResult::empty())
     }
 
-    fn execute_match_create(
+    /// Pairs up the nodes matched by a relationship MERGE pattern.
+    ///
+    /// Returns one row per existing relationship with the given properties
+    /// (`(column index, value)`), and the `(src, dst)` offsets of node pairs
+    /// that are not yet connected, each with a row binding both endpoints.
+    #[allow(clippy::type_complexity)]
+    fn find_merge_rels(
+        &self,
+        pattern: &MatchPattern,
+        props: &[(usize, Value)],
+    ) -> Result<(Vec<Row>, Vec<(u64, u64, Row)>)> {
+        let MatchPattern::Rel {
+            src_node,
+            rel_var,
+            rel_type,
+            dst_node,
+            ..
+        } = pattern
+        else {
+            return Ok((Vec::new(), Vec::new()));
+        };
+
+        let rel_table = self.rel_tables.get(rel_type).ok_or_else(|| {
+            RuzuError::ExecutionError(format!(
+                "Relationship table '{rel_type}' not found in storage"
+            ))
+        })?;
+        let src_table = self.tables.get(&src_node.label).ok_or_else(|| {
+            RuzuError::SchemaError(format!("Table '{}' does not exist", src_node.label))
+        })?;
+        let dst_table = self.tables.get(&dst_node.label).ok_or_else(|| {
+            RuzuError::SchemaError(format!("Table '{}' does not exist", dst_node.label))
+        })?;
+
+        // Split matched node pairs into existing relationships and pairs to connect
+        let dst_offsets = Self::candidate_offsets(dst_table, dst_node, &Row::new())?;
+        let mut matched = Vec::new();
+        let mut to_create = Vec::new();
+        for src_offset in Self::candidate_offsets(src_table, src_node, &Row::new())? {
+            let edges = rel_table.get_forward_edges(src_offset as u64);
+            for &dst_offset in &dst_offsets {
+                let mut existing = edges
+                    .iter()
+                    .filter(|(dst, rel_id)| {
+                        *dst == dst_offset as u64
+                            && props.iter().all(|(col_idx, expected)| {
+                                rel_table
+                                    .get_properties(*rel_id)
+                                    .and_then(|stored| stored.get(*col_idx))
+                                    .is_some_and(|actual| {
+                                        let (actual, expected) =
+                                            promote_for_comparison(actual.clone(), expected.clone());
+                                        actual == expected
+                                    })
+                            })
+                    })
+                    .peekable();
+
+                let mut row = Row::new();
+                Self::bind_node(&mut row, &src_node.var, src_table, src_offset);
+                Self::bind_node(&mut row, &dst_node.var, dst_table, dst_offset);
+                if existing.peek().is_none() {
+                    to_create.push((src_offset as u64, dst_offset as u64, row));
+                    continue;
+                }
+                for &(_, rel_id) in existing {
+                    let mut row = row.clone();
+                    if let Some(var) = rel_var.as_deref() {
+                        Self::bind_rel(&mut row, var, rel_table, rel_id);
+                    }
+                    matched.push(row);
+                }
+            }
+        }
+
+        Ok((matched, to_create))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn execute_match_merge(
         &mut self,
-        src_node: &NodeFilter,
-        dst_node: &NodeFilter,
+        src_filter: &NodeFilter,
+        dst_filter: &NodeFilter,
+        rel_var: Option<&str>,
         rel_type: &str,
-        rel_props: Vec<(String, Literal)>,
-        _src_var: String,
-        _dst_var: String,
+        rel_props: &[(String, Literal)],
+        src_var: &str,
+        dst_var: &str,
+        on_create: &[SetItem],
+        on_match: &[SetItem],
     ) -> Result<QueryResult> {
-        // Validate relationship table exists
         let rel_schema = self.catalog.get_rel_table(rel_type).ok_or_else(|| {
             RuzuError::SchemaError(format!("Relationship table '{rel_type}' does not exist"))
         })?;
-        let rel_table_id = rel_schema.table_id;
 
-        // Find source node
-        let src_table = self.tables.get(&src_node.label).ok_or_else(|| {
-            RuzuError::SchemaError(format!("Table '{}' does not exist", src_node.label))
-        })?;
+        // The relationship pattern may list the matched nodes in either order
+        let find_node = |var: &str| {
+            [src_filter, dst_filter]
+                .into_iter()
+                .find(|node| node.var == var)
+                .ok_or_else(|| RuzuError::from(BindError::UndefinedVariable(var.to_string())))
+        };
+        let src_node = find_node(src_var)?;
+        let dst_node = find_node(dst_var)?;
+        if src_node.label != rel_schema.src_table || dst_node.label != rel_schema.dst_table {
+            return Err(RuzuError::SchemaError(format!(
+                "Relationship '{rel_type}' connects '{}' to '{}', not '{}' to '{}'",
+                rel_schema.src_table, rel_schema.dst_table, src_node.label, dst_node.label
+            )));
+        }
 
-        let src_node_offset = if let Some((key, value)) = &src_node.property_filter {
-            let val = literal_to_value(value);
-            src_table.find_by_pk(key, &val)
-        } else {
-            None
+        let pattern = MatchPattern::Rel {
+            src_node: src_node.clone(),
+            rel_var: rel_var.map(str::to_string),
+            rel_type: rel_type.to_string(),
+            rel_filters: Vec::new(),
+            dst_node: dst_node.clone(),
+            direction: RelDirection::Forward,
         };
+        let create_targets = on_create
+            .iter()
+            .map(|item| self.resolve_set_target(&pattern, item))
+            .collect::<Result<Vec<_>>>()?;
+        for item in on_match {
+            self.resolve_set_target(&pattern, item)?;
+        }
 
-        // Find destination node
-        let dst_table = self.tables.get(&dst_node.label).ok_or_else(|| {
-            RuzuError::SchemaError(format!("Table '{}' does not exist", dst_node.label))
-        })?;
+        // Pattern properties as (column index, value)
+        let props = rel_props
+            .iter()
+            .map(|(key, literal)| {
+                let col_idx = rel_schema.get_column_index(key).ok_or_else(|| {
+                    BindError::UndefinedColumn(rel_type.to_string(), key.clone())
+                })?;
+                let value = coerce_to_column_type(
+                    literal_to_value(literal),
+                    rel_schema.columns[col_idx].data_type,
+                );
+                Ok((col_idx, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        let dst_node_offset = if let Some((key, value)) = &dst_node.property_filter {
-            let val = literal_to_value(value);
-            dst_table.find_by_pk(key, &val)
-        } else {
-            None
-        };
+        let (matched, to_create) = self.find_merge_rels(&pattern, &props)?;
+        let (mut targets, mut updates) = self.set_item_updates(&pattern, &matched, on_match)?;
 
-        // If either source or destination not found, nothing to create
-        let (src_offset, dst_offset) = match (src_node_offset, dst_node_offset) {
-            (Some(s), Some(d)) => (s as u64, d as u64),
-            _ => return Ok(QueryResult::empty()), // No match, no relationship created
-        };
+        // Build the missing relationships. ON CREATE assignments to the new
+        // relationship are folded into its properties; assignments to the
+        // endpoints become regular updates
+        let node_items: Vec<SetItem> = on_create
+            .iter()
+            .filter(|item| Some(item.var.as_str()) != rel_var)
+            .cloned()
+            .collect();
+        let next_rel_id = self
+            .rel_tables
+            .get(rel_type)
+            .map_or(0, RelTable::next_rel_id);
+        let mut rels = Vec::with_capacity(to_create.len());
+        let mut created = Vec::with_capacity(to_create.len());
+        for (rel_id, (src, dst, mut row)) in (next_rel_id..).zip(to_create) {
+            let mut values = vec![Value::Null; rel_schema.columns.len()];
+            for (col_idx, value) in &props {
+                values[*col_idx] = value.clone();
+            }
+            #[allow(clippy::cast_possible_wrap)]
+            if let Some(var) = rel_var {
+                for (col, value) in rel_schema.columns.iter().zip(&values) {
+                    row.set(format!("{var}.{}", col.name), value.clone());
+                }
+                row.set(format!("{var}._id"), Value::Int64(rel_id as i64));
+            }
+            for (item, target) in on_create.iter().zip(&create_targets) {
+                if target.is_rel {
+                    let value = coerce_to_column_type(
+                        executor::evaluate_value(&item.value, &row)?,
+                        target.data_type,
+                    );
+                    row.set(format!("{}.{}", item.var, item.property), value.clone());
+                    values[target.col_idx] = value;
+                }
+            }
+            for (col, value) in rel_schema.columns.iter().zip(&values) {
+                if let Some(actual) = value.data_type().filter(|t| *t != col.data_type) {
+                    return Err(RuzuError::TypeError {
+                        expected: col.data_type.name().into(),
+                        actual: actual.name().into(),
+                    });
+                }
+            }
+            rels.push((src, dst, values));
+            created.push(row);
+        }
+        let (node_targets, node_updates) = self.set_item_updates(&pattern, &created, &node_items)?;
+        let offset = targets.len();
+        targets.extend(node_targets);
+        updates.extend(node_updates.into_iter().map(|(idx, id, value)| (idx + offset, id, value)));
 
-        // Convert relationship properties
-        let props: Vec<Value> = rel_props
-            .into_iter()
-            .map(|(_, literal)| literal_into_value(literal))
+        self.commit_merge(rel_type, rel_schema.table_id, rels, &targets, updates)?;
+
+        Ok(QueryResult::empty())
+    }
+
+    /// Inserts the `(src, dst, properties)` relationships created by a MERGE
+    /// and writes its `(target index, id, value)` updates, logged together as
+    /// one WAL transaction before any of them is applied.
+    fn commit_merge(
+        &mut self,
+        rel_type: &str,
+        rel_table_id: u32,
+        rels: Vec<(u64, u64, Vec<Value>)>,
+        targets: &[SetTarget],
+        updates: Vec<(usize, u64, Value)>,
+    ) -> Result<()> {
+        if rels.is_empty() && updates.is_empty() {
+            return Ok(());
+        }
+
+        self.check_updates(targets, &updates)?;
+        let mut wal_records: Vec<_> = rels
+            .iter()
+            .map(|(src, dst, values)| {
+                (
+                    WalRecordType::RelInsertion,
+                    WalPayload::RelInsertion {
+                        table_id: rel_table_id,
+                        src: *src,
+                        dst: *dst,
+                        props: values.clone(),
+                    },
+                )
+            })
             .collect();
+        wal_records.extend(
+            updates
+                .iter()
+                .map(|(item_idx, id, value)| targets[*item_idx].wal_record(*id, value.clone())),
+        );
+        self.log_transaction(wal_records)?;
 
-        // Get mutable reference to relationship table
         let rel_table = self.rel_tables.get_mut(rel_type).ok_or_else(|| {
             RuzuError::ExecutionError(format!(
                 "Relationship table '{rel_type}' not found in storage"
             ))
         })?;
+        for (src, dst, values) in rels {
+            rel_table.insert(src, dst, values)?;
+        }
+        self.write_properties(targets, updates)?;
+        self.dirty = true;
 
-        // Insert the relationship
-        let _rel_id = rel_table.insert(src_offset, dst_offset, props.clone())?;
+        Ok(())
+    }
 
-        // Write WAL record
-        if let Some(ref mut wal_writer) = self.wal_writer {
-            let tx_id = self.next_tx_id.fetch_add(1, Ordering::Relaxed);
+    /// Resolves a DELETE variable to the node or relationship it is bound to.
+    fn resolve_delete_target<'a>(pattern: &'a MatchPattern, var: &str) -> Result<DeleteTarget<'a>> {
+        let node = match pattern {
+            MatchPattern::Node(node) => (node.var == var).then_some(node),
+            MatchPattern::Rel {
+                src_node,
+                rel_var,
+                rel_type,
+                dst_node,
+                ..
+            } => {
+                if rel_var.as_deref() == Some(var) {
+                    return Ok(DeleteTarget::Rel {
+                        rel_type,
+                        src_var: &src_node.var,
+                        dst_var: &dst_node.var,
+                    });
+                }
+                [src_node, dst_node].into_iter().find(|node| node.var == var)
+            }
+        };
 
-            let begin_lsn = wal_writer.next_lsn();
-            let begin_record = WalRecord::begin_transaction(tx_id, begin_lsn);
-            wal_writer.append(&begin_record)?;
-
-            let insert_lsn = wal_writer.next_lsn();
-            let insert_record = WalRecord::new(
-                WalRecordType::RelInsertion,
-                tx_id,
-                insert_lsn,
-                WalPayload::RelInsertion {
-                    table_id: rel_table_id,
-                    src: src_offset,
-                    dst: dst_offset,
-                    props,
+        node.map(|node| DeleteTarget::Node(&node.label))
+            .ok_or_else(|| BindError::UndefinedVariable(var.to_string()).into())
+    }
+
+    /// Returns every relationship attached to one of `nodes` (given as label and
+    /// offset), in either direction, as `(rel_type, src, dst, rel_id)`.
+    fn incident_edges(&self, nodes: &[(String, u64)]) -> Vec<(String, u64, u64, u64)> {
+        let mut seen = std::collections::HashSet::new();
+        let mut edges = Vec::new();
+        for (label, offset) in nodes {
+            for (rel_type, rel_table) in &self.rel_tables {
+                let rel_schema = rel_table.schema();
+                if rel_schema.src_table == *label {
+                    for (dst, rel_id) in rel_table.get_forward_edges(*offset) {
+                        if seen.insert((rel_type.as_str(), rel_id)) {
+                            edges.push((rel_type.clone(), *offset, dst, rel_id));
+                        }
+                    }
+                }
+                if rel_schema.dst_table == *label {
+                    for (src, rel_id) in rel_table.get_backward_edges(*offset) {
+                        if seen.insert((rel_type.as_str(), rel_id)) {
+                            edges.push((rel_type.clone(), src, *offset, rel_id));
+                        }
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    /// Builds the WAL records for deleting `edges` and then `nodes`.
+    fn deletion_wal_records(
+        &self,
+        edges: &[(String, u64, u64, u64)],
+        nodes: &[(String, u64)],
+    ) -> Result<Vec<(WalRecordType, WalPayload)>> {
+        let mut wal_records = Vec::with_capacity(edges.len() + nodes.len());
+        for (rel_type, src, dst, rel_id) in edges {
+            let table_id = self
+                .catalog
+                .get_rel_table(rel_type)
+                .map(|schema| schema.table_id)
+                .ok_or_else(|| {
+                    RuzuError::SchemaError(format!(
+                        "Relationship table '{rel_type}' does not exist"
+                    ))
+                })?;
+            wal_records.push((
+                WalRecordType::RelDeletion,
+                WalPayload::RelDeletion {
+                    table_id,
+                    src: *src,
+                    dst: *dst,
+                    rel_id: *rel_id,
                 },
-            );
-            wal_writer.append(&insert_record)?;
+            ));
+        }
+        for (label, offset) in nodes {
+            let schema = self
+                .catalog
+                .get_table(label)
+                .ok_or_else(|| RuzuError::SchemaError(format!("Table '{label}' does not exist")))?;
+            let pk = self
+                .tables
+                .get(label)
+                .and_then(|table| table.get(*offset as usize, &schema.primary_key[0]))
+                .unwrap_or(Value::Null);
+            wal_records.push((
+                WalRecordType::NodeDeletion,
+                WalPayload::NodeDeletion {
+                    table_id: schema.table_id,
+                    node_offset: *offset,
+                    pk,
+                },
+            ));
+        }
+        Ok(wal_records)
+    }
 
-            let commit_lsn = wal_writer.next_lsn();
-            let commit_record = WalRecord::commit(tx_id, commit_lsn);
-            wal_writer.append(&commit_record)?;
+    fn execute_match_delete(
+        &mut self,
+        pattern: &MatchPattern,
+        filter: Option<&parser::ast::Expression>,
+        vars: &[String],
+        detach: bool,
+    ) -> Result<QueryResult> {
+        let targets = vars
+            .iter()
+            .map(|var| Self::resolve_delete_target(pattern, var))
+            .collect::<Result<Vec<_>>>()?;
+        let rows = self.collect_pattern_rows(pattern, filter)?;
+
+        // Distinct nodes and relationships to delete, in match order
+        let mut seen_nodes = std::collections::HashSet::new();
+        let mut seen_edges = std::collections::HashSet::new();
+        let mut nodes: Vec<(String, u64)> = Vec::new();
+        let mut edges: Vec<(String, u64, u64, u64)> = Vec::new();
+        for row in &rows {
+            for (var, target) in vars.iter().zip(&targets) {
+                let id = Self::bound_id(row, var)?;
+                match *target {
+                    DeleteTarget::Node(label) => {
+                        if seen_nodes.insert((label, id)) {
+                            nodes.push((label.to_string(), id));
+                        }
+                    }
+                    DeleteTarget::Rel {
+                        rel_type,
+                        src_var,
+                        dst_var,
+                    } => {
+                        if seen_edges.insert((rel_type.to_string(), id)) {
+                            let src = Self::bound_id(row, src_var)?;
+                            let dst = Self::bound_id(row, dst_var)?;
+                            // A backward or undirected pattern may have matched
+                            // the relationship from its destination
+                            let forward = self.rel_tables.get(rel_type).is_some_and(|table| {
+                                table.get_forward_edges(src).contains(&(dst, id))
+                            });
+                            let (src, dst) = if forward { (src, dst) } else { (dst, src) };
+                            edges.push((rel_type.to_string(), src, dst, id));
+                        }
+                    }
+                }
+            }
+        }
 
-            if self.config.wal_sync {
-                wal_writer.sync()?;
-            } else {
-                wal_writer.flush()?;
+        if nodes.is_empty() && edges.is_empty() {
+            return Ok(QueryResult::empty());
+        }
+
+        // Relationships still attached to a deleted node that are not deleted
+        // explicitly by this statement
+        let attached: Vec<_> = self
+            .incident_edges(&nodes)
+            .into_iter()
+            .filter(|(rel_type, _, _, rel_id)| !seen_edges.contains(&(rel_type.clone(), *rel_id)))
+            .collect();
+
+        if !detach {
+            if let Some((rel_type, ..)) = attached.first() {
+                return Err(RuzuError::ReferentialIntegrity(format!(
+                    "Cannot delete node with {} attached relationship(s) (e.g. '{rel_type}'); \
+                     use DETACH DELETE to remove them as well",
+                    attached.len()
+                )));
             }
         }
+        edges.extend(attached);
+
+        let wal_records = self.deletion_wal_records(&edges, &nodes)?;
+
+        // Write WAL before modifying storage
+        self.log_transaction(wal_records)?;
+
+        for (rel_type, src, dst, rel_id) in edges {
+            let rel_table = self.rel_tables.get_mut(&rel_type).ok_or_else(|| {
+                RuzuError::ExecutionError(format!(
+                    "Relationship table '{rel_type}' not found in storage"
+                ))
+            })?;
+            rel_table.delete(src, dst, rel_id)?;
+        }
+        for (label, offset) in nodes {
+            let table = self.tables.get_mut(&label).ok_or_else(|| {
+                RuzuError::SchemaError(format!("Table '{label}' does not exist"))
+            })?;
+            let table = Arc::get_mut(table).ok_or_else(|| {
+                RuzuError::ExecutionError("Cannot modify table: multiple references exist".into())
+            })?;
+            table.delete(offset as usize)?;
+        }
 
         self.dirty = true;
 
@@ -1601,9 +3975,10 @@ impl Database {
     fn collect_multi_hop_rows(
         src_offsets: &[usize],
         rel_table: &RelTable,
+        direction: Direction,
         src_table: &Arc<NodeTable>,
         dst_table: &Arc<NodeTable>,
-        dst_filter: Option<&(String, Value)>,
+        dst_filter: &[(String, Value)],
         simple_projections: &[(String, String)],
         src_node: &NodeFilter,
         dst_node: &NodeFilter,
@@ -1622,7 +3997,7 @@ impl Database {
                     continue;
                 }
 
-                let edges = rel_table.get_forward_edges(current_node);
+                let edges = rel_table.get_edges(current_node, direction);
 
                 for (next_node, _rel_id) in edges {
                     if path.contains(&next_node) {
@@ -1632,15 +4007,8 @@ impl Database {
                     let new_depth = depth + 1;
 
                     if new_depth >= min_hops && new_depth <= max_hops {
-                        let passes_dst_filter = if let Some((key, expected_val)) = dst_filter {
-                            if let Some(actual_val) = dst_table.get(next_node as usize, key) {
-                                &actual_val == expected_val
-                            } else {
-                                false
-                            }
-                        } else {
-                            true
-                        };
+                        let passes_dst_filter =
+                            Self::has_properties(dst_table, next_node as usize, dst_filter);
 
                         if passes_dst_filter {
                             let mut row = Row::new();
@@ -1676,86 +4044,40 @@ impl Database {
     fn collect_single_hop_rows(
         src_offsets: &[usize],
         rel_table: &RelTable,
+        direction: Direction,
         rel_schema: &RelTableSchema,
         src_table: &Arc<NodeTable>,
         dst_table: &Arc<NodeTable>,
-        dst_filter: Option<&(String, Value)>,
+        dst_filter: &[(String, Value)],
+        rel_filter: &[(String, Value)],
         filter: Option<&parser::ast::Expression>,
         simple_projections: &[(String, String)],
         src_node: &NodeFilter,
         dst_node: &NodeFilter,
         rel_var: Option<&String>,
-    ) -> Vec<Row> {
+    ) -> Result<Vec<Row>> {
         let mut rows = Vec::new();
 
         for src_offset in src_offsets {
-            let edges = rel_table.get_forward_edges(*src_offset as u64);
+            let edges = rel_table.get_edges(*src_offset as u64, direction);
 
             for (dst_offset, rel_id) in edges {
-                // Apply destination filter if present
-                if let Some((key, expected_val)) = dst_filter {
-                    if let Some(actual_val) = dst_table.get(dst_offset as usize, key) {
-                        if &actual_val != expected_val {
-                            continue;
-                        }
-                    } else {
-                        continue;
-                    }
+                // Apply inline destination and relationship filters
+                if !Self::has_properties(dst_table, dst_offset as usize, dst_filter)
+                    || !Self::rel_has_properties(rel_table, rel_id, rel_filter)
+                {
+                    continue;
                 }
 
                 // Apply WHERE clause filter if present
                 if let Some(expr) = filter {
-                    let filter_val = if expr.var == src_node.var {
-                        src_table.get(*src_offset, &expr.property)
-                    } else if expr.var == dst_node.var {
-                        dst_table.get(dst_offset as usize, &expr.property)
-                    } else if rel_var == Some(&expr.var) {
-                        if let Some(props) = rel_table.get_properties(rel_id) {
-                            rel_schema
-                                .columns
-                                .iter()
-                                .position(|c| c.name == expr.property)
-                                .and_then(|idx| props.get(idx).cloned())
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-
-                    let matches = match filter_val {
-                        Some(val) => {
-                            let literal_val = literal_to_value(&expr.value);
-                            let (val, literal_val) = promote_for_comparison(val, literal_val);
-                            let cmp_result = val.compare(&literal_val);
-                            match cmp_result {
-                                None => false,
-                                Some(ordering) => match expr.op {
-                                    parser::ast::ComparisonOp::Gt => {
-                                        ordering == std::cmp::Ordering::Greater
-                                    }
-                                    parser::ast::ComparisonOp::Lt => {
-                                        ordering == std::cmp::Ordering::Less
-                                    }
-                                    parser::ast::ComparisonOp::Eq => {
-                                        ordering == std::cmp::Ordering::Equal
-                                    }
-                                    parser::ast::ComparisonOp::Gte => {
-                                        ordering != std::cmp::Ordering::Less
-                                    }
-                                    parser::ast::ComparisonOp::Lte => {
-                                        ordering != std::cmp::Ordering::Greater
-                                    }
-                                    parser::ast::ComparisonOp::Neq => {
-                                        ordering != std::cmp::Ordering::Equal
-                                    }
-                                },
-                            }
-                        }
-                        None => false,
-                    };
-
-                    if !matches {
+                    let mut candidate = Row::new();
+                    Self::bind_node(&mut candidate, &src_node.var, src_table, *src_offset);
+                    Self::bind_node(&mut candidate, &dst_node.var, dst_table, dst_offset as usize);
+                    if let Some(var) = rel_var {
+                        Self::bind_rel(&mut candidate, var, rel_table, rel_id);
+                    }
+                    if !executor::evaluate_expression(expr, &candidate)? {
                         continue;
                     }
                 }
@@ -1791,7 +4113,7 @@ impl Database {
             }
         }
 
-        rows
+        Ok(rows)
     }
 
     fn execute_match_rel(
@@ -1809,11 +4131,13 @@ impl Database {
         let order_by = modifiers.order_by;
         let skip = modifiers.skip;
         let limit = modifiers.limit;
-        // Convert ReturnItem to (String, String) for now - aggregates in rel queries handled later
-        let simple_projections: Vec<(String, String)> = projections.iter().filter_map(|item| {
+        // Convert ReturnItem to (String, String) for simple projections
+        let mut simple_projections: Vec<(String, String)> = projections.iter().filter_map(|item| {
             match item {
                 ReturnItem::Projection { var, property } => Some((var.clone(), property.clone())),
-                ReturnItem::Aggregate(_) => None, // TODO: Handle aggregates in rel queries
+                ReturnItem::Aggregate(_) | ReturnItem::Variable(_) | ReturnItem::Expression(_) => {
+                    None
+                }
             }
         }).collect();
         // Validate relationship table exists
@@ -1836,6 +4160,12 @@ impl Database {
                 "Relationship table '{rel_type}' not found in storage"
             ))
         })?;
+        let direction = traversal_direction(Binder::resolve_direction(
+            &rel_schema,
+            &src_node.label,
+            &dst_node.label,
+            rel.direction,
+        )?);
 
         // Determine output columns
         let output_columns: Vec<String> = simple_projections
@@ -1843,40 +4173,63 @@ impl Database {
             .map(|(var, prop)| format!("{var}.{prop}"))
             .collect();
 
-        // Check if we have a filter on source node
-        let src_offsets: Vec<usize> = if let Some((key, value)) = &src_node.property_filter {
-            let val = literal_to_value(value);
-            if let Some(offset) = src_table.find_by_pk(key, &val) {
-                vec![offset]
-            } else {
-                vec![]
+        // Aggregate inputs are collected alongside the projected columns
+        let has_aggregates = projections
+            .iter()
+            .any(|item| matches!(item, ReturnItem::Aggregate(_)));
+        for property in projections.iter().flat_map(|item| match item {
+            ReturnItem::Aggregate(agg) => agg.input.iter().flat_map(ValueExpr::properties).collect(),
+            _ => Vec::new(),
+        }) {
+            if !simple_projections.contains(&property) {
+                simple_projections.push(property);
             }
+        }
+
+        // Check if we have a filter on source node
+        let src_offsets: Vec<usize> = if !src_node.property_filters.is_empty() {
+            Self::candidate_offsets(src_table, src_node, &Row::new())?
+        } else if let Some(offsets) = filter
+            .as_ref()
+            .and_then(|expr| Self::pk_in_list_offsets(src_table, &src_node.var, expr))
+        {
+            offsets
         } else {
-            // All source nodes
-            (0..src_table.len()).collect()
+            // All live source nodes
+            src_table.live_offsets().collect()
         };
 
-        // Check if we have a filter on destination node
-        let dst_filter = dst_node.property_filter.as_ref().map(|(key, value)| {
-            let val = literal_to_value(value);
-            (key.clone(), val)
-        });
+        // Inline filters on the destination node and the relationship
+        let dst_filter = Self::filter_props(&dst_node.property_filters, &Row::new())?;
+        let rel_filter = Self::filter_props(rel.rel_filters, &Row::new())?;
 
         // Collect all rows via multi-hop or single-hop traversal
         let rows = if let Some((min_hops, max_hops)) = path_bounds {
             Self::collect_multi_hop_rows(
-                &src_offsets, rel_table, src_table, dst_table,
-                dst_filter.as_ref(), &simple_projections, src_node, dst_node,
+                &src_offsets, rel_table, direction, src_table, dst_table,
+                &dst_filter, &simple_projections, src_node, dst_node,
                 min_hops, max_hops,
             )
         } else {
             Self::collect_single_hop_rows(
-                &src_offsets, rel_table, &rel_schema, src_table, dst_table,
-                dst_filter.as_ref(), filter, &simple_projections,
+                &src_offsets, rel_table, direction, &rel_schema, src_table, dst_table,
+                &dst_filter, &rel_filter, filter, &simple_projections,
                 src_node, dst_node, rel_var,
-            )
+            )?
         };
 
+        if has_aggregates {
+            let rows = Self::aggregate_return_items(Box::new(ValuesOperator::new(rows)), projections)?;
+            let output_columns = projections.iter().map(ReturnItem::column_name).collect();
+            return Ok(Self::apply_modifiers_and_build_result(
+                rows,
+                output_columns,
+                order_by,
+                skip,
+                limit,
+            ));
+        }
+
         Ok(Self::apply_modifiers_and_build_result(rows, output_columns, order_by, skip, limit))
     }
 
@@ -2010,9 +4363,11 @@ impl Database {
             progress_callback,
         )?;
 
-        // Mark database as dirty
+        // COPY is not logged to the WAL, so checkpoint it before later
+        // logged changes can refer to its rows
         if import_result.rows_imported > 0 {
             self.dirty = true;
+            self.checkpoint()?;
         }
 
         Ok(import_result)
@@ -2179,23 +4534,35 @@ impl Database {
         final_result.rows_imported = total_inserted;
         final_result.rows_failed += total_failed;
 
-        // Mark database as dirty
+        // COPY is not logged to the WAL, so checkpoint it before later
+        // logged changes can refer to its relationships
         if total_inserted > 0 {
             self.dirty = true;
+            self.checkpoint()?;
         }
 
         Ok(final_result)
     }
 }
 
-/// Promotes values for cross-type comparison (Int64 vs Float64).
-/// Returns the pair with appropriate type promotion applied.
-#[allow(clippy::cast_precision_loss)]
-fn promote_for_comparison(a: Value, b: Value) -> (Value, Value) {
-    match (&a, &b) {
-        (Value::Int64(n), Value::Float64(_)) => (Value::Float64(*n as f64), b),
-        (Value::Float64(_), Value::Int64(n)) => (a, Value::Float64(*n as f64)),
-        _ => (a, b),
+/// Converts a numeric value to the float type of the column it is stored
+/// in: integers to FLOAT64 or FLOAT32, and FLOAT64 to FLOAT32.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn coerce_to_column_type(value: Value, data_type: DataType) -> Value {
+    match (value, data_type) {
+        (Value::Int64(n), DataType::Float64) => Value::Float64(n as f64),
+        (Value::Int64(n), DataType::Float32) => Value::Float32(n as f32),
+        (Value::Float64(f), DataType::Float32) => Value::Float32(f as f32),
+        (value, _) => value,
+    }
+}
+
+/// Maps the direction of a pattern relationship to the adjacency it is read from.
+fn traversal_direction(direction: RelDirection) -> Direction {
+    match direction {
+        RelDirection::Forward => Direction::Forward,
+        RelDirection::Backward => Direction::Backward,
+        RelDirection::Both => Direction::Both,
     }
 }
 
@@ -2206,6 +4573,11 @@ fn literal_to_value(literal: &Literal) -> Value {
         Literal::String(s) => Value::String(s.clone()),
         Literal::Float64(f) => Value::Float64(*f),
         Literal::Bool(b) => Value::Bool(*b),
+        Literal::List(items) => Value::List(items.iter().map(literal_to_value).collect()),
+        Literal::Date(days) => Value::Date(*days),
+        Literal::Timestamp(micros) => Value::Timestamp(*micros),
+        // Parameters are bound to their values before execution
+        Literal::Null | Literal::Parameter(_) => Value::Null,
     }
 }
 
@@ -2216,6 +4588,10 @@ fn literal_into_value(literal: Literal) -> Value {
         Literal::String(s) => Value::String(s),
         Literal::Float64(f) => Value::Float64(f),
         Literal::Bool(b) => Value::Bool(b),
+        Literal::List(items) => Value::List(items.into_iter().map(literal_into_value).collect()),
+        Literal::Date(days) => Value::Date(days),
+        Literal::Timestamp(micros) => Value::Timestamp(micros),
+        Literal::Null | Literal::Parameter(_) => Value::Null,
     }
 }
 
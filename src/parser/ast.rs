@@ -1,5 +1,7 @@
 //! Abstract Syntax Tree definitions for Cypher queries.
 
+use crate::types::temporal;
+
 /// A parsed Cypher statement.
 #[derive(Debug, Clone)]
 pub enum Statement {
@@ -43,8 +45,8 @@ pub enum Statement {
         filter: Option<Expression>,
         projections: Vec<ReturnItem>,
         order_by: Option<Vec<OrderByItem>>,
-        skip: Option<i64>,
-        limit: Option<i64>,
+        skip: Option<RowCount>,
+        limit: Option<RowCount>,
     },
     /// MATCH query statement with relationship pattern.
     MatchRel {
@@ -53,6 +55,8 @@ pub enum Statement {
         /// Relationship variable (optional), type
         rel_var: Option<String>,
         rel_type: String,
+        /// Inline property filters of the relationship
+        rel_filters: Vec<(String, ValueExpr)>,
         /// Destination node variable, label, and optional filter
         dst_node: NodeFilter,
         /// WHERE clause filter
@@ -62,11 +66,105 @@ pub enum Statement {
         /// ORDER BY clause
         order_by: Option<Vec<OrderByItem>>,
         /// SKIP amount
-        skip: Option<i64>,
+        skip: Option<RowCount>,
         /// LIMIT amount
-        limit: Option<i64>,
+        limit: Option<RowCount>,
         /// Variable-length path bounds (min, max) for multi-hop traversal
         path_bounds: Option<(u32, u32)>,
+        /// Direction of the relationship relative to the pattern
+        direction: RelDirection,
+    },
+    /// MERGE statement for a single node.
+    ///
+    /// Matches nodes carrying all of `properties`, or creates one if none exists.
+    Merge {
+        /// Node variable
+        var: String,
+        /// Node label (table name)
+        label: String,
+        /// Properties identifying the node
+        properties: Vec<(String, Literal)>,
+        /// Assignments applied when the node is created
+        on_create: Vec<SetItem>,
+        /// Assignments applied to every matched node
+        on_match: Vec<SetItem>,
+    },
+    /// `UNWIND list AS alias` followed by CREATE or MERGE of a node, which
+    /// runs once for each element of the list.
+    UnwindWrite {
+        /// List to unwind
+        list: ValueExpr,
+        /// Variable bound to each element
+        alias: String,
+        /// Node to create or merge; its property values may refer to `alias`
+        node: NodeFilter,
+        /// Whether the node is merged rather than created
+        merge: bool,
+        /// Assignments applied when a merged node is created
+        on_create: Vec<SetItem>,
+        /// Assignments applied to every matched node of a MERGE
+        on_match: Vec<SetItem>,
+    },
+    /// MATCH ... MERGE relationship statement.
+    ///
+    /// Creates the relationship between the matched nodes unless one with
+    /// the same properties already exists.
+    MatchMerge {
+        /// Source node variable, label, and optional property filter (key, value)
+        src_node: NodeFilter,
+        /// Destination node variable, label, and optional property filter (key, value)
+        dst_node: NodeFilter,
+        /// Relationship variable (optional)
+        rel_var: Option<String>,
+        /// Relationship type
+        rel_type: String,
+        /// Properties identifying the relationship
+        rel_props: Vec<(String, Literal)>,
+        /// Source variable name in relationship pattern
+        src_var: String,
+        /// Destination variable name in relationship pattern
+        dst_var: String,
+        /// Assignments applied when the relationship is created
+        on_create: Vec<SetItem>,
+        /// Assignments applied to every matched relationship
+        on_match: Vec<SetItem>,
+    },
+    /// MATCH ... SET statement for property updates.
+    MatchSet {
+        /// Pattern binding the variables to update
+        pattern: MatchPattern,
+        /// WHERE clause filter
+        filter: Option<Expression>,
+        /// Property assignments, applied in order
+        items: Vec<SetItem>,
+    },
+    /// MATCH ... [DETACH] DELETE statement for removing nodes and relationships.
+    MatchDelete {
+        /// Pattern binding the variables to delete
+        pattern: MatchPattern,
+        /// WHERE clause filter
+        filter: Option<Expression>,
+        /// Variables to delete
+        vars: Vec<String>,
+        /// Whether relationships attached to deleted nodes are removed too
+        detach: bool,
+    },
+    /// Multi-stage read query: `MATCH ... WITH ... RETURN ...`.
+    ///
+    /// Clauses run in order, each one transforming the rows produced by the
+    /// previous one; `projection` is the final RETURN.
+    Query {
+        /// Reading and projecting clauses preceding RETURN
+        clauses: Vec<Clause>,
+        /// RETURN clause
+        projection: Projection,
+    },
+    /// Read queries combined with `UNION` or `UNION ALL`.
+    Union {
+        /// Combined queries, in order
+        queries: Vec<SingleQuery>,
+        /// Whether duplicate rows are kept (`UNION ALL`)
+        all: bool,
     },
     /// COPY command for bulk CSV import.
     Copy {
@@ -102,10 +200,469 @@ pub struct CopyOptions {
 pub struct NodeFilter {
     /// Variable binding for this node
     pub var: String,
-    /// Node label (table name)
+    /// Node label (table name); empty when omitted on a variable bound by an
+    /// earlier clause of a multi-stage query
     pub label: String,
-    /// Optional property filter (key, value)
-    pub property_filter: Option<(String, Literal)>,
+    /// Inline property filters (key, value), all of which must match; a value
+    /// may refer to a variable bound by an earlier clause of a multi-stage query
+    pub property_filters: Vec<(String, ValueExpr)>,
+}
+
+impl NodeFilter {
+    /// Whether the node was written without a variable, as in `(:Car)`. It
+    /// is then bound to an internal variable starting with `#`, which is not
+    /// a valid identifier.
+    #[must_use]
+    pub fn is_anonymous(&self) -> bool {
+        self.var.starts_with('#')
+    }
+}
+
+/// Renders the node as Cypher, e.g. `(p:Person {name: 'Alice'})`.
+impl std::fmt::Display for NodeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        if !self.is_anonymous() {
+            write!(f, "{}", self.var)?;
+        }
+        if !self.label.is_empty() {
+            write!(f, ":{}", self.label)?;
+        }
+        write_property_filters(f, &self.property_filters)?;
+        write!(f, ")")
+    }
+}
+
+/// Writes inline property filters as ` {key: value, ...}`, or nothing.
+fn write_property_filters(
+    f: &mut std::fmt::Formatter<'_>,
+    filters: &[(String, ValueExpr)],
+) -> std::fmt::Result {
+    for (i, (key, value)) in filters.iter().enumerate() {
+        write!(f, "{}{key}: {value}", if i == 0 { " {" } else { ", " })?;
+    }
+    if filters.is_empty() {
+        Ok(())
+    } else {
+        write!(f, "}}")
+    }
+}
+
+/// Pattern matched by an updating MATCH statement (SET, DELETE).
+#[derive(Debug, Clone)]
+pub enum MatchPattern {
+    /// Single node pattern: `(n:Label {key: value})`.
+    Node(NodeFilter),
+    /// Single-hop relationship pattern: `(a:Label)-[r:REL_TYPE]->(b:Label)`.
+    Rel {
+        /// Source node variable, label, and optional filter
+        src_node: NodeFilter,
+        /// Relationship variable (optional)
+        rel_var: Option<String>,
+        /// Relationship type
+        rel_type: String,
+        /// Inline property filters of the relationship
+        rel_filters: Vec<(String, ValueExpr)>,
+        /// Destination node variable, label, and optional filter
+        dst_node: NodeFilter,
+        /// Direction of the relationship relative to the pattern
+        direction: RelDirection,
+    },
+}
+
+/// Direction of a relationship in a pattern, read from left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RelDirection {
+    /// `-[]->`: from the left node to the right node
+    #[default]
+    Forward,
+    /// `<-[]-`: from the right node to the left node
+    Backward,
+    /// `-[]-`: either way
+    Both,
+}
+
+/// Path pattern of a multi-stage MATCH: a node followed by any number of
+/// relationship hops, e.g. `(a:Person)-[:Knows]->(b)-[w:WorksAt]->(c:Company)`.
+#[derive(Debug, Clone)]
+pub struct PathPattern {
+    /// First node of the path
+    pub start: NodeFilter,
+    /// Relationships and the nodes they lead to, in path order
+    pub hops: Vec<PatternHop>,
+}
+
+impl PathPattern {
+    /// Returns the node and relationship variables of the path, in order.
+    pub fn variables(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.start.var).chain(
+            self.hops
+                .iter()
+                .flat_map(|hop| hop.rel_var.iter().chain(std::iter::once(&hop.node.var))),
+        )
+    }
+
+    /// Returns the same path read from its last node back to its first.
+    #[must_use]
+    pub fn reversed(&self) -> PathPattern {
+        let mut nodes = std::iter::once(&self.start)
+            .chain(self.hops.iter().map(|hop| &hop.node))
+            .rev();
+        let start = nodes.next().cloned().unwrap_or_else(|| self.start.clone());
+        let hops = self
+            .hops
+            .iter()
+            .rev()
+            .zip(nodes)
+            .map(|(hop, node)| PatternHop {
+                rel_var: hop.rel_var.clone(),
+                rel_type: hop.rel_type.clone(),
+                rel_filters: hop.rel_filters.clone(),
+                direction: match hop.direction {
+                    RelDirection::Forward => RelDirection::Backward,
+                    RelDirection::Backward => RelDirection::Forward,
+                    RelDirection::Both => RelDirection::Both,
+                },
+                node: node.clone(),
+            })
+            .collect();
+        PathPattern { start, hops }
+    }
+}
+
+/// Renders the path as Cypher, e.g. `(p)-[:Owns]->(:Car)`.
+impl std::fmt::Display for PathPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.start)?;
+        for hop in &self.hops {
+            let (left, right) = match hop.direction {
+                RelDirection::Forward => ("-", "->"),
+                RelDirection::Backward => ("<-", "-"),
+                RelDirection::Both => ("-", "-"),
+            };
+            write!(f, "{left}[{}:{}", hop.rel_var.as_deref().unwrap_or(""), hop.rel_type)?;
+            write_property_filters(f, &hop.rel_filters)?;
+            write!(f, "]{right}{}", hop.node)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<MatchPattern> for PathPattern {
+    fn from(pattern: MatchPattern) -> Self {
+        match pattern {
+            MatchPattern::Node(start) => PathPattern {
+                start,
+                hops: Vec::new(),
+            },
+            MatchPattern::Rel {
+                src_node,
+                rel_var,
+                rel_type,
+                rel_filters,
+                dst_node,
+                direction,
+            } => PathPattern {
+                start: src_node,
+                hops: vec![PatternHop {
+                    rel_var,
+                    rel_type,
+                    rel_filters,
+                    direction,
+                    node: dst_node,
+                }],
+            },
+        }
+    }
+}
+
+/// One relationship of a path pattern and the node it leads to:
+/// `-[r:REL_TYPE]->(b:Label)`, `<-[r:REL_TYPE]-(b:Label)` or `-[r:REL_TYPE]-(b:Label)`.
+#[derive(Debug, Clone)]
+pub struct PatternHop {
+    /// Relationship variable (optional)
+    pub rel_var: Option<String>,
+    /// Relationship type
+    pub rel_type: String,
+    /// Inline property filters of the relationship
+    pub rel_filters: Vec<(String, ValueExpr)>,
+    /// Direction of the relationship relative to the path
+    pub direction: RelDirection,
+    /// Node at the end of the relationship
+    pub node: NodeFilter,
+}
+
+/// One read query of a UNION.
+#[derive(Debug, Clone)]
+pub struct SingleQuery {
+    /// Reading and projecting clauses preceding RETURN
+    pub clauses: Vec<Clause>,
+    /// RETURN clause
+    pub projection: Projection,
+}
+
+/// Clause of a multi-stage query.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum Clause {
+    /// MATCH or OPTIONAL MATCH of comma-separated patterns with an optional
+    /// WHERE filter.
+    ///
+    /// Variables bound by earlier clauses or patterns join a pattern to the
+    /// incoming rows; a pattern sharing none is joined on the equalities of
+    /// the WHERE filter, or else as a cartesian product. An OPTIONAL MATCH
+    /// keeps rows without a match, binding the new variables to NULL.
+    Match {
+        patterns: Vec<PathPattern>,
+        filter: Option<Expression>,
+        optional: bool,
+    },
+    /// WITH projection, ending the scope of variables it does not carry over.
+    With(Projection),
+    /// UNWIND expansion: one row per element of `list`, bound to `alias`.
+    Unwind { list: ValueExpr, alias: String },
+}
+
+/// Projection performed by a WITH or RETURN clause.
+#[derive(Debug, Clone)]
+pub struct Projection {
+    /// Whether duplicate rows are removed (`DISTINCT`).
+    pub distinct: bool,
+    /// Whether every variable in scope is projected (`*`), ahead of `items`.
+    pub star: bool,
+    /// Projected items.
+    pub items: Vec<ProjectionItem>,
+    /// Filter applied to the projected rows (`WITH ... WHERE`).
+    pub filter: Option<Expression>,
+    /// ORDER BY clause.
+    pub order_by: Option<Vec<OrderByItem>>,
+    /// SKIP amount.
+    pub skip: Option<RowCount>,
+    /// LIMIT amount.
+    pub limit: Option<RowCount>,
+}
+
+/// Projected item with an optional `AS` alias.
+#[derive(Debug, Clone)]
+pub struct ProjectionItem {
+    /// Projected value.
+    pub item: ReturnItem,
+    /// Name the value is bound to in the following clauses.
+    pub alias: Option<String>,
+}
+
+/// Property assignment in a SET clause (`var.property = value`).
+#[derive(Debug, Clone)]
+pub struct SetItem {
+    /// Variable name.
+    pub var: String,
+    /// Property name.
+    pub property: String,
+    /// New value.
+    pub value: ValueExpr,
+}
+
+/// Value-producing expression, e.g. the right-hand side of a SET assignment.
+#[derive(Debug, Clone)]
+pub enum ValueExpr {
+    /// Constant value.
+    Literal(Literal),
+    /// Property of a bound variable (`var.property`).
+    Property { var: String, property: String },
+    /// Value bound to a variable by WITH or UNWIND (`alias`).
+    Variable(String),
+    /// Binary arithmetic (`left op right`).
+    Arithmetic {
+        left: Box<ValueExpr>,
+        op: ArithmeticOp,
+        right: Box<ValueExpr>,
+    },
+    /// Unary minus (`-operand`).
+    Negate(Box<ValueExpr>),
+    /// `CASE ... END`.
+    Case(Box<CaseExpr>),
+    /// Scalar function call (`name(arg, ...)`), resolved by the binder.
+    Function { name: String, args: Vec<ValueExpr> },
+}
+
+impl ValueExpr {
+    /// Returns the `(var, property)` pairs the expression reads, in order.
+    #[must_use]
+    pub fn properties(&self) -> Vec<(String, String)> {
+        match self {
+            ValueExpr::Property { var, property } => vec![(var.clone(), property.clone())],
+            ValueExpr::Arithmetic { left, right, .. } => {
+                let mut properties = left.properties();
+                properties.extend(right.properties());
+                properties
+            }
+            ValueExpr::Negate(operand) => operand.properties(),
+            ValueExpr::Case(case) => case
+                .operands()
+                .into_iter()
+                .flat_map(ValueExpr::properties)
+                .collect(),
+            ValueExpr::Function { args, .. } => args.iter().flat_map(ValueExpr::properties).collect(),
+            ValueExpr::Literal(_) | ValueExpr::Variable(_) => Vec::new(),
+        }
+    }
+}
+
+/// Renders the expression as Cypher, e.g. `p.price * p.qty`; used as the
+/// column name of an unaliased RETURN item.
+impl std::fmt::Display for ValueExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueExpr::Literal(literal) => write!(f, "{literal}"),
+            ValueExpr::Property { var, property } => write!(f, "{var}.{property}"),
+            ValueExpr::Variable(name) => write!(f, "{name}"),
+            ValueExpr::Arithmetic { left, op, right } => {
+                // Parenthesize operands that bind looser than `op`; the right
+                // operand also when equal, since - / % are not associative
+                let binds_looser = |operand: &ValueExpr, or_equal: bool| match operand {
+                    ValueExpr::Arithmetic { op: inner, .. } => {
+                        inner.precedence() < op.precedence()
+                            || (or_equal && inner.precedence() == op.precedence())
+                    }
+                    _ => false,
+                };
+                if binds_looser(left, false) {
+                    write!(f, "({left})")?;
+                } else {
+                    write!(f, "{left}")?;
+                }
+                write!(f, " {} ", op.symbol())?;
+                if binds_looser(right, true) {
+                    write!(f, "({right})")
+                } else {
+                    write!(f, "{right}")
+                }
+            }
+            ValueExpr::Negate(operand) => match **operand {
+                ValueExpr::Arithmetic { .. } => write!(f, "-({operand})"),
+                _ => write!(f, "-{operand}"),
+            },
+            ValueExpr::Case(case) => write!(f, "{case}"),
+            ValueExpr::Function { name, args } => {
+                write!(f, "{name}(")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// `CASE` expression, yielding the result of the first WHEN branch that
+/// matches, else the ELSE result (NULL when omitted).
+///
+/// A simple CASE (`CASE p.tier WHEN 1 THEN 'gold' ... END`) compares its
+/// operand with each WHEN value; a searched CASE (`CASE WHEN p.age < 18 THEN
+/// 'minor' ... END`) tests each WHEN condition.
+#[derive(Debug, Clone)]
+pub struct CaseExpr {
+    /// Operand of a simple CASE; None for a searched CASE.
+    pub operand: Option<ValueExpr>,
+    /// WHEN items and their THEN results, in order.
+    pub branches: Vec<(CaseWhen, ValueExpr)>,
+    /// ELSE result.
+    pub default: Option<ValueExpr>,
+}
+
+/// WHEN item of a CASE branch.
+#[derive(Debug, Clone)]
+pub enum CaseWhen {
+    /// Value compared with the operand of a simple CASE.
+    Value(ValueExpr),
+    /// Condition of a searched CASE.
+    Condition(Expression),
+}
+
+impl CaseExpr {
+    /// Returns every value expression the CASE evaluates, including those
+    /// inside WHEN conditions.
+    #[must_use]
+    pub fn operands(&self) -> Vec<&ValueExpr> {
+        let mut operands: Vec<&ValueExpr> = self.operand.iter().collect();
+        for (when, result) in &self.branches {
+            match when {
+                CaseWhen::Value(value) => operands.push(value),
+                CaseWhen::Condition(condition) => operands.extend(condition.operands()),
+            }
+            operands.push(result);
+        }
+        operands.extend(&self.default);
+        operands
+    }
+}
+
+impl std::fmt::Display for CaseExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CASE")?;
+        if let Some(operand) = &self.operand {
+            write!(f, " {operand}")?;
+        }
+        for (when, result) in &self.branches {
+            match when {
+                CaseWhen::Value(value) => write!(f, " WHEN {value}")?,
+                CaseWhen::Condition(condition) => write!(f, " WHEN {condition}")?,
+            }
+            write!(f, " THEN {result}")?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, " ELSE {default}")?;
+        }
+        write!(f, " END")
+    }
+}
+
+/// Arithmetic operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl ArithmeticOp {
+    /// Parses an arithmetic operator from a string.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "+" => Some(ArithmeticOp::Add),
+            "-" => Some(ArithmeticOp::Sub),
+            "*" => Some(ArithmeticOp::Mul),
+            "/" => Some(ArithmeticOp::Div),
+            "%" => Some(ArithmeticOp::Mod),
+            _ => None,
+        }
+    }
+
+    /// Returns the operator symbol.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ArithmeticOp::Add => "+",
+            ArithmeticOp::Sub => "-",
+            ArithmeticOp::Mul => "*",
+            ArithmeticOp::Div => "/",
+            ArithmeticOp::Mod => "%",
+        }
+    }
+
+    /// Binding strength: `* / %` bind tighter than `+ -`.
+    fn precedence(self) -> u8 {
+        match self {
+            ArithmeticOp::Add | ArithmeticOp::Sub => 1,
+            ArithmeticOp::Mul | ArithmeticOp::Div | ArithmeticOp::Mod => 2,
+        }
+    }
 }
 
 /// Literal values in Cypher queries.
@@ -119,6 +676,44 @@ pub enum Literal {
     Float64(f64),
     /// Boolean literal.
     Bool(bool),
+    /// List literal (`[1, 2, 3]`).
+    List(Vec<Literal>),
+    /// Date literal (`date('2024-01-31')`), in days since the epoch.
+    Date(i32),
+    /// Timestamp literal (`timestamp('2024-01-31 12:30:00')`), in
+    /// microseconds since the epoch.
+    Timestamp(i64),
+    /// NULL, written `NULL` or bound to a parameter.
+    Null,
+    /// Query parameter (`$name`), replaced by its value before execution.
+    Parameter(String),
+}
+
+impl std::fmt::Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::String(s) => write!(f, "'{s}'"),
+            Literal::Int64(n) => write!(f, "{n}"),
+            Literal::Float64(x) => write!(f, "{x:?}"),
+            Literal::Bool(b) => write!(f, "{b}"),
+            Literal::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Literal::Date(days) => write!(f, "date('{}')", temporal::format_date(*days)),
+            Literal::Timestamp(micros) => {
+                write!(f, "timestamp('{}')", temporal::format_timestamp(*micros))
+            }
+            Literal::Null => write!(f, "NULL"),
+            Literal::Parameter(name) => write!(f, "${name}"),
+        }
+    }
 }
 
 /// Return item in RETURN clause.
@@ -131,6 +726,11 @@ pub enum ReturnItem {
     },
     /// Aggregate expression.
     Aggregate(AggregateExpr),
+    /// Bare variable: a node or relationship carried through WITH, or a value
+    /// bound by an earlier `AS` alias.
+    Variable(String),
+    /// Computed value, e.g. `p.price * p.qty`.
+    Expression(ValueExpr),
 }
 
 impl ReturnItem {
@@ -142,18 +742,58 @@ impl ReturnItem {
 
     /// Creates an aggregate return item.
     #[must_use]
-    pub fn aggregate(func: AstAggregateFunction, input: Option<(String, String)>) -> Self {
-        ReturnItem::Aggregate(AggregateExpr { function: func, input })
+    pub fn aggregate(func: AstAggregateFunction, input: Option<ValueExpr>) -> Self {
+        ReturnItem::Aggregate(AggregateExpr {
+            function: func,
+            input,
+            distinct: false,
+            percentile: None,
+        })
+    }
+
+    /// Returns the result column name used when the item has no alias,
+    /// e.g. `p.name`, `COUNT(*)`, `SUM(p.age)` or `COUNT(DISTINCT p.city)`.
+    #[must_use]
+    pub fn column_name(&self) -> String {
+        match self {
+            ReturnItem::Projection { var, property } => format!("{var}.{property}"),
+            ReturnItem::Aggregate(agg) => agg.to_string(),
+            ReturnItem::Variable(name) => name.clone(),
+            ReturnItem::Expression(expr) => expr.to_string(),
+        }
     }
 }
 
 /// Aggregate expression in the AST.
 #[derive(Debug, Clone)]
 pub struct AggregateExpr {
-    /// Aggregate function (COUNT, SUM, AVG, MIN, MAX).
+    /// Aggregate function (COUNT, SUM, AVG, MIN, MAX, ...).
     pub function: AstAggregateFunction,
     /// Input expression (None for COUNT(*)).
-    pub input: Option<(String, String)>, // (var, property)
+    pub input: Option<ValueExpr>,
+    /// `DISTINCT`: each distinct input value is aggregated once.
+    pub distinct: bool,
+    /// Percentile between 0 and 1, for `percentileCont`/`percentileDisc`.
+    pub percentile: Option<ValueExpr>,
+}
+
+/// Renders the aggregate as Cypher, e.g. `COUNT(DISTINCT p.city)`; used as
+/// the column name of an unaliased RETURN item.
+impl std::fmt::Display for AggregateExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.function.name())?;
+        if self.distinct {
+            write!(f, "DISTINCT ")?;
+        }
+        match &self.input {
+            Some(input) => write!(f, "{input}")?,
+            None => write!(f, "*")?,
+        }
+        if let Some(percentile) = &self.percentile {
+            write!(f, ", {percentile}")?;
+        }
+        write!(f, ")")
+    }
 }
 
 /// Aggregate functions in AST.
@@ -164,6 +804,16 @@ pub enum AstAggregateFunction {
     Avg,
     Min,
     Max,
+    /// Collects the input values into a list.
+    Collect,
+    /// Sample standard deviation.
+    StDev,
+    /// Population standard deviation.
+    StDevP,
+    /// Percentile interpolated between the two nearest values.
+    PercentileCont,
+    /// Percentile rounded to the nearest input value.
+    PercentileDisc,
 }
 
 impl AstAggregateFunction {
@@ -176,29 +826,192 @@ impl AstAggregateFunction {
             "AVG" => Some(AstAggregateFunction::Avg),
             "MIN" => Some(AstAggregateFunction::Min),
             "MAX" => Some(AstAggregateFunction::Max),
+            "COLLECT" => Some(AstAggregateFunction::Collect),
+            "STDEV" => Some(AstAggregateFunction::StDev),
+            "STDEVP" => Some(AstAggregateFunction::StDevP),
+            "PERCENTILECONT" => Some(AstAggregateFunction::PercentileCont),
+            "PERCENTILEDISC" => Some(AstAggregateFunction::PercentileDisc),
             _ => None,
         }
     }
+
+    /// Returns the upper-case name of the function.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            AstAggregateFunction::Count => "COUNT",
+            AstAggregateFunction::Sum => "SUM",
+            AstAggregateFunction::Avg => "AVG",
+            AstAggregateFunction::Min => "MIN",
+            AstAggregateFunction::Max => "MAX",
+            AstAggregateFunction::Collect => "COLLECT",
+            AstAggregateFunction::StDev => "STDEV",
+            AstAggregateFunction::StDevP => "STDEVP",
+            AstAggregateFunction::PercentileCont => "PERCENTILECONT",
+            AstAggregateFunction::PercentileDisc => "PERCENTILEDISC",
+        }
+    }
+
+    /// Returns true for the percentile functions, which take the percentile
+    /// as a second argument.
+    #[must_use]
+    pub fn takes_percentile(&self) -> bool {
+        matches!(
+            self,
+            AstAggregateFunction::PercentileCont | AstAggregateFunction::PercentileDisc
+        )
+    }
 }
 
 /// ORDER BY item.
 #[derive(Debug, Clone)]
 pub struct OrderByItem {
-    /// Variable name.
-    pub var: String,
-    /// Property name.
-    pub property: String,
+    /// Sort key (`var.property` or a WITH alias). An aggregate such as
+    /// `COUNT(*)` is a `Variable` named like its output column.
+    pub expr: ValueExpr,
     /// Sort direction (true = ASC, false = DESC).
     pub ascending: bool,
 }
 
-/// Expression in WHERE clause.
+/// Number of rows in a SKIP or LIMIT clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowCount {
+    /// Literal count: `LIMIT 10`.
+    Count(i64),
+    /// Query parameter (`$name`), replaced by its value before execution.
+    Parameter(String),
+}
+
+/// Boolean expression in a WHERE clause.
 #[derive(Debug, Clone)]
-pub struct Expression {
-    pub var: String,
-    pub property: String,
-    pub op: ComparisonOp,
-    pub value: Literal,
+pub enum Expression {
+    /// Comparison of two values: `p.age > 30`, `a.balance > b.balance * 2`.
+    Comparison {
+        left: ValueExpr,
+        op: ComparisonOp,
+        right: ValueExpr,
+    },
+    /// Boolean value used as a predicate: `p.active`.
+    Value(ValueExpr),
+    /// NULL test: `p.email IS NULL`, or `IS NOT NULL` when `negated`.
+    IsNull { operand: ValueExpr, negated: bool },
+    /// List membership: `p.name IN ['Alice', 'Bob']`, or `NOT IN` when `negated`.
+    In {
+        operand: ValueExpr,
+        list: ValueExpr,
+        negated: bool,
+    },
+    /// Conjunction: `a AND b`.
+    And(Box<Expression>, Box<Expression>),
+    /// Disjunction: `a OR b`.
+    Or(Box<Expression>, Box<Expression>),
+    /// Negation: `NOT a`.
+    Not(Box<Expression>),
+    /// Whether a path from the variables bound so far has a match satisfying
+    /// `filter`: `EXISTS { MATCH (p)-[:Owns]->(c:Car) WHERE c.year > 2020 }`,
+    /// or a pattern predicate such as `(p)-[:Owns]->(:Car)`.
+    Exists {
+        pattern: Box<PathPattern>,
+        filter: Option<Box<Expression>>,
+    },
+}
+
+impl Expression {
+    /// Returns the value expressions the predicate evaluates, in order.
+    #[must_use]
+    pub fn operands(&self) -> Vec<&ValueExpr> {
+        match self {
+            Expression::Comparison { left, right, .. }
+            | Expression::In {
+                operand: left,
+                list: right,
+                ..
+            } => vec![left, right],
+            Expression::Value(value) | Expression::IsNull { operand: value, .. } => vec![value],
+            Expression::And(left, right) | Expression::Or(left, right) => {
+                let mut operands = left.operands();
+                operands.extend(right.operands());
+                operands
+            }
+            Expression::Not(operand) => operand.operands(),
+            Expression::Exists { pattern, filter } => {
+                let nodes =
+                    std::iter::once(&pattern.start).chain(pattern.hops.iter().map(|h| &h.node));
+                let mut operands: Vec<&ValueExpr> = nodes
+                    .flat_map(|node| &node.property_filters)
+                    .chain(pattern.hops.iter().flat_map(|hop| &hop.rel_filters))
+                    .map(|(_, value)| value)
+                    .collect();
+                operands.extend(filter.iter().flat_map(|filter| filter.operands()));
+                operands
+            }
+        }
+    }
+
+    /// Whether the predicate contains an EXISTS subquery or pattern predicate.
+    #[must_use]
+    pub fn has_subquery(&self) -> bool {
+        match self {
+            Expression::Exists { .. } => true,
+            Expression::And(left, right) | Expression::Or(left, right) => {
+                left.has_subquery() || right.has_subquery()
+            }
+            Expression::Not(operand) => operand.has_subquery(),
+            Expression::Comparison { .. }
+            | Expression::Value(_)
+            | Expression::IsNull { .. }
+            | Expression::In { .. } => false,
+        }
+    }
+}
+
+/// Renders the predicate as Cypher, e.g. `p.age > 30 AND NOT p.active`.
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // AND binds tighter than OR, and NOT tighter than both
+        let write_operand = |f: &mut std::fmt::Formatter<'_>, operand: &Expression, loose: bool| {
+            if loose {
+                write!(f, "({operand})")
+            } else {
+                write!(f, "{operand}")
+            }
+        };
+        match self {
+            Expression::Comparison { left, op, right } => {
+                write!(f, "{left} {} {right}", op.symbol())
+            }
+            Expression::Value(value) => write!(f, "{value}"),
+            Expression::IsNull { operand, negated } => {
+                write!(f, "{operand} IS {}NULL", if *negated { "NOT " } else { "" })
+            }
+            Expression::In {
+                operand,
+                list,
+                negated,
+            } => write!(f, "{operand} {}IN {list}", if *negated { "NOT " } else { "" }),
+            Expression::And(left, right) => {
+                write_operand(f, left, matches!(**left, Expression::Or(..)))?;
+                write!(f, " AND ")?;
+                write_operand(f, right, matches!(**right, Expression::Or(..)))
+            }
+            Expression::Or(left, right) => write!(f, "{left} OR {right}"),
+            Expression::Not(operand) => {
+                write!(f, "NOT ")?;
+                write_operand(
+                    f,
+                    operand,
+                    matches!(**operand, Expression::And(..) | Expression::Or(..)),
+                )
+            }
+            Expression::Exists { pattern, filter } => {
+                write!(f, "EXISTS {{ MATCH {pattern}")?;
+                if let Some(filter) = filter {
+                    write!(f, " WHERE {filter}")?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
 }
 
 /// Comparison operators.
@@ -210,10 +1023,20 @@ pub enum ComparisonOp {
     Gte,
     Lte,
     Neq,
+    /// `STARTS WITH`.
+    StartsWith,
+    /// `ENDS WITH`.
+    EndsWith,
+    /// `CONTAINS`.
+    Contains,
+    /// `=~`: the string matches a regular expression in full.
+    RegexMatch,
 }
 
 impl ComparisonOp {
     /// Parses a comparison operator from a string.
+    ///
+    /// Keyword operators are case-insensitive, e.g. `starts  with`.
     #[must_use]
     pub fn parse(s: &str) -> Option<Self> {
         match s {
@@ -223,7 +1046,30 @@ impl ComparisonOp {
             ">=" => Some(ComparisonOp::Gte),
             "<=" => Some(ComparisonOp::Lte),
             "<>" => Some(ComparisonOp::Neq),
-            _ => None,
+            "=~" => Some(ComparisonOp::RegexMatch),
+            _ => match s.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase().as_str() {
+                "STARTS WITH" => Some(ComparisonOp::StartsWith),
+                "ENDS WITH" => Some(ComparisonOp::EndsWith),
+                "CONTAINS" => Some(ComparisonOp::Contains),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the operator as written in Cypher.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ComparisonOp::Gt => ">",
+            ComparisonOp::Lt => "<",
+            ComparisonOp::Eq => "=",
+            ComparisonOp::Gte => ">=",
+            ComparisonOp::Lte => "<=",
+            ComparisonOp::Neq => "<>",
+            ComparisonOp::StartsWith => "STARTS WITH",
+            ComparisonOp::EndsWith => "ENDS WITH",
+            ComparisonOp::Contains => "CONTAINS",
+            ComparisonOp::RegexMatch => "=~",
         }
     }
 }
@@ -4,7 +4,8 @@ use pest::Parser;
 use pest_derive::Parser;
 
 use crate::error::{Result, RuzuError};
-use crate::parser::ast::{AstAggregateFunction, ComparisonOp, CopyOptions, Expression, Literal, NodeFilter, OrderByItem, ReturnItem, Statement};
+use crate::parser::ast::{AggregateExpr, ArithmeticOp, AstAggregateFunction, CaseExpr, CaseWhen, Clause, ComparisonOp, CopyOptions, Expression, Literal, MatchPattern, NodeFilter, OrderByItem, PathPattern, PatternHop, Projection, RelDirection, ProjectionItem, ReturnItem, RowCount, SetItem, SingleQuery, Statement, ValueExpr};
+use crate::types::temporal;
 
 #[derive(Parser)]
 #[grammar = "parser/grammar.pest"]
@@ -57,7 +58,14 @@ fn build_statement(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
             Rule::create_rel_table => return Ok(build_create_rel_table(inner)),
             Rule::create_node => return build_create_node(inner),
             Rule::match_create => return build_match_create(inner),
+            Rule::union_query => return build_union_query(inner),
             Rule::match_query => return build_match_query(inner),
+            Rule::pipeline_query => return build_pipeline_query(inner),
+            Rule::match_set => return build_match_set(inner),
+            Rule::merge_node => return build_merge_node(inner),
+            Rule::unwind_write => return build_unwind_write(inner),
+            Rule::match_merge => return build_match_merge(inner),
+            Rule::match_delete => return build_match_delete(inner),
             _ => {}
         }
     }
@@ -168,10 +176,8 @@ fn build_create_node_table(pair: pest::iterators::Pair<Rule>) -> Statement {
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
-            Rule::identifier => {
-                if table_name.is_empty() {
-                    table_name = inner.as_str().to_string();
-                }
+            Rule::identifier if table_name.is_empty() => {
+                table_name = inner.as_str().to_string();
             }
             Rule::column_list => {
                 for col_pair in inner.into_inner() {
@@ -213,10 +219,8 @@ fn build_create_rel_table(pair: pest::iterators::Pair<Rule>) -> Statement {
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
-            Rule::identifier => {
-                if table_name.is_empty() {
-                    table_name = inner.as_str().to_string();
-                }
+            Rule::identifier if table_name.is_empty() => {
+                table_name = inner.as_str().to_string();
             }
             Rule::from_to_clause => {
                 let mut idents = inner.into_inner();
@@ -369,7 +373,7 @@ fn build_match_create(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
 fn build_node_filter(pair: pest::iterators::Pair<Rule>) -> Result<NodeFilter> {
     let mut var = String::new();
     let mut label = String::new();
-    let mut property_filter = None;
+    let mut property_filters = Vec::new();
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
@@ -380,17 +384,7 @@ fn build_node_filter(pair: pest::iterators::Pair<Rule>) -> Result<NodeFilter> {
                     label = inner.as_str().to_string();
                 }
             }
-            Rule::property_filter => {
-                for filter_inner in inner.into_inner() {
-                    if filter_inner.as_rule() == Rule::property_key_value {
-                        let mut parts = filter_inner.into_inner();
-                        let key = parts.next().unwrap().as_str().to_string();
-                        let lit_pair = parts.next().unwrap();
-                        let value = build_literal(lit_pair)?;
-                        property_filter = Some((key, value));
-                    }
-                }
-            }
+            Rule::property_filter => property_filters = build_property_filters(inner)?,
             _ => {}
         }
     }
@@ -398,19 +392,40 @@ fn build_node_filter(pair: pest::iterators::Pair<Rule>) -> Result<NodeFilter> {
     Ok(NodeFilter {
         var,
         label,
-        property_filter,
+        property_filters,
     })
 }
 
+/// Builds the `key: value` pairs of an inline `{key: value, ...}` property
+/// filter.
+fn build_property_filters(pair: pest::iterators::Pair<Rule>) -> Result<Vec<(String, ValueExpr)>> {
+    pair.into_inner()
+        .map(|property| {
+            let mut parts = property.into_inner();
+            let key = parts.next().unwrap().as_str().to_string();
+            let value = build_value_expr(parts.next().unwrap())?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
 /// Extracts an integer literal from a clause pair (used for SKIP and LIMIT).
-fn parse_integer_clause(pair: pest::iterators::Pair<Rule>, name: &str) -> Result<i64> {
+fn parse_row_count_clause(pair: pest::iterators::Pair<Rule>, name: &str) -> Result<RowCount> {
     for inner in pair.into_inner() {
-        if inner.as_rule() == Rule::integer_literal {
-            return inner.as_str().parse().map_err(|_| RuzuError::ParseError {
-                line: 0,
-                col: 0,
-                message: format!("Invalid {name} value"),
-            });
+        match inner.as_rule() {
+            Rule::integer_literal => {
+                let count = inner.as_str().parse().map_err(|_| RuzuError::ParseError {
+                    line: 0,
+                    col: 0,
+                    message: format!("Invalid {name} value"),
+                })?;
+                return Ok(RowCount::Count(count));
+            }
+            Rule::parameter => {
+                let parameter = inner.into_inner().next().unwrap().as_str();
+                return Ok(RowCount::Parameter(parameter.to_string()));
+            }
+            _ => {}
         }
     }
     Err(RuzuError::ParseError {
@@ -426,7 +441,9 @@ struct RelPattern {
     dst_node: Option<NodeFilter>,
     rel_var: Option<String>,
     rel_type: String,
+    rel_filters: Vec<(String, ValueExpr)>,
     path_bounds: Option<(u32, u32)>,
+    direction: RelDirection,
 }
 
 /// Parses a `match_rel_pattern` pair into its component parts.
@@ -436,7 +453,9 @@ fn build_rel_pattern(pair: pest::iterators::Pair<Rule>) -> Result<RelPattern> {
         dst_node: None,
         rel_var: None,
         rel_type: String::new(),
+        rel_filters: Vec::new(),
         path_bounds: None,
+        direction: RelDirection::Forward,
     };
 
     for rel_inner in pair.into_inner() {
@@ -449,32 +468,79 @@ fn build_rel_pattern(pair: pest::iterators::Pair<Rule>) -> Result<RelPattern> {
                     result.dst_node = Some(node_filter);
                 }
             }
-            Rule::match_rel_type => {
-                for type_inner in rel_inner.into_inner() {
-                    match type_inner.as_rule() {
-                        Rule::identifier => {
-                            if result.rel_type.is_empty() {
-                                result.rel_type = type_inner.as_str().to_string();
-                            } else {
-                                result.rel_var = Some(std::mem::take(&mut result.rel_type));
-                                result.rel_type = type_inner.as_str().to_string();
-                            }
-                        }
-                        Rule::path_length => {
-                            result.path_bounds = Some(build_path_length(type_inner)?);
-                        }
-                        _ => {}
+            Rule::rel_segment => {
+                let segment = build_rel_segment(rel_inner)?;
+                result.rel_var = segment.rel_var;
+                result.rel_type = segment.rel_type;
+                result.rel_filters = segment.rel_filters;
+                result.path_bounds = segment.path_bounds;
+                result.direction = segment.direction;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parsed components of a relationship between two pattern nodes.
+struct RelSegment {
+    rel_var: Option<String>,
+    rel_type: String,
+    rel_filters: Vec<(String, ValueExpr)>,
+    path_bounds: Option<(u32, u32)>,
+    direction: RelDirection,
+}
+
+/// Parses a `rel_segment` pair such as `<-[r:REL_TYPE*1..3]-` or
+/// `-[r:REL_TYPE {key: value}]->`.
+fn build_rel_segment(pair: pest::iterators::Pair<Rule>) -> Result<RelSegment> {
+    let arrow = pair.into_inner().next().unwrap();
+    let mut result = RelSegment {
+        rel_var: None,
+        rel_type: String::new(),
+        rel_filters: Vec::new(),
+        path_bounds: None,
+        direction: match arrow.as_rule() {
+            Rule::backward_rel => RelDirection::Backward,
+            Rule::undirected_rel => RelDirection::Both,
+            _ => RelDirection::Forward,
+        },
+    };
+
+    for rel_type in arrow.into_inner() {
+        for type_inner in rel_type.into_inner() {
+            match type_inner.as_rule() {
+                Rule::identifier => {
+                    if result.rel_type.is_empty() {
+                        result.rel_type = type_inner.as_str().to_string();
+                    } else {
+                        result.rel_var = Some(std::mem::take(&mut result.rel_type));
+                        result.rel_type = type_inner.as_str().to_string();
                     }
                 }
+                Rule::path_length => {
+                    result.path_bounds = Some(build_path_length(type_inner)?);
+                }
+                Rule::property_filter => result.rel_filters = build_property_filters(type_inner)?,
+                _ => {}
             }
-            _ => {}
         }
     }
 
+    if result.path_bounds.is_some() && !result.rel_filters.is_empty() {
+        return Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Property filters on variable-length relationships are not supported".into(),
+        });
+    }
+
     Ok(result)
 }
 
 fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let source = pair.as_str();
     let mut var = String::new();
     let mut label = String::new();
     let mut filter = None;
@@ -484,19 +550,17 @@ fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
     let mut limit = None;
 
     // Check if this is a relationship match or a simple node match
-    let mut is_rel_match = false;
+    let is_rel_match = pair
+        .clone()
+        .into_inner()
+        .any(|inner| inner.as_rule() == Rule::match_rel_pattern);
     let mut src_node = None;
     let mut dst_node = None;
     let mut rel_var = None;
     let mut rel_type = String::new();
+    let mut rel_filters = Vec::new();
     let mut path_bounds = None;
-
-    for inner in pair.clone().into_inner() {
-        if inner.as_rule() == Rule::match_rel_pattern {
-            is_rel_match = true;
-            break;
-        }
-    }
+    let mut direction = RelDirection::Forward;
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
@@ -511,7 +575,9 @@ fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
                 dst_node = rp.dst_node;
                 rel_var = rp.rel_var;
                 rel_type = rp.rel_type;
+                rel_filters = rp.rel_filters;
                 path_bounds = rp.path_bounds;
+                direction = rp.direction;
             }
             Rule::where_clause => {
                 for where_inner in inner.into_inner() {
@@ -528,18 +594,23 @@ fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
                 }
             }
             Rule::order_by_clause => {
-                order_by = Some(build_order_by_clause(inner));
+                order_by = Some(build_order_by_clause(inner)?);
             }
             Rule::skip_clause => {
-                skip = Some(parse_integer_clause(inner, "SKIP")?);
+                skip = Some(parse_row_count_clause(inner, "SKIP")?);
             }
             Rule::limit_clause => {
-                limit = Some(parse_integer_clause(inner, "LIMIT")?);
+                limit = Some(parse_row_count_clause(inner, "LIMIT")?);
             }
             _ => {}
         }
     }
 
+    // EXISTS needs the bound variables of the multi-stage executor
+    if filter.as_ref().is_some_and(Expression::has_subquery) {
+        return build_as_pipeline_query(source);
+    }
+
     if is_rel_match {
         Ok(Statement::MatchRel {
             src_node: src_node.ok_or_else(|| RuzuError::ParseError {
@@ -549,6 +620,7 @@ fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
             })?,
             rel_var,
             rel_type,
+            rel_filters,
             dst_node: dst_node.ok_or_else(|| RuzuError::ParseError {
                 line: 0,
                 col: 0,
@@ -560,6 +632,7 @@ fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
             skip,
             limit,
             path_bounds,
+            direction,
         })
     } else {
         Ok(Statement::Match {
@@ -574,6 +647,577 @@ fn build_match_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
     }
 }
 
+/// Parses a single-stage MATCH statement again as a multi-stage query.
+fn build_as_pipeline_query(source: &str) -> Result<Statement> {
+    let pair = CypherParser::parse(Rule::pipeline_query, source)
+        .ok()
+        .and_then(|mut pairs| pairs.next())
+        .filter(|pair| pair.as_str().len() == source.len())
+        .ok_or_else(|| RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "EXISTS is not supported in this query".into(),
+        })?;
+    build_pipeline_query(pair)
+}
+
+fn build_pipeline_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut clauses = Vec::new();
+    let mut projection = Projection {
+        distinct: false,
+        star: false,
+        items: Vec::new(),
+        filter: None,
+        order_by: None,
+        skip: None,
+        limit: None,
+    };
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::match_clause | Rule::optional_match_clause => {
+                let optional = inner.as_rule() == Rule::optional_match_clause;
+                let mut patterns = Vec::new();
+                let mut filter = None;
+                for clause_inner in inner.into_inner() {
+                    match clause_inner.as_rule() {
+                        Rule::query_pattern => patterns.push(build_query_pattern(clause_inner)?),
+                        Rule::where_clause => {
+                            for where_inner in clause_inner.into_inner() {
+                                if where_inner.as_rule() == Rule::expression {
+                                    filter = Some(build_expression(where_inner)?);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if patterns.is_empty() {
+                    return Err(RuzuError::ParseError {
+                        line: 0,
+                        col: 0,
+                        message: "Missing pattern in MATCH".into(),
+                    });
+                }
+                clauses.push(Clause::Match {
+                    patterns,
+                    filter,
+                    optional,
+                });
+            }
+            Rule::with_clause => clauses.push(Clause::With(build_projection(inner)?)),
+            Rule::unwind_clause => {
+                let mut parts = inner.into_inner();
+                let list = build_value_expr(parts.next().unwrap())?;
+                let alias = parts.next().unwrap().as_str().to_string();
+                clauses.push(Clause::Unwind { list, alias });
+            }
+            Rule::projection_body => build_projection_body(inner, &mut projection)?,
+            Rule::order_by_clause => projection.order_by = Some(build_order_by_clause(inner)?),
+            Rule::skip_clause => projection.skip = Some(parse_row_count_clause(inner, "SKIP")?),
+            Rule::limit_clause => projection.limit = Some(parse_row_count_clause(inner, "LIMIT")?),
+            _ => {}
+        }
+    }
+
+    Ok(Statement::Query { clauses, projection })
+}
+
+fn build_union_query(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut queries = Vec::new();
+    let mut all = None;
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::pipeline_query => {
+                if let Statement::Query { clauses, projection } = build_pipeline_query(inner)? {
+                    queries.push(SingleQuery { clauses, projection });
+                }
+            }
+            Rule::union_op => {
+                let op_all = inner.into_inner().next().is_some();
+                if all.is_some_and(|all| all != op_all) {
+                    return Err(RuzuError::ParseError {
+                        line: 0,
+                        col: 0,
+                        message: "Cannot mix UNION and UNION ALL in one query".into(),
+                    });
+                }
+                all = Some(op_all);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Statement::Union {
+        queries,
+        all: all.unwrap_or(false),
+    })
+}
+
+/// Builds the path pattern of a pipeline MATCH clause from a `query_pattern` pair.
+fn build_query_pattern(pair: pest::iterators::Pair<Rule>) -> Result<PathPattern> {
+    let mut start = None;
+    let mut hops = Vec::new();
+    // Relationship awaiting the node it leads to
+    let mut pending_rel: Option<RelSegment> = None;
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::query_node | Rule::subquery_node => {
+                let node = build_query_node(inner)?;
+                match pending_rel.take() {
+                    Some(segment) => hops.push(PatternHop {
+                        rel_var: segment.rel_var,
+                        rel_type: segment.rel_type,
+                        rel_filters: segment.rel_filters,
+                        direction: segment.direction,
+                        node,
+                    }),
+                    None => start = Some(node),
+                }
+            }
+            Rule::rel_segment => {
+                let segment = build_rel_segment(inner)?;
+                if segment.path_bounds.is_some() {
+                    return Err(RuzuError::ParseError {
+                        line: 0,
+                        col: 0,
+                        message: "Variable-length relationship patterns are not supported \
+                                  in multi-clause queries"
+                            .into(),
+                    });
+                }
+                pending_rel = Some(segment);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(PathPattern {
+        start: start.ok_or_else(|| RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Missing node in MATCH pattern".into(),
+        })?,
+        hops,
+    })
+}
+
+/// Builds a node of a pipeline MATCH pattern from a `query_node` or
+/// `subquery_node` pair.
+///
+/// An anonymous node is bound to an internal variable named after its
+/// position in the query, so that nested patterns never share one.
+fn build_query_node(pair: pest::iterators::Pair<Rule>) -> Result<NodeFilter> {
+    let mut node = NodeFilter {
+        var: format!("#node{}", pair.as_span().start()),
+        label: String::new(),
+        property_filters: Vec::new(),
+    };
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::identifier => node.var = inner.as_str().to_string(),
+            Rule::node_label => node.label = inner.into_inner().as_str().to_string(),
+            Rule::query_property_filter => node.property_filters = build_property_filters(inner)?,
+            _ => {}
+        }
+    }
+    if node.is_anonymous() && node.label.is_empty() {
+        return Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Anonymous node in a pattern needs a label".into(),
+        });
+    }
+    Ok(node)
+}
+
+/// Builds a WITH projection from a `with_clause` pair.
+fn build_projection(pair: pest::iterators::Pair<Rule>) -> Result<Projection> {
+    let mut projection = Projection {
+        distinct: false,
+        star: false,
+        items: Vec::new(),
+        filter: None,
+        order_by: None,
+        skip: None,
+        limit: None,
+    };
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::projection_body => build_projection_body(inner, &mut projection)?,
+            Rule::where_clause => {
+                for where_inner in inner.into_inner() {
+                    if where_inner.as_rule() == Rule::expression {
+                        projection.filter = Some(build_expression(where_inner)?);
+                    }
+                }
+            }
+            Rule::order_by_clause => projection.order_by = Some(build_order_by_clause(inner)?),
+            Rule::skip_clause => projection.skip = Some(parse_row_count_clause(inner, "SKIP")?),
+            Rule::limit_clause => projection.limit = Some(parse_row_count_clause(inner, "LIMIT")?),
+            _ => {}
+        }
+    }
+
+    Ok(projection)
+}
+
+/// Fills the DISTINCT flag, `*` and items of a WITH or RETURN projection
+/// from a `projection_body` pair.
+fn build_projection_body(pair: pest::iterators::Pair<Rule>, projection: &mut Projection) -> Result<()> {
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::distinct_kw => projection.distinct = true,
+            Rule::star_projection => {
+                projection.star = true;
+                projection.items = build_projection_items(inner)?;
+            }
+            Rule::projection_item_list => projection.items = build_projection_items(inner)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn build_projection_items(pair: pest::iterators::Pair<Rule>) -> Result<Vec<ProjectionItem>> {
+    let mut items = Vec::new();
+
+    for projection_item in pair.into_inner() {
+        if projection_item.as_rule() == Rule::projection_item {
+            let mut parts = projection_item.into_inner();
+            let value = parts.next().unwrap();
+            let item = if value.as_rule() == Rule::aggregate_expr {
+                build_aggregate_expr(value)?
+            } else {
+                match build_value_expr(value)? {
+                    ValueExpr::Property { var, property } => ReturnItem::projection(var, property),
+                    ValueExpr::Variable(name) => ReturnItem::Variable(name),
+                    expr => ReturnItem::Expression(expr),
+                }
+            };
+            let alias = parts.next().map(|alias| alias.as_str().to_string());
+            items.push(ProjectionItem { item, alias });
+        }
+    }
+
+    Ok(items)
+}
+
+/// Builds the pattern of an updating MATCH statement from a
+/// `match_rel_pattern` or `match_node_with_filter` pair.
+fn build_match_pattern(pair: pest::iterators::Pair<Rule>) -> Result<MatchPattern> {
+    if pair.as_rule() == Rule::match_node_with_filter {
+        return Ok(MatchPattern::Node(build_node_filter_with_optional_props(pair)?));
+    }
+
+    let rp = build_rel_pattern(pair)?;
+    if rp.path_bounds.is_some() {
+        return Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Variable-length relationship patterns cannot be updated".into(),
+        });
+    }
+
+    Ok(MatchPattern::Rel {
+        src_node: rp.src_node.ok_or_else(|| RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Missing source node in relationship match".into(),
+        })?,
+        rel_var: rp.rel_var,
+        rel_type: rp.rel_type,
+        rel_filters: rp.rel_filters,
+        dst_node: rp.dst_node.ok_or_else(|| RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Missing destination node in relationship match".into(),
+        })?,
+        direction: rp.direction,
+    })
+}
+
+fn build_match_set(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut pattern = None;
+    let mut filter = None;
+    let mut items = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::match_rel_pattern | Rule::match_node_with_filter => {
+                pattern = Some(build_match_pattern(inner)?);
+            }
+            Rule::where_clause => {
+                for where_inner in inner.into_inner() {
+                    if where_inner.as_rule() == Rule::expression {
+                        filter = Some(build_expression(where_inner)?);
+                    }
+                }
+            }
+            Rule::set_clause => items = build_set_clause(inner)?,
+            _ => {}
+        }
+    }
+
+    Ok(Statement::MatchSet {
+        pattern: pattern.ok_or_else(|| RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Missing pattern in MATCH SET".into(),
+        })?,
+        filter,
+        items,
+    })
+}
+
+fn build_set_clause(pair: pest::iterators::Pair<Rule>) -> Result<Vec<SetItem>> {
+    let mut items = Vec::new();
+
+    for item in pair.into_inner() {
+        if item.as_rule() == Rule::set_item {
+            let mut parts = item.into_inner();
+            let mut proj_parts = parts.next().unwrap().into_inner();
+            let var = proj_parts.next().unwrap().as_str().to_string();
+            let property = proj_parts.next().unwrap().as_str().to_string();
+            let value = build_value_expr(parts.next().unwrap())?;
+            items.push(SetItem { var, property, value });
+        }
+    }
+
+    Ok(items)
+}
+
+/// Builds `(key, literal)` pairs from a `properties` or `rel_properties` pair.
+fn build_property_map(pair: pest::iterators::Pair<Rule>) -> Result<Vec<(String, Literal)>> {
+    let mut properties = Vec::new();
+
+    for prop_list in pair.into_inner() {
+        if prop_list.as_rule() == Rule::property_list {
+            for prop in prop_list.into_inner() {
+                if prop.as_rule() == Rule::property {
+                    let mut parts = prop.into_inner();
+                    let name = parts.next().unwrap().as_str().to_string();
+                    let literal = build_literal(parts.next().unwrap())?;
+                    properties.push((name, literal));
+                }
+            }
+        }
+    }
+
+    Ok(properties)
+}
+
+/// Splits `merge_action` pairs into (ON CREATE, ON MATCH) assignments.
+fn build_merge_action(
+    pair: pest::iterators::Pair<Rule>,
+    on_create: &mut Vec<SetItem>,
+    on_match: &mut Vec<SetItem>,
+) -> Result<()> {
+    let mut is_create = false;
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::on_create => is_create = true,
+            Rule::on_match => is_create = false,
+            Rule::set_clause => {
+                let items = build_set_clause(inner)?;
+                if is_create {
+                    on_create.extend(items);
+                } else {
+                    on_match.extend(items);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn build_merge_node(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut var = String::new();
+    let mut label = String::new();
+    let mut properties = Vec::new();
+    let mut on_create = Vec::new();
+    let mut on_match = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::merge_node_pattern => {
+                for node_inner in inner.into_inner() {
+                    match node_inner.as_rule() {
+                        Rule::identifier if var.is_empty() => {
+                            var = node_inner.as_str().to_string();
+                        }
+                        Rule::identifier => label = node_inner.as_str().to_string(),
+                        Rule::properties => properties = build_property_map(node_inner)?,
+                        _ => {}
+                    }
+                }
+            }
+            Rule::merge_action => build_merge_action(inner, &mut on_create, &mut on_match)?,
+            _ => {}
+        }
+    }
+
+    Ok(Statement::Merge {
+        var,
+        label,
+        properties,
+        on_create,
+        on_match,
+    })
+}
+
+/// Builds `UNWIND list AS alias` followed by CREATE or MERGE of a node.
+fn build_unwind_write(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut inner = pair.into_inner();
+    let mut unwind = inner.next().unwrap().into_inner();
+    let list = build_value_expr(unwind.next().unwrap())?;
+    let alias = unwind.next().unwrap().as_str().to_string();
+
+    let write = inner.next().unwrap();
+    let merge = write.as_rule() == Rule::unwind_merge;
+    let mut node = None;
+    let mut on_create = Vec::new();
+    let mut on_match = Vec::new();
+    for write_inner in write.into_inner() {
+        match write_inner.as_rule() {
+            Rule::subquery_node => node = Some(build_query_node(write_inner)?),
+            Rule::merge_action => build_merge_action(write_inner, &mut on_create, &mut on_match)?,
+            _ => {}
+        }
+    }
+    let node = node.unwrap();
+    if node.label.is_empty() {
+        return Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: format!("Node {node} after UNWIND needs a label"),
+        });
+    }
+
+    Ok(Statement::UnwindWrite {
+        list,
+        alias,
+        node,
+        merge,
+        on_create,
+        on_match,
+    })
+}
+
+fn build_match_merge(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut match_filters: Vec<NodeFilter> = Vec::new();
+    let mut rel_var = None;
+    let mut rel_type = String::new();
+    let mut rel_props = Vec::new();
+    let mut src_var = String::new();
+    let mut dst_var = String::new();
+    let mut on_create = Vec::new();
+    let mut on_match = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::match_node_filter => match_filters.push(build_node_filter(inner)?),
+            Rule::merge_rel_pattern => {
+                for rel_inner in inner.into_inner() {
+                    match rel_inner.as_rule() {
+                        Rule::identifier if src_var.is_empty() => {
+                            src_var = rel_inner.as_str().to_string();
+                        }
+                        Rule::identifier => dst_var = rel_inner.as_str().to_string(),
+                        Rule::merge_rel_type => {
+                            for type_inner in rel_inner.into_inner() {
+                                match type_inner.as_rule() {
+                                    Rule::rel_variable => {
+                                        rel_var = Some(type_inner.as_str().to_string());
+                                    }
+                                    Rule::identifier => rel_type = type_inner.as_str().to_string(),
+                                    Rule::rel_properties => {
+                                        rel_props = build_property_map(type_inner)?;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Rule::merge_action => build_merge_action(inner, &mut on_create, &mut on_match)?,
+            _ => {}
+        }
+    }
+
+    let mut match_filters = match_filters.into_iter();
+    let (Some(src_node), Some(dst_node)) = (match_filters.next(), match_filters.next()) else {
+        return Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "MATCH MERGE requires two node patterns".into(),
+        });
+    };
+
+    Ok(Statement::MatchMerge {
+        src_node,
+        dst_node,
+        rel_var,
+        rel_type,
+        rel_props,
+        src_var,
+        dst_var,
+        on_create,
+        on_match,
+    })
+}
+
+fn build_match_delete(pair: pest::iterators::Pair<Rule>) -> Result<Statement> {
+    let mut pattern = None;
+    let mut filter = None;
+    let mut vars = Vec::new();
+    let mut detach = false;
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::match_rel_pattern | Rule::match_node_with_filter => {
+                pattern = Some(build_match_pattern(inner)?);
+            }
+            Rule::where_clause => {
+                for where_inner in inner.into_inner() {
+                    if where_inner.as_rule() == Rule::expression {
+                        filter = Some(build_expression(where_inner)?);
+                    }
+                }
+            }
+            Rule::delete_clause => {
+                for item in inner.into_inner() {
+                    match item.as_rule() {
+                        Rule::detach_keyword => detach = true,
+                        Rule::identifier => vars.push(item.as_str().to_string()),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Statement::MatchDelete {
+        pattern: pattern.ok_or_else(|| RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Missing pattern in MATCH DELETE".into(),
+        })?,
+        filter,
+        vars,
+        detach,
+    })
+}
+
 fn build_return_item_list(pair: pest::iterators::Pair<Rule>) -> Result<Vec<ReturnItem>> {
     let mut items = Vec::new();
 
@@ -601,7 +1245,8 @@ fn build_return_item_list(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Retur
 
 fn build_aggregate_expr(pair: pest::iterators::Pair<Rule>) -> Result<ReturnItem> {
     let mut func: Option<AstAggregateFunction> = None;
-    let mut input: Option<(String, String)> = None;
+    let mut distinct = false;
+    let mut arguments = Vec::new();
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
@@ -617,43 +1262,58 @@ fn build_aggregate_expr(pair: pest::iterators::Pair<Rule>) -> Result<ReturnItem>
                     message: format!("Unknown aggregate function: {func_name}"),
                 })?);
             }
-            Rule::projection => {
-                let mut parts = inner.into_inner();
-                let var = parts.next().unwrap().as_str().to_string();
-                let prop = parts.next().unwrap().as_str().to_string();
-                input = Some((var, prop));
-            }
+            Rule::distinct_kw => distinct = true,
+            Rule::value_expr => arguments.push(build_value_expr(inner)?),
             _ => {}
         }
     }
 
-    match func {
-        Some(f) => Ok(ReturnItem::aggregate(f, input)),
-        None => Err(RuzuError::ParseError {
+    let Some(function) = func else {
+        return Err(RuzuError::ParseError {
             line: 0,
             col: 0,
             message: "Invalid aggregate expression: missing function".into(),
-        }),
+        });
+    };
+    let mut arguments = arguments.into_iter();
+    let input = arguments.next();
+    let percentile = arguments.next();
+    if function.takes_percentile() != percentile.is_some() {
+        let expected = if function.takes_percentile() { 2 } else { 1 };
+        return Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: format!("{} takes {expected} argument(s)", function.name()),
+        });
     }
+
+    Ok(ReturnItem::Aggregate(AggregateExpr {
+        function,
+        input,
+        distinct,
+        percentile,
+    }))
 }
 
-fn build_order_by_clause(pair: pest::iterators::Pair<Rule>) -> Vec<OrderByItem> {
+fn build_order_by_clause(pair: pest::iterators::Pair<Rule>) -> Result<Vec<OrderByItem>> {
     let mut items = Vec::new();
 
     for inner in pair.into_inner() {
         if inner.as_rule() == Rule::order_by_item_list {
             for order_item in inner.into_inner() {
                 if order_item.as_rule() == Rule::order_by_item {
-                    let mut var = String::new();
-                    let mut property = String::new();
+                    let mut expr = None;
                     let mut ascending = true; // Default to ASC
 
                     for item_inner in order_item.into_inner() {
                         match item_inner.as_rule() {
-                            Rule::projection => {
-                                let mut parts = item_inner.into_inner();
-                                var = parts.next().unwrap().as_str().to_string();
-                                property = parts.next().unwrap().as_str().to_string();
+                            Rule::value_expr => {
+                                expr = Some(build_value_expr(item_inner)?);
+                            }
+                            Rule::aggregate_expr => {
+                                // Named like its output column, which the binder resolves
+                                let column = build_aggregate_expr(item_inner)?.column_name();
+                                expr = Some(ValueExpr::Variable(column));
                             }
                             Rule::order_direction => {
                                 ascending = item_inner.as_str().to_uppercase() == "ASC";
@@ -662,13 +1322,15 @@ fn build_order_by_clause(pair: pest::iterators::Pair<Rule>) -> Vec<OrderByItem>
                         }
                     }
 
-                    items.push(OrderByItem { var, property, ascending });
+                    if let Some(expr) = expr {
+                        items.push(OrderByItem { expr, ascending });
+                    }
                 }
             }
         }
     }
 
-    items
+    Ok(items)
 }
 
 fn build_path_length(pair: pest::iterators::Pair<Rule>) -> Result<(u32, u32)> {
@@ -706,7 +1368,7 @@ fn build_path_length(pair: pest::iterators::Pair<Rule>) -> Result<(u32, u32)> {
 fn build_node_filter_with_optional_props(pair: pest::iterators::Pair<Rule>) -> Result<NodeFilter> {
     let mut var = String::new();
     let mut label = String::new();
-    let mut property_filter = None;
+    let mut property_filters = Vec::new();
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
@@ -717,17 +1379,7 @@ fn build_node_filter_with_optional_props(pair: pest::iterators::Pair<Rule>) -> R
                     label = inner.as_str().to_string();
                 }
             }
-            Rule::property_filter => {
-                for filter_inner in inner.into_inner() {
-                    if filter_inner.as_rule() == Rule::property_key_value {
-                        let mut parts = filter_inner.into_inner();
-                        let key = parts.next().unwrap().as_str().to_string();
-                        let lit_pair = parts.next().unwrap();
-                        let value = build_literal(lit_pair)?;
-                        property_filter = Some((key, value));
-                    }
-                }
-            }
+            Rule::property_filter => property_filters = build_property_filters(inner)?,
             _ => {}
         }
     }
@@ -735,19 +1387,89 @@ fn build_node_filter_with_optional_props(pair: pest::iterators::Pair<Rule>) -> R
     Ok(NodeFilter {
         var,
         label,
-        property_filter,
+        property_filters,
     })
 }
 
 fn build_expression(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
-    for inner in pair.into_inner() {
-        if inner.as_rule() == Rule::comparison {
-            let mut parts = inner.into_inner();
+    match pair.as_rule() {
+        // Left-associative chains: operand (op operand)*
+        Rule::expression | Rule::and_expr => {
+            let mut parts = pair.into_inner();
+            let mut left = build_expression(parts.next().unwrap())?;
+            while let (Some(op_pair), Some(right_pair)) = (parts.next(), parts.next()) {
+                let right = Box::new(build_expression(right_pair)?);
+                left = if op_pair.as_rule() == Rule::or_op {
+                    Expression::Or(Box::new(left), right)
+                } else {
+                    Expression::And(Box::new(left), right)
+                };
+            }
+            Ok(left)
+        }
+        Rule::not_expr => {
+            let mut parts: Vec<_> = pair.into_inner().collect();
+            let mut expr = build_expression(parts.pop().unwrap())?;
+            for _ in parts {
+                expr = Expression::Not(Box::new(expr));
+            }
+            Ok(expr)
+        }
+        Rule::predicate => {
+            let inner = pair.into_inner().next().unwrap();
+            match inner.as_rule() {
+                Rule::value_expr => Ok(Expression::Value(build_value_expr(inner)?)),
+                _ => build_expression(inner),
+            }
+        }
+        Rule::exists_subquery => {
+            let mut pattern = None;
+            let mut filter = None;
+            for inner in pair.into_inner() {
+                match inner.as_rule() {
+                    Rule::subquery_pattern => pattern = Some(build_query_pattern(inner)?),
+                    Rule::where_clause => {
+                        let expr = inner.into_inner().next().unwrap();
+                        filter = Some(Box::new(build_expression(expr)?));
+                    }
+                    _ => {}
+                }
+            }
+            let pattern = pattern.ok_or_else(|| RuzuError::ParseError {
+                line: 0,
+                col: 0,
+                message: "Missing pattern in EXISTS".into(),
+            })?;
+            Ok(Expression::Exists {
+                pattern: Box::new(pattern),
+                filter,
+            })
+        }
+        Rule::pattern_predicate => Ok(Expression::Exists {
+            pattern: Box::new(build_query_pattern(pair)?),
+            filter: None,
+        }),
+        Rule::null_check => {
+            let mut parts = pair.into_inner();
+            let operand = build_value_expr(parts.next().unwrap())?;
+            let negated = parts.any(|part| part.as_rule() == Rule::not_op);
+            Ok(Expression::IsNull { operand, negated })
+        }
+        Rule::in_check => {
+            let mut parts = pair.into_inner();
+            let operand = build_value_expr(parts.next().unwrap())?;
+            let negated = parts.peek().is_some_and(|part| part.as_rule() == Rule::not_op);
+            let list = build_value_expr(parts.last().unwrap())?;
+            Ok(Expression::In {
+                operand,
+                list,
+                negated,
+            })
+        }
+        Rule::comparison => {
+            let mut parts = pair.into_inner();
 
-            let projection = parts.next().unwrap();
-            let mut proj_parts = projection.into_inner();
-            let var = proj_parts.next().unwrap().as_str().to_string();
-            let property = proj_parts.next().unwrap().as_str().to_string();
+            let left = build_value_expr(parts.next().unwrap())?;
 
             let op_str = parts.next().unwrap().as_str();
             let op = ComparisonOp::parse(op_str).ok_or_else(|| RuzuError::ParseError {
@@ -756,23 +1478,113 @@ fn build_expression(pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
                 message: format!("Unknown operator: {op_str}"),
             })?;
 
-            let lit_pair = parts.next().unwrap();
-            let value = build_literal(lit_pair)?;
+            let right = build_value_expr(parts.next().unwrap())?;
 
-            return Ok(Expression {
-                var,
-                property,
-                op,
-                value,
-            });
+            Ok(Expression::Comparison { left, op, right })
         }
+        _ => Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: "Invalid expression".into(),
+        }),
     }
+}
 
-    Err(RuzuError::ParseError {
-        line: 0,
-        col: 0,
-        message: "Invalid expression".into(),
-    })
+fn build_value_expr(pair: pest::iterators::Pair<Rule>) -> Result<ValueExpr> {
+    match pair.as_rule() {
+        // Left-associative chains: operand (op operand)*
+        Rule::value_expr | Rule::value_term => {
+            let mut parts = pair.into_inner();
+            let mut left = build_value_expr(parts.next().unwrap())?;
+            while let (Some(op_pair), Some(right_pair)) = (parts.next(), parts.next()) {
+                let op = ArithmeticOp::parse(op_pair.as_str()).ok_or_else(|| {
+                    RuzuError::ParseError {
+                        line: 0,
+                        col: 0,
+                        message: format!("Unknown operator: {}", op_pair.as_str()),
+                    }
+                })?;
+                left = ValueExpr::Arithmetic {
+                    left: Box::new(left),
+                    op,
+                    right: Box::new(build_value_expr(right_pair)?),
+                };
+            }
+            Ok(left)
+        }
+        // `x.year()` is `year(x)`
+        Rule::value_factor => {
+            let mut parts = pair.into_inner();
+            let mut value = build_value_expr(parts.next().unwrap())?;
+            for accessor in parts {
+                let name = accessor.into_inner().next().unwrap().as_str().to_lowercase();
+                value = ValueExpr::Function {
+                    name,
+                    args: vec![value],
+                };
+            }
+            Ok(value)
+        }
+        Rule::literal => Ok(ValueExpr::Literal(build_literal(pair)?)),
+        Rule::null_kw => Ok(ValueExpr::Literal(Literal::Null)),
+        Rule::negation => Ok(ValueExpr::Negate(Box::new(build_value_expr(
+            pair.into_inner().next().unwrap(),
+        )?))),
+        Rule::case_expr => build_case_expr(pair),
+        Rule::function_call => {
+            let mut parts = pair.into_inner();
+            let name = parts.next().unwrap().as_str().to_string();
+            let args = parts.map(build_value_expr).collect::<Result<_>>()?;
+            Ok(ValueExpr::Function { name, args })
+        }
+        Rule::projection => {
+            let mut parts = pair.into_inner();
+            let var = parts.next().unwrap().as_str().to_string();
+            let property = parts.next().unwrap().as_str().to_string();
+            Ok(ValueExpr::Property { var, property })
+        }
+        Rule::identifier => Ok(ValueExpr::Variable(pair.as_str().to_string())),
+        _ => Err(RuzuError::ParseError {
+            line: 0,
+            col: 0,
+            message: format!("Invalid value expression: {}", pair.as_str()),
+        }),
+    }
+}
+
+fn build_case_expr(pair: pest::iterators::Pair<Rule>) -> Result<ValueExpr> {
+    let mut case = CaseExpr {
+        operand: None,
+        branches: Vec::new(),
+        default: None,
+    };
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::value_expr => case.operand = Some(build_value_expr(inner)?),
+            Rule::simple_case_when | Rule::searched_case_when => {
+                let rule = inner.as_rule();
+                let mut parts = inner.into_inner().filter(|part| {
+                    !matches!(part.as_rule(), Rule::when_kw | Rule::then_kw)
+                });
+                let when = parts.next().unwrap();
+                let when = if rule == Rule::simple_case_when {
+                    CaseWhen::Value(build_value_expr(when)?)
+                } else {
+                    CaseWhen::Condition(build_expression(when)?)
+                };
+                let result = build_value_expr(parts.next().unwrap())?;
+                case.branches.push((when, result));
+            }
+            Rule::case_else => {
+                let default = inner.into_inner().find(|part| part.as_rule() == Rule::value_expr);
+                case.default = default.map(build_value_expr).transpose()?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ValueExpr::Case(Box::new(case)))
 }
 
 fn build_literal(pair: pest::iterators::Pair<Rule>) -> Result<Literal> {
@@ -780,6 +1592,20 @@ fn build_literal(pair: pest::iterators::Pair<Rule>) -> Result<Literal> {
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
+            Rule::parameter => {
+                let name = inner.into_inner().next().unwrap().as_str();
+                return Ok(Literal::Parameter(name.to_string()));
+            }
+            Rule::list_literal => {
+                return inner
+                    .into_inner()
+                    .map(|element| match element.as_rule() {
+                        Rule::null_kw => Ok(Literal::Null),
+                        _ => build_literal(element),
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map(Literal::List);
+            }
             Rule::string_literal => {
                 let s = inner.as_str();
                 let content = &s[1..s.len() - 1];
@@ -812,6 +1638,23 @@ fn build_literal(pair: pest::iterators::Pair<Rule>) -> Result<Literal> {
                 let b = inner.as_str().eq_ignore_ascii_case("true");
                 return Ok(Literal::Bool(b));
             }
+            Rule::temporal_literal => {
+                let (line, col) = inner.line_col();
+                let mut parts = inner.into_inner();
+                let kind = parts.next().unwrap().as_str().to_uppercase();
+                let text = parts.next().unwrap().as_str();
+                let text = &text[1..text.len() - 1];
+                let literal = if kind == "DATE" {
+                    temporal::parse_date(text).map(Literal::Date)
+                } else {
+                    temporal::parse_timestamp(text).map(Literal::Timestamp)
+                };
+                return literal.ok_or_else(|| RuzuError::ParseError {
+                    line,
+                    col,
+                    message: format!("Invalid {kind}: '{text}'"),
+                });
+            }
             _ => {}
         }
     }
@@ -14,9 +14,12 @@ pub use optimizer::{
 };
 pub use physical_plan::PlanMapper;
 
+use std::collections::HashSet;
 use std::fmt::Write;
 
-use crate::binder::{BoundQuery, QueryGraph};
+use crate::binder::{
+    BoundExpression, BoundNode, BoundQuery, BoundRelationship, ComparisonOp, LogicalOp, QueryGraph,
+};
 use crate::catalog::Catalog;
 use crate::error::{Result, RuzuError};
 
@@ -64,7 +67,7 @@ impl<'a> Planner<'a> {
     /// Returns an error if the query graph is empty or cannot be planned.
     pub fn plan(&self, query: &BoundQuery) -> Result<LogicalPlan> {
         // Start with scan operators for each node in the query graph
-        let mut plan = self.plan_query_graph(&query.query_graph)?;
+        let mut plan = self.plan_query_graph(&query.query_graph, query.where_clause.as_ref())?;
 
         // Add WHERE clause filter
         if let Some(ref where_clause) = query.where_clause {
@@ -100,22 +103,76 @@ impl<'a> Planner<'a> {
     }
 
     /// Plans the query graph (MATCH pattern).
-    #[allow(clippy::unused_self)]
-    fn plan_query_graph(&self, graph: &QueryGraph) -> Result<LogicalPlan> {
+    ///
+    /// Each connected component of the graph is scanned from its first node
+    /// and extended along its relationships. Disconnected components (from
+    /// comma-separated patterns) are combined with hash joins on the
+    /// `a.x = b.y` conjuncts of `predicate` that relate them, or on no keys
+    /// (a cartesian product) when there are none.
+    fn plan_query_graph(
+        &self,
+        graph: &QueryGraph,
+        predicate: Option<&BoundExpression>,
+    ) -> Result<LogicalPlan> {
         if graph.nodes.is_empty() {
             return Err(RuzuError::PlanError("Empty query graph".into()));
         }
 
-        // Start with the first node
-        let first_node = &graph.nodes[0];
+        let mut relationships: Vec<&BoundRelationship> = graph.relationships.iter().collect();
+        let mut planned: Option<(LogicalPlan, HashSet<&str>)> = None;
+        for node in &graph.nodes {
+            if planned.as_ref().is_some_and(|(_, vars)| vars.contains(node.variable.as_str())) {
+                continue;
+            }
+            let (component, component_vars) = self.plan_component(node, &mut relationships);
+            planned = Some(match planned {
+                None => (component, component_vars),
+                Some((left, mut vars)) => {
+                    let (left_keys, right_keys) =
+                        predicate.map_or_else(Default::default, |predicate| {
+                            Self::join_keys(predicate, &vars, &component_vars)
+                        });
+                    vars.extend(component_vars);
+                    let plan = LogicalPlan::HashJoin {
+                        left: Box::new(left),
+                        right: Box::new(component),
+                        left_keys,
+                        right_keys,
+                        join_type: JoinType::Inner,
+                    };
+                    (plan, vars)
+                }
+            });
+        }
+
+        planned
+            .map(|(plan, _)| plan)
+            .ok_or_else(|| RuzuError::PlanError("Empty query graph".into()))
+    }
+
+    /// Plans the connected component of `start`: a scan of `start` extended
+    /// along each relationship reaching the component, which are taken out
+    /// of `relationships` in order. Returns the plan and its node variables.
+    #[allow(clippy::unused_self)]
+    fn plan_component<'g>(
+        &self,
+        start: &'g BoundNode,
+        relationships: &mut Vec<&'g BoundRelationship>,
+    ) -> (LogicalPlan, HashSet<&'g str>) {
         let mut plan = LogicalPlan::node_scan(
-            first_node.table_name().to_string(),
-            first_node.variable.clone(),
-            first_node.table_schema.clone(),
+            start.table_name().to_string(),
+            start.variable.clone(),
+            start.table_schema.clone(),
         );
+        let mut vars = HashSet::from([start.variable.as_str()]);
 
         // Add relationships as Extend operations
-        for rel in &graph.relationships {
+        while let Some(index) = relationships.iter().position(|rel| {
+            vars.contains(rel.src_variable.as_str()) || vars.contains(rel.dst_variable.as_str())
+        }) {
+            let rel = relationships.remove(index);
+            vars.insert(&rel.src_variable);
+            vars.insert(&rel.dst_variable);
             plan = LogicalPlan::Extend {
                 input: Box::new(plan),
                 rel_type: rel.rel_type().to_string(),
@@ -142,7 +199,60 @@ impl<'a> Planner<'a> {
             }
         }
 
-        Ok(plan)
+        (plan, vars)
+    }
+
+    /// Collects the `a.x = b.y` conjuncts of `predicate` comparing a property
+    /// of a `left` variable with one of a `right` variable, as the join keys
+    /// (`var.prop` columns) of each side.
+    fn join_keys(
+        predicate: &BoundExpression,
+        left: &HashSet<&str>,
+        right: &HashSet<&str>,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut keys = (Vec::new(), Vec::new());
+        match predicate {
+            BoundExpression::Logical {
+                op: LogicalOp::And,
+                operands,
+                ..
+            } => {
+                for operand in operands {
+                    let (left_keys, right_keys) = Self::join_keys(operand, left, right);
+                    keys.0.extend(left_keys);
+                    keys.1.extend(right_keys);
+                }
+            }
+            BoundExpression::Comparison {
+                left: a,
+                op: ComparisonOp::Eq,
+                right: b,
+                ..
+            } => {
+                if let (
+                    BoundExpression::PropertyAccess {
+                        variable: a_var,
+                        property: a_prop,
+                        ..
+                    },
+                    BoundExpression::PropertyAccess {
+                        variable: b_var,
+                        property: b_prop,
+                        ..
+                    },
+                ) = (a.as_ref(), b.as_ref())
+                {
+                    let (a_key, b_key) = (format!("{a_var}.{a_prop}"), format!("{b_var}.{b_prop}"));
+                    if left.contains(a_var.as_str()) && right.contains(b_var.as_str()) {
+                        keys = (vec![a_key], vec![b_key]);
+                    } else if left.contains(b_var.as_str()) && right.contains(a_var.as_str()) {
+                        keys = (vec![b_key], vec![a_key]);
+                    }
+                }
+            }
+            _ => {}
+        }
+        keys
     }
 
     /// Applies all optimizer rules to the logical plan.
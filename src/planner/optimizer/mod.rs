@@ -83,11 +83,10 @@ fn try_evaluate_constant(expr: &BoundExpression) -> ConstantValue {
                 BoundExpression::Literal { value: right_val, .. },
             ) = (left.as_ref(), right.as_ref())
             {
-                let result = evaluate_comparison(left_val, *op, right_val);
-                if result {
-                    ConstantValue::True
-                } else {
-                    ConstantValue::False
+                match evaluate_comparison(left_val, *op, right_val) {
+                    Some(true) => ConstantValue::True,
+                    Some(false) => ConstantValue::False,
+                    None => ConstantValue::Unknown,
                 }
             } else {
                 ConstantValue::Unknown
@@ -141,9 +140,24 @@ fn try_evaluate_constant(expr: &BoundExpression) -> ConstantValue {
 }
 
 /// Evaluates a comparison between two constant values.
-fn evaluate_comparison(left: &Value, op: ComparisonOp, right: &Value) -> bool {
+///
+/// Returns `None` for regular expression matches, which are left for the
+/// executor to evaluate, and for string predicates on a NULL or non-string
+/// operand, which are unknown rather than false.
+fn evaluate_comparison(left: &Value, op: ComparisonOp, right: &Value) -> Option<bool> {
+    if op.is_string_predicate() {
+        let (Value::String(text), Value::String(pattern)) = (left, right) else {
+            return None;
+        };
+        return match op {
+            ComparisonOp::StartsWith => Some(text.starts_with(pattern.as_str())),
+            ComparisonOp::EndsWith => Some(text.ends_with(pattern.as_str())),
+            ComparisonOp::Contains => Some(text.contains(pattern.as_str())),
+            _ => None,
+        };
+    }
     let ordering = left.compare(right);
-    match ordering {
+    Some(match ordering {
         None => false,
         Some(std::cmp::Ordering::Equal) => {
             matches!(op, ComparisonOp::Eq | ComparisonOp::Lte | ComparisonOp::Gte)
@@ -154,7 +168,7 @@ fn evaluate_comparison(left: &Value, op: ComparisonOp, right: &Value) -> bool {
         Some(std::cmp::Ordering::Greater) => {
             matches!(op, ComparisonOp::Gt | ComparisonOp::Gte | ComparisonOp::Neq)
         }
-    }
+    })
 }
 
 /// Simplifies a predicate expression by removing constant subexpressions.
@@ -228,8 +242,10 @@ fn simplify_predicate(expr: &BoundExpression) -> BoundExpression {
                 BoundExpression::Literal { value: right_val, .. },
             ) = (left.as_ref(), right.as_ref())
             {
-                let result = evaluate_comparison(left_val, *op, right_val);
-                BoundExpression::literal(Value::Bool(result))
+                match evaluate_comparison(left_val, *op, right_val) {
+                    Some(result) => BoundExpression::literal(Value::Bool(result)),
+                    None => expr.clone(),
+                }
             } else {
                 expr.clone()
             }
@@ -313,3 +329,34 @@ impl OptimizerRule for ConstantFoldingRule {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_predicate_on_null_is_not_folded() {
+        for (left, right) in [
+            (Value::Null, Value::String("a".into())),
+            (Value::String("abc".into()), Value::Null),
+            (Value::Int64(1), Value::String("1".into())),
+        ] {
+            let predicate = BoundExpression::not(BoundExpression::comparison(
+                BoundExpression::literal(left),
+                ComparisonOp::StartsWith,
+                BoundExpression::literal(right),
+            ));
+            assert_eq!(try_evaluate_constant(&predicate), ConstantValue::Unknown);
+        }
+    }
+
+    #[test]
+    fn test_string_predicate_on_strings_is_folded() {
+        let predicate = BoundExpression::not(BoundExpression::comparison(
+            BoundExpression::literal(Value::String("abc".into())),
+            ComparisonOp::Contains,
+            BoundExpression::literal(Value::String("b".into())),
+        ));
+        assert_eq!(try_evaluate_constant(&predicate), ConstantValue::False);
+    }
+}
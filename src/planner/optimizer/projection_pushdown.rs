@@ -100,11 +100,17 @@ fn collect_columns_from_expr(expr: &BoundExpression, columns: &mut HashSet<Strin
             columns.insert(variable.clone());
         }
         BoundExpression::Comparison { left, right, .. }
-        | BoundExpression::Arithmetic { left, right, .. } => {
+        | BoundExpression::Arithmetic { left, right, .. }
+        | BoundExpression::InList {
+            operand: left,
+            list: right,
+            ..
+        } => {
             collect_columns_from_expr(left, columns);
             collect_columns_from_expr(right, columns);
         }
-        BoundExpression::Logical { operands, .. } => {
+        BoundExpression::Logical { operands, .. }
+        | BoundExpression::Function { args: operands, .. } => {
             for operand in operands {
                 collect_columns_from_expr(operand, columns);
             }
@@ -117,6 +123,31 @@ fn collect_columns_from_expr(expr: &BoundExpression, columns: &mut HashSet<Strin
         BoundExpression::IsNull { operand, .. } => {
             collect_columns_from_expr(operand, columns);
         }
+        BoundExpression::Case {
+            branches, default, ..
+        } => {
+            for (condition, result) in branches {
+                collect_columns_from_expr(condition, columns);
+                collect_columns_from_expr(result, columns);
+            }
+            if let Some(default) = default {
+                collect_columns_from_expr(default, columns);
+            }
+        }
+        BoundExpression::Exists {
+            query_graph,
+            predicate,
+            ..
+        } => {
+            for node in &query_graph.nodes {
+                for (_, value) in &node.property_filters {
+                    collect_columns_from_expr(value, columns);
+                }
+            }
+            if let Some(predicate) = predicate {
+                collect_columns_from_expr(predicate, columns);
+            }
+        }
         BoundExpression::Literal { .. } => {
             // Literals don't reference columns
         }
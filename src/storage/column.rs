@@ -28,6 +28,15 @@ impl ColumnStorage {
         self.data.get(index)
     }
 
+    /// Replaces the value at `index`, returning the previous value.
+    ///
+    /// Returns `None` (and leaves the column unchanged) if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: Value) -> Option<Value> {
+        self.data
+            .get_mut(index)
+            .map(|slot| std::mem::replace(slot, value))
+    }
+
     /// Returns the number of values in the column.
     #[must_use]
     pub fn len(&self) -> usize {
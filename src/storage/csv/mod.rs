@@ -557,11 +557,7 @@ pub(crate) fn estimate_avg_row_size(data: &[u8]) -> usize {
     let newlines = data[..sample_size]
         .iter()
         .fold(0usize, |n, &b| n + usize::from(b == b'\n'));
-    if newlines > 0 {
-        sample_size / newlines
-    } else {
-        100
-    }
+    sample_size.checked_div(newlines).unwrap_or(100)
 }
 
 #[cfg(test)]
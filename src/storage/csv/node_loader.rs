@@ -9,7 +9,7 @@ use crate::storage::csv::{
     parallel_read_all, shared_interner, CsvImportConfig, CsvParser, ImportError, ImportProgress,
     ImportResult, MmapReader, ProgressCallback, SharedInterner,
 };
-use crate::types::{DataType, Value};
+use crate::types::{temporal, DataType, Value};
 
 /// Minimum file size to use parallel processing (256KB).
 const MIN_PARALLEL_FILE_SIZE: u64 = 256 * 1024;
@@ -123,17 +123,31 @@ impl NodeLoader {
                     Ok(Value::String(field.to_string()))
                 }
             }
-            DataType::Date => {
-                // Simple date parsing (YYYY-MM-DD format)
-                // In a real implementation, we'd use chrono
-                Ok(Value::String(field.to_string()))
-            }
-            DataType::Timestamp => {
-                // Parse as microseconds from epoch or as ISO format string
-                field.parse::<i64>().map(Value::Timestamp).map_err(|e| {
-                    ImportError::column_error(row_num, col_name, format!("Invalid TIMESTAMP: {e}"))
-                })
-            }
+            DataType::Date => temporal::parse_date(field).map(Value::Date).ok_or_else(|| {
+                ImportError::column_error(
+                    row_num,
+                    col_name,
+                    format!("Invalid DATE: {field} (expected YYYY-MM-DD)"),
+                )
+            }),
+            // ISO format, or microseconds since the epoch
+            DataType::Timestamp => field
+                .parse::<i64>()
+                .ok()
+                .or_else(|| temporal::parse_timestamp(field))
+                .map(Value::Timestamp)
+                .ok_or_else(|| {
+                    ImportError::column_error(
+                        row_num,
+                        col_name,
+                        format!("Invalid TIMESTAMP: {field}"),
+                    )
+                }),
+            DataType::List | DataType::Map => Err(ImportError::column_error(
+                row_num,
+                col_name,
+                format!("{} columns cannot be imported from CSV", data_type.name()),
+            )),
         }
     }
 
@@ -173,7 +187,7 @@ impl NodeLoader {
         self.config.validate()?;
 
         // Check file size to decide on processing strategy
-        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let file_size = std::fs::metadata(path).map_or(0, |m| m.len());
 
         // Use parallel processing for large files when enabled
         if self.config.parallel && file_size >= MIN_PARALLEL_FILE_SIZE {
@@ -506,10 +520,27 @@ fn parse_field_with_interner(
                 Ok(Value::String(field.to_string()))
             }
         }
-        DataType::Date => Ok(Value::String(field.to_string())),
-        DataType::Timestamp => field.parse::<i64>().map(Value::Timestamp).map_err(|e| {
-            ImportError::column_error(row_num, col_name, format!("Invalid TIMESTAMP: {e}"))
+        DataType::Date => temporal::parse_date(field).map(Value::Date).ok_or_else(|| {
+            ImportError::column_error(
+                row_num,
+                col_name,
+                format!("Invalid DATE: {field} (expected YYYY-MM-DD)"),
+            )
         }),
+        // ISO format, or microseconds since the epoch
+        DataType::Timestamp => field
+            .parse::<i64>()
+            .ok()
+            .or_else(|| temporal::parse_timestamp(field))
+            .map(Value::Timestamp)
+            .ok_or_else(|| {
+                ImportError::column_error(row_num, col_name, format!("Invalid TIMESTAMP: {field}"))
+            }),
+        DataType::List | DataType::Map => Err(ImportError::column_error(
+            row_num,
+            col_name,
+            format!("{} columns cannot be imported from CSV", data_type.name()),
+        )),
     }
 }
 
@@ -645,6 +676,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_temporal_fields() {
+        let loader = NodeLoader::new(create_test_schema(), CsvImportConfig::default());
+
+        let date = loader.parse_field("2024-01-31", DataType::Date, 1, "day");
+        assert_eq!(date.unwrap(), Value::Date(19_753));
+        assert!(loader
+            .parse_field("31/01/2024", DataType::Date, 1, "day")
+            .is_err());
+
+        // ISO text or microseconds since the epoch
+        for field in [
+            "2024-01-31 12:30:00",
+            "2024-01-31T12:30:00Z",
+            "1706704200000000",
+        ] {
+            let timestamp = loader.parse_field(field, DataType::Timestamp, 1, "at");
+            assert_eq!(timestamp.unwrap(), Value::Timestamp(1_706_704_200_000_000));
+        }
+        assert!(loader
+            .parse_field("noon", DataType::Timestamp, 1, "at")
+            .is_err());
+    }
+
     #[test]
     fn test_load_sequential_explicit() {
         let schema = create_test_schema();
@@ -212,9 +212,8 @@ impl ParallelCsvReader {
         };
 
         // Determine thread count
-        let available_threads = std::thread::available_parallelism()
-            .map(std::num::NonZeroUsize::get)
-            .unwrap_or(1);
+        let available_threads =
+            std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
         let num_threads = num_threads
             .unwrap_or(available_threads)
             .min(num_blocks)
@@ -12,7 +12,7 @@ use crate::storage::csv::{
     parallel_read_all, shared_interner, CsvImportConfig, CsvParser, ImportError, ImportProgress,
     ImportResult, MmapReader, ProgressCallback, SharedInterner,
 };
-use crate::types::{DataType, Value};
+use crate::types::{temporal, DataType, Value};
 
 /// Minimum file size to use parallel processing (256KB).
 const MIN_PARALLEL_FILE_SIZE: u64 = 256 * 1024;
@@ -227,7 +227,7 @@ impl RelLoader {
         self.config.validate()?;
 
         // Check file size to decide on processing strategy
-        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let file_size = std::fs::metadata(path).map_or(0, |m| m.len());
 
         // Use parallel processing for large files when enabled
         if self.config.parallel && file_size >= MIN_PARALLEL_FILE_SIZE {
@@ -728,10 +728,27 @@ fn parse_field_with_interner(
                 Ok(Value::String(field.to_string()))
             }
         }
-        DataType::Date => Ok(Value::String(field.to_string())),
-        DataType::Timestamp => field.parse::<i64>().map(Value::Timestamp).map_err(|e| {
-            ImportError::column_error(row_num, col_name, format!("Invalid TIMESTAMP: {e}"))
+        DataType::Date => temporal::parse_date(field).map(Value::Date).ok_or_else(|| {
+            ImportError::column_error(
+                row_num,
+                col_name,
+                format!("Invalid DATE: {field} (expected YYYY-MM-DD)"),
+            )
         }),
+        // ISO format, or microseconds since the epoch
+        DataType::Timestamp => field
+            .parse::<i64>()
+            .ok()
+            .or_else(|| temporal::parse_timestamp(field))
+            .map(Value::Timestamp)
+            .ok_or_else(|| {
+                ImportError::column_error(row_num, col_name, format!("Invalid TIMESTAMP: {field}"))
+            }),
+        DataType::List | DataType::Map => Err(ImportError::column_error(
+            row_num,
+            col_name,
+            format!("{} columns cannot be imported from CSV", data_type.name()),
+        )),
     }
 }
 
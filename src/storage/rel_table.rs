@@ -24,7 +24,7 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
-use crate::catalog::RelTableSchema;
+use crate::catalog::{Direction, RelTableSchema};
 use crate::error::{Result, RuzuError};
 use crate::storage::ColumnStorage;
 use crate::types::Value;
@@ -288,6 +288,33 @@ impl CsrNodeGroup {
         Ok(())
     }
 
+    /// Removes the edge with the given relationship ID from a node's edge list.
+    ///
+    /// Like `insert_edge`, this shifts all subsequent edges and offsets.
+    ///
+    /// Returns `true` if the edge was found and removed.
+    pub fn remove_edge(&mut self, local_node_id: u32, rel_id: u64) -> bool {
+        if local_node_id as usize >= self.num_nodes as usize {
+            return false;
+        }
+
+        let start = self.offsets[local_node_id as usize] as usize;
+        let end = self.offsets[local_node_id as usize + 1] as usize;
+        let Some(pos) = self.rel_ids[start..end].iter().position(|&id| id == rel_id) else {
+            return false;
+        };
+
+        self.neighbors.remove(start + pos);
+        self.rel_ids.remove(start + pos);
+
+        // Update all subsequent offsets
+        for i in (local_node_id as usize + 1)..self.offsets.len() {
+            self.offsets[i] -= 1;
+        }
+
+        true
+    }
+
     /// Serializes the CSR node group to bytes.
     ///
     /// # Errors
@@ -472,6 +499,38 @@ impl RelTable {
         Ok(rel_id)
     }
 
+    /// Deletes a relationship from both the forward and backward indexes,
+    /// together with its properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no relationship with `rel_id` connects
+    /// `src_node_id` to `dst_node_id`.
+    pub fn delete(&mut self, src_node_id: u64, dst_node_id: u64, rel_id: u64) -> Result<()> {
+        let src_group_id = (src_node_id / NODE_GROUP_SIZE as u64) as u32;
+        let dst_group_id = (dst_node_id / NODE_GROUP_SIZE as u64) as u32;
+        let src_local_id = (src_node_id % NODE_GROUP_SIZE as u64) as u32;
+        let dst_local_id = (dst_node_id % NODE_GROUP_SIZE as u64) as u32;
+
+        let removed = self
+            .forward_groups
+            .get_mut(&src_group_id)
+            .is_some_and(|group| group.remove_edge(src_local_id, rel_id));
+        if !removed {
+            return Err(RuzuError::ExecutionError(format!(
+                "Relationship {rel_id} from {src_node_id} to {dst_node_id} does not exist in table '{}'",
+                self.schema.name
+            )));
+        }
+
+        if let Some(group) = self.backward_groups.get_mut(&dst_group_id) {
+            group.remove_edge(dst_local_id, rel_id);
+        }
+        self.properties.remove(&rel_id);
+
+        Ok(())
+    }
+
     /// Ensures a forward group exists with at least `min_nodes` capacity.
     fn ensure_forward_group(&mut self, group_id: u32, min_nodes: u32) {
         self.forward_groups.entry(group_id).or_insert_with(|| {
@@ -558,12 +617,78 @@ impl RelTable {
         Vec::new()
     }
 
+    /// Gets the edges of a node in the given direction.
+    ///
+    /// Returns (`neighbor_node_id`, `rel_id`) pairs. With [`Direction::Both`]
+    /// the node is treated as both source and destination, and a self-loop is
+    /// returned only once.
+    #[must_use]
+    pub fn get_edges(&self, node_id: u64, direction: Direction) -> Vec<(u64, u64)> {
+        match direction {
+            Direction::Forward => self.get_forward_edges(node_id),
+            Direction::Backward => self.get_backward_edges(node_id),
+            Direction::Both => {
+                let mut edges = self.get_forward_edges(node_id);
+                edges.extend(
+                    self.get_backward_edges(node_id)
+                        .into_iter()
+                        .filter(|&(src, _)| src != node_id),
+                );
+                edges
+            }
+        }
+    }
+
+    /// Returns the ID the next inserted relationship will be assigned.
+    #[must_use]
+    pub fn next_rel_id(&self) -> u64 {
+        self.next_rel_id
+    }
+
     /// Gets properties for a relationship by ID.
     #[must_use]
     pub fn get_properties(&self, rel_id: u64) -> Option<&Vec<Value>> {
         self.properties.get(&rel_id)
     }
 
+    /// Updates a single property of an existing relationship in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the relationship or property column does not exist,
+    /// or if the value has the wrong type.
+    pub fn update_property(&mut self, rel_id: u64, col_idx: usize, value: Value) -> Result<()> {
+        let col_def = self.schema.columns.get(col_idx).ok_or_else(|| {
+            RuzuError::SchemaError(format!(
+                "Property index {col_idx} out of range for relationship table '{}'",
+                self.schema.name
+            ))
+        })?;
+
+        if let Some(val_type) = value.data_type() {
+            if val_type != col_def.data_type {
+                return Err(RuzuError::TypeError {
+                    expected: col_def.data_type.name().into(),
+                    actual: val_type.name().into(),
+                });
+            }
+        }
+
+        let slot = self
+            .properties
+            .get_mut(&rel_id)
+            .and_then(|props| props.get_mut(col_idx))
+            .ok_or_else(|| {
+                RuzuError::ExecutionError(format!(
+                    "Relationship {rel_id} does not exist in table '{}'",
+                    self.schema.name
+                ))
+            })?;
+        *slot = value;
+
+        Ok(())
+    }
+
     /// Returns the total number of relationships.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -650,7 +775,7 @@ impl RelTable {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::catalog::{ColumnDef, Direction};
+    use crate::catalog::ColumnDef;
     use crate::types::DataType;
 
     fn create_test_schema() -> Arc<RelTableSchema> {
@@ -821,4 +946,45 @@ mod tests {
         assert_eq!(restored.get_forward_edges(0).len(), 1);
         assert_eq!(restored.get_forward_edges(1).len(), 1);
     }
+
+    #[test]
+    fn test_rel_table_delete() {
+        let schema = create_test_schema();
+        let mut table = RelTable::new(schema);
+
+        table.insert(0, 1, vec![Value::Int64(2018)]).unwrap();
+        let rel_id = table.insert(0, 2, vec![Value::Int64(2019)]).unwrap();
+        table.insert(1, 2, vec![Value::Int64(2020)]).unwrap();
+
+        table.delete(0, 2, rel_id).unwrap();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_forward_edges(0).len(), 1);
+        assert_eq!(table.get_backward_edges(2), vec![(1, 2)]);
+        assert!(table.get_properties(rel_id).is_none());
+        for group in table.forward_groups.values().chain(table.backward_groups.values()) {
+            group.validate().unwrap();
+        }
+
+        // Deleting the same relationship twice is an error
+        assert!(table.delete(0, 2, rel_id).is_err());
+    }
+
+    #[test]
+    fn test_rel_table_get_edges_by_direction() {
+        let schema = create_test_schema();
+        let mut table = RelTable::new(schema);
+
+        let out = table.insert(0, 1, vec![Value::Int64(2018)]).unwrap();
+        let incoming = table.insert(2, 0, vec![Value::Int64(2019)]).unwrap();
+        let self_loop = table.insert(0, 0, vec![Value::Int64(2020)]).unwrap();
+
+        assert_eq!(table.get_edges(0, Direction::Forward).len(), 2);
+        assert_eq!(table.get_edges(1, Direction::Backward), vec![(0, out)]);
+
+        // Undirected: each edge once, including the self-loop
+        let mut both = table.get_edges(0, Direction::Both);
+        both.sort_unstable();
+        assert_eq!(both, vec![(0, self_loop), (1, out), (2, incoming)]);
+    }
 }
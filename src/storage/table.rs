@@ -1,6 +1,6 @@
 //! Node table storage with columnar layout.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -20,11 +20,18 @@ pub struct TableData {
 }
 
 /// Node table with columnar storage.
+///
+/// Deleted rows are kept as tombstones so that node offsets referenced by
+/// relationship tables stay stable. A tombstone has every column set to NULL,
+/// which lets it survive serialization without changing the on-disk format;
+/// since primary keys can never be NULL, a NULL primary key identifies a
+/// tombstone when the table is reloaded.
 pub struct NodeTable {
     schema: Arc<NodeTableSchema>,
     columns: Vec<ColumnStorage>,
     row_count: usize,
     pk_index: HashMap<Vec<Value>, usize>,
+    deleted: HashSet<usize>,
 }
 
 impl std::fmt::Debug for NodeTable {
@@ -32,6 +39,7 @@ impl std::fmt::Debug for NodeTable {
         f.debug_struct("NodeTable")
             .field("schema", &self.schema.name)
             .field("row_count", &self.row_count)
+            .field("deleted_count", &self.deleted.len())
             .field("column_count", &self.columns.len())
             .finish_non_exhaustive()
     }
@@ -51,6 +59,7 @@ impl NodeTable {
             columns,
             row_count: 0,
             pk_index: HashMap::new(),
+            deleted: HashSet::new(),
         }
     }
 
@@ -58,8 +67,9 @@ impl NodeTable {
     #[must_use]
     pub fn from_data(schema: Arc<NodeTableSchema>, data: TableData) -> Self {
         let mut pk_index = HashMap::new();
+        let mut deleted = HashSet::new();
 
-        // Rebuild primary key index
+        // Rebuild primary key index, skipping tombstones (NULL primary key)
         for row_idx in 0..data.row_count {
             let pk_values: Vec<Value> = schema
                 .primary_key
@@ -71,7 +81,11 @@ impl NodeTable {
                         .and_then(|col| col.get(row_idx).cloned())
                 })
                 .collect();
-            pk_index.insert(pk_values, row_idx);
+            if pk_values.iter().any(Value::is_null) {
+                deleted.insert(row_idx);
+            } else {
+                pk_index.insert(pk_values, row_idx);
+            }
         }
 
         NodeTable {
@@ -79,6 +93,7 @@ impl NodeTable {
             columns: data.columns,
             row_count: data.row_count,
             pk_index,
+            deleted,
         }
     }
 
@@ -97,12 +112,26 @@ impl NodeTable {
         &self.schema
     }
 
-    /// Returns the number of rows in the table.
+    /// Returns the number of row slots in the table, including deleted rows.
+    ///
+    /// Node offsets are always below this value; use [`NodeTable::len`] for
+    /// the number of live rows.
     #[must_use]
     pub fn row_count(&self) -> usize {
         self.row_count
     }
 
+    /// Returns true if the row at `row_idx` has been deleted.
+    #[must_use]
+    pub fn is_deleted(&self, row_idx: usize) -> bool {
+        self.deleted.contains(&row_idx)
+    }
+
+    /// Returns an iterator over the offsets of all live (non-deleted) rows.
+    pub fn live_offsets(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.row_count).filter(move |idx| !self.deleted.contains(idx))
+    }
+
     /// Gets a column by index.
     #[must_use]
     pub fn get_column(&self, index: usize) -> Option<&ColumnStorage> {
@@ -116,7 +145,7 @@ impl NodeTable {
     /// Returns an error if:
     /// - A required column is missing
     /// - A value has the wrong type
-    /// - The primary key is duplicated
+    /// - The primary key is NULL or duplicated
     ///
     /// # Panics
     ///
@@ -160,6 +189,12 @@ impl NodeTable {
             .map(|col_name| row.get(col_name).unwrap().clone())
             .collect();
 
+        if pk_values.iter().any(Value::is_null) {
+            return Err(RuzuError::ConstraintViolation(
+                "Primary key cannot be NULL".into(),
+            ));
+        }
+
         // Check primary key uniqueness
         if self.pk_index.contains_key(&pk_values) {
             return Err(RuzuError::ConstraintViolation(format!(
@@ -180,16 +215,128 @@ impl NodeTable {
         Ok(())
     }
 
-    /// Returns the number of rows in the table.
+    /// Updates a single column value of an existing row in place.
+    ///
+    /// Updating a primary key column keeps the primary key index in sync.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The row or column does not exist
+    /// - The value has the wrong type
+    /// - A primary key column is set to NULL or to a key that already exists
+    pub fn update(&mut self, row_idx: usize, col_idx: usize, value: Value) -> Result<()> {
+        if row_idx >= self.row_count || self.deleted.contains(&row_idx) {
+            return Err(RuzuError::ExecutionError(format!(
+                "Row {row_idx} does not exist in table '{}'",
+                self.schema.name
+            )));
+        }
+
+        let col_def = self.schema.columns.get(col_idx).ok_or_else(|| {
+            RuzuError::SchemaError(format!(
+                "Column index {col_idx} out of range for table '{}'",
+                self.schema.name
+            ))
+        })?;
+
+        if let Some(val_type) = value.data_type() {
+            if val_type != col_def.data_type {
+                return Err(RuzuError::TypeError {
+                    expected: col_def.data_type.name().into(),
+                    actual: val_type.name().into(),
+                });
+            }
+        }
+
+        if self.schema.primary_key.contains(&col_def.name) {
+            if value.is_null() {
+                return Err(RuzuError::ConstraintViolation(format!(
+                    "Primary key column '{}' cannot be NULL",
+                    col_def.name
+                )));
+            }
+
+            let old_key = self.pk_values(row_idx);
+            let new_key: Vec<Value> = self
+                .schema
+                .primary_key
+                .iter()
+                .zip(&old_key)
+                .map(|(pk_col, old)| {
+                    if *pk_col == col_def.name {
+                        value.clone()
+                    } else {
+                        old.clone()
+                    }
+                })
+                .collect();
+
+            if new_key != old_key {
+                if self.pk_index.contains_key(&new_key) {
+                    return Err(RuzuError::ConstraintViolation(format!(
+                        "Duplicate primary key: {new_key:?}"
+                    )));
+                }
+                self.pk_index.remove(&old_key);
+                self.pk_index.insert(new_key, row_idx);
+            }
+        }
+
+        self.columns[col_idx].set(row_idx, value);
+        Ok(())
+    }
+
+    /// Deletes a row, leaving a tombstone at its offset.
+    ///
+    /// The row's primary key is removed from the index, so the key can be
+    /// reused by a later insert. Offsets of other rows are unaffected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row does not exist or was already deleted.
+    pub fn delete(&mut self, row_idx: usize) -> Result<()> {
+        if row_idx >= self.row_count || self.deleted.contains(&row_idx) {
+            return Err(RuzuError::ExecutionError(format!(
+                "Row {row_idx} does not exist in table '{}'",
+                self.schema.name
+            )));
+        }
+
+        let key = self.pk_values(row_idx);
+        self.pk_index.remove(&key);
+        for column in &mut self.columns {
+            column.set(row_idx, Value::Null);
+        }
+        self.deleted.insert(row_idx);
+
+        Ok(())
+    }
+
+    /// Returns the primary key values of a row, in primary key order.
+    fn pk_values(&self, row_idx: usize) -> Vec<Value> {
+        self.schema
+            .primary_key
+            .iter()
+            .filter_map(|col_name| {
+                self.schema
+                    .get_column_index(col_name)
+                    .and_then(|idx| self.columns.get(idx))
+                    .and_then(|col| col.get(row_idx).cloned())
+            })
+            .collect()
+    }
+
+    /// Returns the number of live rows in the table.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.row_count
+        self.row_count - self.deleted.len()
     }
 
-    /// Returns true if the table has no rows.
+    /// Returns true if the table has no live rows.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.row_count == 0
+        self.len() == 0
     }
 
     /// Finds a row by primary key value.
@@ -214,15 +361,30 @@ impl NodeTable {
         let col_idx = self.schema.get_column_index(key_column)?;
         let column = self.columns.get(col_idx)?;
 
-        for row_idx in 0..self.row_count {
-            if let Some(val) = column.get(row_idx) {
-                if val == value {
-                    return Some(row_idx);
-                }
-            }
-        }
+        self.live_offsets()
+            .find(|&row_idx| column.get(row_idx) == Some(value))
+    }
 
-        None
+    /// Finds a row by its full primary key, with one value per primary key
+    /// column in declaration order. Works for single and composite keys.
+    #[must_use]
+    pub fn find_by_key(&self, key: &[Value]) -> Option<usize> {
+        self.pk_index.get(key).copied()
+    }
+
+    /// Finds the rows with any of the given primary key values.
+    ///
+    /// Each value is a point lookup, as in [`NodeTable::find_by_pk`]. Offsets
+    /// are returned in the order of `values` without duplicates; values with
+    /// no row are skipped.
+    #[must_use]
+    pub fn find_by_pks(&self, key_column: &str, values: &[Value]) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        values
+            .iter()
+            .filter_map(|value| self.find_by_pk(key_column, value))
+            .filter(|offset| seen.insert(*offset))
+            .collect()
     }
 
     /// Gets a column value for a specific row.
@@ -255,6 +417,7 @@ impl NodeTable {
     /// - Column count doesn't match schema
     /// - Any column name is unknown
     /// - Type mismatch for any value
+    /// - NULL primary key
     /// - Duplicate primary key in batch or existing table
     pub fn insert_batch(&mut self, rows: Vec<Vec<Value>>, columns: &[String]) -> Result<usize> {
         if rows.is_empty() {
@@ -313,6 +476,12 @@ impl NodeTable {
                 })
                 .collect();
 
+            if pk_values.iter().any(Value::is_null) {
+                return Err(RuzuError::ConstraintViolation(format!(
+                    "Primary key cannot be NULL in batch row {row_idx}"
+                )));
+            }
+
             // Check within batch
             if !batch_pks.insert(pk_values.clone()) {
                 return Err(RuzuError::ConstraintViolation(format!(
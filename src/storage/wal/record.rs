@@ -26,6 +26,11 @@ pub enum WalRecordType {
     RelInsertion = 36,
     /// Checkpoint marker.
     Checkpoint = 254,
+    /// Update a relationship property.
+    ///
+    /// Declared last so the serialized variant indices of existing record
+    /// types stay stable.
+    RelUpdate = 34,
 }
 
 impl TryFrom<u8> for WalRecordType {
@@ -40,6 +45,7 @@ impl TryFrom<u8> for WalRecordType {
             31 => Ok(WalRecordType::NodeDeletion),
             32 => Ok(WalRecordType::NodeUpdate),
             33 => Ok(WalRecordType::RelDeletion),
+            34 => Ok(WalRecordType::RelUpdate),
             36 => Ok(WalRecordType::RelInsertion),
             254 => Ok(WalRecordType::Checkpoint),
             _ => Err(()),
@@ -119,6 +125,17 @@ pub enum WalPayload {
         /// Checkpoint ID.
         checkpoint_id: u64,
     },
+    /// Relationship property update payload.
+    RelUpdate {
+        /// Relationship table ID.
+        table_id: u32,
+        /// Relationship ID.
+        rel_id: u64,
+        /// Property column ID being updated.
+        col_id: u32,
+        /// New value.
+        value: Value,
+    },
 }
 
 /// A single WAL record.
@@ -252,6 +269,43 @@ mod tests {
         assert_eq!(deserialized.lsn, record.lsn);
     }
 
+    #[test]
+    fn test_update_record_roundtrip() {
+        assert_eq!(WalRecordType::try_from(32), Ok(WalRecordType::NodeUpdate));
+        assert_eq!(WalRecordType::try_from(34), Ok(WalRecordType::RelUpdate));
+
+        let record = WalRecord::new(
+            WalRecordType::RelUpdate,
+            7,
+            3,
+            WalPayload::RelUpdate {
+                table_id: 2,
+                rel_id: 11,
+                col_id: 0,
+                value: Value::Int64(2020),
+            },
+        );
+
+        let bytes = record.serialize().unwrap();
+        let deserialized = WalRecord::deserialize(&bytes).unwrap();
+
+        assert_eq!(deserialized.record_type, WalRecordType::RelUpdate);
+        match deserialized.payload {
+            WalPayload::RelUpdate {
+                table_id,
+                rel_id,
+                col_id,
+                value,
+            } => {
+                assert_eq!(table_id, 2);
+                assert_eq!(rel_id, 11);
+                assert_eq!(col_id, 0);
+                assert_eq!(value, Value::Int64(2020));
+            }
+            _ => panic!("Wrong payload type"),
+        }
+    }
+
     #[test]
     fn test_table_insertion_record() {
         let rows = vec![
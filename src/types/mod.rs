@@ -1,5 +1,6 @@
 //! Type system for ruzu database.
 
+pub mod temporal;
 mod value;
 
 pub use value::{DataType, QueryResult, Row, Value};
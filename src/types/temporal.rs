@@ -0,0 +1,87 @@
+//! Calendar conversions for DATE and TIMESTAMP values.
+//!
+//! Dates are stored as days since 1970-01-01 and timestamps as microseconds
+//! since 1970-01-01 00:00:00 UTC, matching Arrow's `Date32` and
+//! `Timestamp(Microsecond)` layouts.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime};
+
+/// Microseconds in a day.
+pub const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Days from 0001-01-01 (day 1 of the common era) to 1970-01-01.
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Parses a `YYYY-MM-DD` date into days since the epoch.
+#[must_use]
+pub fn parse_date(s: &str) -> Option<i32> {
+    let date = NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()?;
+    Some(date.num_days_from_ce() - EPOCH_DAYS_FROM_CE)
+}
+
+/// Parses a timestamp into microseconds since the epoch.
+///
+/// Accepts `YYYY-MM-DD HH:MM[:SS[.ffffff]]`, with a space or `T` separator
+/// and an optional trailing `Z`, or a bare date (midnight).
+#[must_use]
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let datetime = [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)
+    })?;
+    Some(datetime.and_utc().timestamp_micros())
+}
+
+/// Converts days since the epoch to a calendar date.
+#[must_use]
+pub fn date_from_days(days: i32) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(days.checked_add(EPOCH_DAYS_FROM_CE)?)
+}
+
+/// Converts microseconds since the epoch to a date and time.
+#[must_use]
+pub fn datetime_from_micros(micros: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp_micros(micros).map(|datetime| datetime.naive_utc())
+}
+
+/// Returns the day (since the epoch) on which a timestamp falls.
+#[must_use]
+pub fn timestamp_to_days(micros: i64) -> i32 {
+    i32::try_from(micros.div_euclid(MICROS_PER_DAY)).unwrap_or(i32::MAX)
+}
+
+/// Returns midnight of a day (since the epoch) as a timestamp.
+#[must_use]
+pub fn days_to_timestamp(days: i32) -> i64 {
+    i64::from(days) * MICROS_PER_DAY
+}
+
+/// Formats a date as `YYYY-MM-DD`.
+#[must_use]
+pub fn format_date(days: i32) -> String {
+    date_from_days(days).map_or_else(
+        || days.to_string(),
+        |date| date.format("%Y-%m-%d").to_string(),
+    )
+}
+
+/// Formats a timestamp as `YYYY-MM-DD HH:MM:SS`, with a fraction when the
+/// seconds are not whole.
+#[must_use]
+pub fn format_timestamp(micros: i64) -> String {
+    datetime_from_micros(micros).map_or_else(
+        || micros.to_string(),
+        |datetime| datetime.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+    )
+}
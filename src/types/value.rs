@@ -1,7 +1,7 @@
 //! Value and `DataType` definitions for ruzu.
 
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +22,10 @@ pub enum DataType {
     Date,
     /// Timestamp (stored as microseconds since epoch).
     Timestamp,
+    /// List of values (query results only; element types are not tracked).
+    List,
+    /// Map from property names to values (query results only).
+    Map,
 }
 
 impl DataType {
@@ -36,9 +40,30 @@ impl DataType {
             DataType::String => "STRING",
             DataType::Date => "DATE",
             DataType::Timestamp => "TIMESTAMP",
+            DataType::List => "LIST",
+            DataType::Map => "MAP",
         }
     }
 
+    /// Parses a column type name as written in DDL, ignoring case.
+    ///
+    /// Returns None for unknown names and for LIST and MAP, which cannot be
+    /// stored in tables.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            DataType::Int64,
+            DataType::Float32,
+            DataType::Float64,
+            DataType::Bool,
+            DataType::String,
+            DataType::Date,
+            DataType::Timestamp,
+        ]
+        .into_iter()
+        .find(|data_type| data_type.name().eq_ignore_ascii_case(name))
+    }
+
     /// Returns whether this type is a fixed-width type.
     #[must_use]
     pub fn is_fixed_width(&self) -> bool {
@@ -60,7 +85,7 @@ impl DataType {
             DataType::Int64 | DataType::Float64 | DataType::Timestamp => Some(8),
             DataType::Float32 | DataType::Date => Some(4),
             DataType::Bool => Some(1),
-            DataType::String => None, // variable width
+            DataType::String | DataType::List | DataType::Map => None, // variable width
         }
     }
 
@@ -100,6 +125,20 @@ impl DataType {
             DataType::Timestamp => {
                 arrow::datatypes::DataType::Timestamp(arrow::datatypes::TimeUnit::Microsecond, None)
             }
+            DataType::List => arrow::datatypes::DataType::List(std::sync::Arc::new(
+                arrow::datatypes::Field::new_list_field(arrow::datatypes::DataType::Null, true),
+            )),
+            DataType::Map => arrow::datatypes::DataType::Map(
+                std::sync::Arc::new(arrow::datatypes::Field::new(
+                    "entries",
+                    arrow::datatypes::DataType::Struct(arrow::datatypes::Fields::from(vec![
+                        arrow::datatypes::Field::new("keys", arrow::datatypes::DataType::Utf8, false),
+                        arrow::datatypes::Field::new("values", arrow::datatypes::DataType::Null, true),
+                    ])),
+                    false,
+                )),
+                false,
+            ),
         }
     }
 
@@ -120,6 +159,8 @@ impl DataType {
                 Some(DataType::Date)
             }
             arrow::datatypes::DataType::Timestamp(_, _) => Some(DataType::Timestamp),
+            arrow::datatypes::DataType::List(_) => Some(DataType::List),
+            arrow::datatypes::DataType::Map(_, _) => Some(DataType::Map),
             _ => None,
         }
     }
@@ -142,6 +183,10 @@ pub enum Value {
     Date(i32),
     /// Timestamp value (microseconds since Unix epoch).
     Timestamp(i64),
+    /// List value.
+    List(Vec<Value>),
+    /// Map value, e.g. the properties of a returned node.
+    Map(BTreeMap<String, Value>),
     /// Null value.
     Null,
 }
@@ -157,6 +202,8 @@ impl std::hash::Hash for Value {
             Value::Bool(v) => v.hash(state),
             Value::String(v) => v.hash(state),
             Value::Date(v) => v.hash(state),
+            Value::List(v) => v.hash(state),
+            Value::Map(v) => v.hash(state),
             Value::Null => {}
         }
     }
@@ -201,6 +248,8 @@ impl Value {
             Value::String(_) => Some(DataType::String),
             Value::Date(_) => Some(DataType::Date),
             Value::Timestamp(_) => Some(DataType::Timestamp),
+            Value::List(_) => Some(DataType::List),
+            Value::Map(_) => Some(DataType::Map),
             Value::Null => None,
         }
     }
@@ -232,6 +281,24 @@ impl Value {
         }
     }
 
+    /// Attempts to extract a list of values.
+    #[must_use]
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Attempts to extract a map of values.
+    #[must_use]
+    pub fn as_map(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
     /// Attempts to extract a bool value.
     #[must_use]
     pub fn as_bool(&self) -> Option<bool> {
@@ -243,7 +310,8 @@ impl Value {
 
     /// Compares two values using SQL null semantics.
     ///
-    /// Returns None if either value is null or types don't match.
+    /// Lists compare element by element; maps are not ordered. Returns None
+    /// if either value is null or types don't match.
     #[must_use]
     pub fn compare(&self, other: &Value) -> Option<Ordering> {
         match (self, other) {
@@ -254,6 +322,15 @@ impl Value {
             (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
             (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
             (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+            (Value::List(a), Value::List(b)) => {
+                for (x, y) in a.iter().zip(b) {
+                    match x.compare(y)? {
+                        Ordering::Equal => {}
+                        ordering => return Some(ordering),
+                    }
+                }
+                Some(a.len().cmp(&b.len()))
+            }
             // Null or type mismatch
             _ => None,
         }
@@ -313,6 +390,11 @@ impl Row {
     pub fn insert(&mut self, column: String, value: Value) {
         self.values.insert(column, value);
     }
+
+    /// Removes a column from the row, returning its value.
+    pub fn remove(&mut self, column: &str) -> Option<Value> {
+        self.values.remove(column)
+    }
 }
 
 /// Result of query execution containing rows and metadata.
@@ -2,6 +2,25 @@
 
 use ruzu::{Database, Value};
 
+/// Values of column `name` in every result row, NULL where a row lacks it.
+fn column(result: &ruzu::QueryResult, name: &str) -> Vec<Value> {
+    result
+        .rows
+        .iter()
+        .map(|row| row.get(name).cloned().unwrap_or(Value::Null))
+        .collect()
+}
+
+/// String values, for comparing against a `column` of strings.
+fn strings(values: &[&str]) -> Vec<Value> {
+    values.iter().map(|v| s(v)).collect()
+}
+
+/// A string value.
+fn s(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
 // =============================================================================
 // Storage Integration Tests (Phase 2)
 // =============================================================================
@@ -4059,7 +4078,61 @@ mod aggregation_tests {
 // =============================================================================
 
 mod multi_hop_tests {
-    use ruzu::Database;
+    use ruzu::{Database, RuzuError, Value};
+    use super::{column, s};
+
+    /// Alice -> Bob -> Carol -> Alice is a `Knows` triangle and Bob also knows
+    /// Dave. Carol works at Acme (Oslo), Dave at Initech (Bergen).
+    fn setup_chain_graph() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Company(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE City(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        db.execute("CREATE REL TABLE WorksAt(FROM Person TO Company)")
+            .unwrap();
+        db.execute("CREATE REL TABLE LocatedIn(FROM Company TO City)")
+            .unwrap();
+        for name in ["Alice", "Bob", "Carol", "Dave"] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}'}})"))
+                .unwrap();
+        }
+        for (company, city) in [("Acme", "Oslo"), ("Initech", "Bergen")] {
+            db.execute(&format!("CREATE (:Company {{name: '{company}'}})"))
+                .unwrap();
+            db.execute(&format!("CREATE (:City {{name: '{city}'}})"))
+                .unwrap();
+            db.execute(&format!(
+                "MATCH (a:Company {{name: '{company}'}}), (b:City {{name: '{city}'}}) \
+                 CREATE (a)-[:LocatedIn]->(b)"
+            ))
+            .unwrap();
+        }
+        for (src, dst, since) in [
+            ("Alice", "Bob", 2010),
+            ("Bob", "Carol", 2015),
+            ("Carol", "Alice", 2020),
+            ("Bob", "Dave", 2018),
+        ] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) \
+                 CREATE (a)-[:Knows {{since: {since}}}]->(b)"
+            ))
+            .unwrap();
+        }
+        for (person, company) in [("Carol", "Acme"), ("Dave", "Initech")] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{person}'}}), (b:Company {{name: '{company}'}}) \
+                 CREATE (a)-[:WorksAt]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
 
     #[test]
     fn test_single_hop_traversal() {
@@ -4150,6 +4223,116 @@ mod multi_hop_tests {
         // Should execute without error
         assert_eq!(result.row_count(), 0);
     }
+
+    #[test]
+    fn test_friends_of_friends() {
+        let mut db = setup_chain_graph();
+        let result = db
+            .execute(
+                "MATCH (a:Person {name: 'Alice'})-[:Knows]->(b:Person)-[:Knows]->(c:Person) \
+                 RETURN b.name, c.name ORDER BY c.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "b.name"), vec![s("Bob"), s("Bob")]);
+        assert_eq!(column(&result, "c.name"), vec![s("Carol"), s("Dave")]);
+    }
+
+    #[test]
+    fn test_chain_across_rel_types() {
+        let mut db = setup_chain_graph();
+        let result = db
+            .execute(
+                "MATCH (a:Person {name: 'Alice'})-[:Knows]->(b:Person)-[:Knows]->(c:Person)\
+                 -[:WorksAt]->(co:Company)-[:LocatedIn]->(city:City) \
+                 RETURN c.name, co.name, city.name ORDER BY c.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "c.name"), vec![s("Carol"), s("Dave")]);
+        assert_eq!(column(&result, "co.name"), vec![s("Acme"), s("Initech")]);
+        assert_eq!(column(&result, "city.name"), vec![s("Oslo"), s("Bergen")]);
+    }
+
+    #[test]
+    fn test_chain_inline_filter_on_middle_node() {
+        let mut db = setup_chain_graph();
+        let result = db
+            .execute(
+                "MATCH (a:Person)-[:Knows]->(b:Person {name: 'Bob'})-[:Knows]->(c:Person) \
+                 RETURN a.name, c.name ORDER BY c.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.name"), vec![s("Alice"), s("Alice")]);
+        assert_eq!(column(&result, "c.name"), vec![s("Carol"), s("Dave")]);
+    }
+
+    #[test]
+    fn test_chain_rel_variables_and_where() {
+        let mut db = setup_chain_graph();
+        let result = db
+            .execute(
+                "MATCH (a:Person)-[k1:Knows]->(b:Person)-[k2:Knows]->(c:Person) \
+                 WHERE k2.since > 2016 RETURN a.name, k1.since, c.name ORDER BY a.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.name"), vec![s("Alice"), s("Bob")]);
+        assert_eq!(
+            column(&result, "k1.since"),
+            vec![Value::Int64(2010), Value::Int64(2015)]
+        );
+        assert_eq!(column(&result, "c.name"), vec![s("Dave"), s("Alice")]);
+    }
+
+    #[test]
+    fn test_chain_closing_a_cycle() {
+        let mut db = setup_chain_graph();
+        let result = db
+            .execute(
+                "MATCH (a:Person)-[:Knows]->(b:Person)-[:Knows]->(c:Person)-[:Knows]->(a) \
+                 RETURN a.name ORDER BY a.name",
+            )
+            .unwrap();
+        assert_eq!(
+            column(&result, "a.name"),
+            vec![s("Alice"), s("Bob"), s("Carol")]
+        );
+    }
+
+    #[test]
+    fn test_optional_match_chain_returns_nulls() {
+        let mut db = setup_chain_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) \
+                 OPTIONAL MATCH (p)-[:WorksAt]->(co:Company)-[:LocatedIn]->(city:City) \
+                 RETURN p.name, co.name, city.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(
+            column(&result, "city.name"),
+            vec![Value::Null, Value::Null, s("Oslo"), s("Bergen")]
+        );
+        assert_eq!(
+            column(&result, "co.name"),
+            vec![Value::Null, Value::Null, s("Acme"), s("Initech")]
+        );
+    }
+
+    #[test]
+    fn test_chain_with_mismatched_endpoint_label_fails() {
+        let mut db = setup_chain_graph();
+        let err = db
+            .execute("MATCH (a:Person)-[:Knows]->(b:Person)-[:LocatedIn]->(c:City) RETURN c.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::SchemaError(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_chain_with_undefined_rel_type_fails() {
+        let mut db = setup_chain_graph();
+        assert!(db
+            .execute("MATCH (a:Person)-[:Knows]->(b:Person)-[:Likes]->(c:Person) RETURN c.name")
+            .is_err());
+    }
 }
 
 // =============================================================================
@@ -5811,3 +5994,4892 @@ mod multi_page_crash_recovery_tests {
         }
     }
 }
+
+// =============================================================================
+// SET Clause Tests
+// =============================================================================
+
+mod set_clause_tests {
+    use ruzu::{Database, DatabaseConfig, RuzuError, Value};
+    use tempfile::TempDir;
+
+    fn setup_people(db: &mut Database) {
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        db.execute("CREATE (:Person {name: 'Alice', age: 30, city: 'Bergen'})")
+            .unwrap();
+        db.execute("CREATE (:Person {name: 'Bob', age: 25, city: 'Bergen'})")
+            .unwrap();
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) CREATE (a)-[:Knows {since: 2010}]->(b)",
+        )
+        .unwrap();
+    }
+
+    fn person(db: &mut Database, name: &str) -> ruzu::Row {
+        let result = db
+            .execute(&format!(
+                "MATCH (p:Person) WHERE p.name = '{name}' RETURN p.name, p.age, p.city"
+            ))
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        result.get_row(0).unwrap().clone()
+    }
+
+    #[test]
+    fn test_set_node_properties_with_where() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (p:Person) WHERE p.name = 'Alice' SET p.age = 31, p.city = 'Oslo'")
+            .unwrap();
+
+        let alice = person(&mut db, "Alice");
+        assert_eq!(alice.get("p.age"), Some(&Value::Int64(31)));
+        assert_eq!(alice.get("p.city"), Some(&Value::String("Oslo".into())));
+
+        let bob = person(&mut db, "Bob");
+        assert_eq!(bob.get("p.age"), Some(&Value::Int64(25)));
+        assert_eq!(bob.get("p.city"), Some(&Value::String("Bergen".into())));
+    }
+
+    #[test]
+    fn test_set_all_matching_nodes() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (p:Person {city: 'Bergen'}) SET p.city = 'Trondheim'")
+            .unwrap();
+
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.city = 'Trondheim' RETURN COUNT(*)")
+            .unwrap();
+        assert_eq!(result.get_row(0).unwrap().get("COUNT(*)"), Some(&Value::Int64(2)));
+    }
+
+    #[test]
+    fn test_set_relationship_property() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) WHERE b.name = 'Bob' SET r.since = 2020")
+            .unwrap();
+
+        let result = db
+            .execute("MATCH (a:Person)-[r:Knows]->(b:Person) RETURN a.name, r.since")
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(result.get_row(0).unwrap().get("r.since"), Some(&Value::Int64(2020)));
+    }
+
+    #[test]
+    fn test_set_primary_key_keeps_index_in_sync() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (p:Person {name: 'Bob'}) SET p.name = 'Robert'")
+            .unwrap();
+
+        // The renamed node is reachable through the primary key index
+        db.execute("MATCH (p:Person {name: 'Robert'}) SET p.age = 26")
+            .unwrap();
+        assert_eq!(person(&mut db, "Robert").get("p.age"), Some(&Value::Int64(26)));
+
+        let err = db
+            .execute("MATCH (p:Person {name: 'Robert'}) SET p.name = 'Alice'")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::ConstraintViolation(_)));
+    }
+
+    #[test]
+    fn test_set_failure_leaves_data_unchanged() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        // Both rows match; renaming both to the same key must fail as a whole
+        let err = db
+            .execute("MATCH (p:Person) SET p.age = 40, p.name = 'Same'")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::ConstraintViolation(_)));
+
+        assert_eq!(person(&mut db, "Alice").get("p.age"), Some(&Value::Int64(30)));
+        assert_eq!(person(&mut db, "Bob").get("p.age"), Some(&Value::Int64(25)));
+    }
+
+    #[test]
+    fn test_set_invalid_targets() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        assert!(db.execute("MATCH (p:Person) SET p.salary = 10").is_err());
+        assert!(db.execute("MATCH (p:Person) SET q.age = 10").is_err());
+        assert!(matches!(
+            db.execute("MATCH (p:Person) SET p.age = 'old'"),
+            Err(RuzuError::TypeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_set_replayed_from_wal_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_people(&mut db);
+            db.checkpoint().unwrap();
+
+            db.execute("MATCH (p:Person {name: 'Alice'}) SET p.age = 31, p.city = 'Oslo'")
+                .unwrap();
+            db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) SET r.since = 2021")
+                .unwrap();
+
+            // Simulate crash: drop without close (WAL not truncated)
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            let alice = person(&mut db, "Alice");
+            assert_eq!(alice.get("p.age"), Some(&Value::Int64(31)));
+            assert_eq!(alice.get("p.city"), Some(&Value::String("Oslo".into())));
+
+            let result = db
+                .execute("MATCH (a:Person)-[r:Knows]->(b:Person) RETURN r.since")
+                .unwrap();
+            assert_eq!(result.get_row(0).unwrap().get("r.since"), Some(&Value::Int64(2021)));
+        }
+    }
+
+    #[test]
+    fn test_set_on_copy_loaded_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("people.csv");
+        std::fs::write(&csv_path, "name,age,city\nAlice,30,Bergen\nBob,25,Bergen\n").unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            db.execute(
+                "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+            )
+            .unwrap();
+            db.execute(&format!("COPY Person FROM '{}'", csv_path.display()))
+                .unwrap();
+            db.checkpoint().unwrap();
+
+            db.execute("MATCH (p:Person) WHERE p.age < 28 SET p.city = 'Oslo'")
+                .unwrap();
+            assert_eq!(
+                person(&mut db, "Bob").get("p.city"),
+                Some(&Value::String("Oslo".into()))
+            );
+
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(
+                person(&mut db, "Bob").get("p.city"),
+                Some(&Value::String("Oslo".into()))
+            );
+            assert_eq!(
+                person(&mut db, "Alice").get("p.city"),
+                Some(&Value::String("Bergen".into()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_changes_to_copied_rows_replayed_from_wal_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("people.csv");
+        std::fs::write(&csv_path, "name,age,city\nAlice,30,Bergen\nBob,25,Bergen\n").unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            db.execute(
+                "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+            )
+            .unwrap();
+            db.execute(&format!("COPY Person FROM '{}'", csv_path.display()))
+                .unwrap();
+
+            // No checkpoint: the logged changes refer to rows that COPY loaded
+            db.execute("MATCH (p:Person) WHERE p.name = 'Bob' SET p.city = 'Oslo'")
+                .unwrap();
+            db.execute("MATCH (p:Person) WHERE p.name = 'Alice' DELETE p")
+                .unwrap();
+
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(
+                person(&mut db, "Bob").get("p.city"),
+                Some(&Value::String("Oslo".into()))
+            );
+            let result = db.execute("MATCH (p:Person) RETURN p.name").unwrap();
+            assert_eq!(result.row_count(), 1);
+        }
+    }
+}
+
+// =============================================================================
+// DELETE Tests
+// =============================================================================
+
+mod delete_tests {
+    use ruzu::{Database, DatabaseConfig, RuzuError, Value};
+    use tempfile::TempDir;
+
+    fn setup_people(db: &mut Database) {
+        db.execute("CREATE NODE TABLE Person(name STRING, age INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        db.execute("CREATE (:Person {name: 'Alice', age: 30})")
+            .unwrap();
+        db.execute("CREATE (:Person {name: 'Bob', age: 25})").unwrap();
+        db.execute("CREATE (:Person {name: 'Carol', age: 95})")
+            .unwrap();
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) CREATE (a)-[:Knows {since: 2010}]->(b)",
+        )
+        .unwrap();
+    }
+
+    fn names(db: &mut Database) -> Vec<String> {
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name ORDER BY p.name")
+            .unwrap();
+        result
+            .rows
+            .iter()
+            .map(|row| match row.get("p.name") {
+                Some(Value::String(s)) => s.clone(),
+                other => panic!("unexpected name {other:?}"),
+            })
+            .collect()
+    }
+
+    fn knows_count(db: &mut Database) -> usize {
+        db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) RETURN a.name, b.name")
+            .unwrap()
+            .row_count()
+    }
+
+    #[test]
+    fn test_delete_node_without_relationships() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (p:Person) WHERE p.age > 90 DELETE p")
+            .unwrap();
+
+        assert_eq!(names(&mut db), vec!["Alice", "Bob"]);
+        let result = db.execute("MATCH (p:Person) RETURN COUNT(*)").unwrap();
+        assert_eq!(result.get_row(0).unwrap().get("COUNT(*)"), Some(&Value::Int64(2)));
+    }
+
+    #[test]
+    fn test_delete_node_with_relationships_is_rejected() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        // Alice has an outgoing edge, Bob an incoming one
+        for name in ["Alice", "Bob"] {
+            let err = db
+                .execute(&format!("MATCH (p:Person {{name: '{name}'}}) DELETE p"))
+                .unwrap_err();
+            assert!(matches!(err, RuzuError::ReferentialIntegrity(_)));
+        }
+
+        assert_eq!(names(&mut db), vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(knows_count(&mut db), 1);
+    }
+
+    #[test]
+    fn test_detach_delete_removes_relationships() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (p:Person {name: 'Bob'}) DETACH DELETE p")
+            .unwrap();
+
+        assert_eq!(names(&mut db), vec!["Alice", "Carol"]);
+        assert_eq!(knows_count(&mut db), 0);
+
+        // Alice no longer has edges, so a plain DELETE now succeeds
+        db.execute("MATCH (p:Person {name: 'Alice'}) DELETE p")
+            .unwrap();
+        assert_eq!(names(&mut db), vec!["Carol"]);
+    }
+
+    #[test]
+    fn test_deleted_primary_key_can_be_reused() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        db.execute("MATCH (p:Person {name: 'Carol'}) DELETE p")
+            .unwrap();
+        assert_eq!(
+            db.execute("MATCH (p:Person) WHERE p.name = 'Carol' RETURN p.age")
+                .unwrap()
+                .row_count(),
+            0
+        );
+
+        db.execute("CREATE (:Person {name: 'Carol', age: 40})")
+            .unwrap();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.name = 'Carol' RETURN p.age")
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(result.get_row(0).unwrap().get("p.age"), Some(&Value::Int64(40)));
+
+        // Relationships can be created to the re-inserted node
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Carol'}) CREATE (a)-[:Knows {since: 2024}]->(b)",
+        )
+        .unwrap();
+        assert_eq!(knows_count(&mut db), 2);
+    }
+
+    #[test]
+    fn test_delete_undefined_variable() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        assert!(matches!(
+            db.execute("MATCH (p:Person) DELETE q"),
+            Err(RuzuError::BindError(_))
+        ));
+        assert_eq!(names(&mut db).len(), 3);
+    }
+
+    #[test]
+    fn test_delete_replayed_from_wal_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_people(&mut db);
+            db.checkpoint().unwrap();
+
+            db.execute("MATCH (p:Person {name: 'Bob'}) DETACH DELETE p")
+                .unwrap();
+            db.execute("MATCH (p:Person {name: 'Carol'}) DELETE p")
+                .unwrap();
+
+            // Simulate crash: drop without close (WAL not truncated)
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(names(&mut db), vec!["Alice"]);
+            assert_eq!(knows_count(&mut db), 0);
+        }
+    }
+
+    #[test]
+    fn test_delete_persists_across_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_people(&mut db);
+            db.execute("MATCH (p:Person {name: 'Alice'}) DETACH DELETE p")
+                .unwrap();
+            db.close().unwrap();
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(names(&mut db), vec!["Bob", "Carol"]);
+            assert_eq!(knows_count(&mut db), 0);
+
+            // The deleted key is free again after reload
+            db.execute("CREATE (:Person {name: 'Alice', age: 31})")
+                .unwrap();
+            assert_eq!(names(&mut db), vec!["Alice", "Bob", "Carol"]);
+        }
+    }
+
+    fn setup_knows_history(db: &mut Database) {
+        db.execute("CREATE NODE TABLE Person(name STRING, age INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        for name in ["Alice", "Bob", "Carol"] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}', age: 30}})"))
+                .unwrap();
+        }
+        for (src, dst, since) in [
+            ("Alice", "Bob", 1995),
+            ("Alice", "Carol", 2005),
+            ("Bob", "Carol", 1999),
+            ("Carol", "Alice", 2015),
+        ] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) CREATE (a)-[:Knows {{since: {since}}}]->(b)"
+            ))
+            .unwrap();
+        }
+    }
+
+    fn knows_since(db: &mut Database) -> Vec<(String, String, i64)> {
+        let result = db
+            .execute("MATCH (a:Person)-[r:Knows]->(b:Person) RETURN a.name, b.name, r.since ORDER BY r.since")
+            .unwrap();
+        result
+            .rows
+            .iter()
+            .map(|row| match (row.get("a.name"), row.get("b.name"), row.get("r.since")) {
+                (Some(Value::String(a)), Some(Value::String(b)), Some(Value::Int64(since))) => {
+                    (a.clone(), b.clone(), *since)
+                }
+                other => panic!("unexpected row {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_delete_relationships_with_where() {
+        let mut db = Database::new();
+        setup_knows_history(&mut db);
+
+        db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) WHERE r.since < 2000 DELETE r")
+            .unwrap();
+
+        assert_eq!(
+            knows_since(&mut db),
+            vec![
+                ("Alice".to_string(), "Carol".to_string(), 2005),
+                ("Carol".to_string(), "Alice".to_string(), 2015),
+            ]
+        );
+        // Nodes are untouched
+        assert_eq!(names(&mut db), vec!["Alice", "Bob", "Carol"]);
+
+        // Backward edges are gone as well: Bob has no relationships left
+        db.execute("MATCH (p:Person {name: 'Bob'}) DELETE p")
+            .unwrap();
+        assert_eq!(names(&mut db), vec!["Alice", "Carol"]);
+    }
+
+    #[test]
+    fn test_delete_relationship_and_endpoint_together() {
+        let mut db = Database::new();
+        setup_people(&mut db);
+
+        // Bob's only relationship is deleted in the same statement
+        db.execute("MATCH (a:Person)-[r:Knows]->(b:Person {name: 'Bob'}) DELETE r, b")
+            .unwrap();
+
+        assert_eq!(names(&mut db), vec!["Alice", "Carol"]);
+        assert_eq!(knows_count(&mut db), 0);
+    }
+
+    #[test]
+    fn test_delete_relationships_replayed_from_wal_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_knows_history(&mut db);
+            db.checkpoint().unwrap();
+
+            db.execute("MATCH (a:Person {name: 'Alice'})-[r:Knows]->(b:Person) DELETE r")
+                .unwrap();
+
+            // Simulate crash: drop without close (WAL not truncated)
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(
+                knows_since(&mut db),
+                vec![
+                    ("Bob".to_string(), "Carol".to_string(), 1999),
+                    ("Carol".to_string(), "Alice".to_string(), 2015),
+                ]
+            );
+
+            // Relationships created after recovery get fresh IDs and properties
+            db.execute(
+                "MATCH (a:Person {name: 'Bob'}), (b:Person {name: 'Alice'}) CREATE (a)-[:Knows {since: 2024}]->(b)",
+            )
+            .unwrap();
+            assert_eq!(knows_since(&mut db).last().unwrap().2, 2024);
+        }
+    }
+
+    #[test]
+    fn test_wal_replayed_after_crash_between_save_and_truncate() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+        let wal_path = db_path.join("wal.log");
+        let saved_wal = temp_dir.path().join("wal.saved");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_people(&mut db);
+            db.checkpoint().unwrap();
+
+            db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) SET r.since = 2020")
+                .unwrap();
+            db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) DELETE r")
+                .unwrap();
+            db.execute("MATCH (p:Person) WHERE p.age > 90 SET p.age = 96")
+                .unwrap();
+            db.execute("MATCH (p:Person) WHERE p.name = 'Bob' DELETE p")
+                .unwrap();
+
+            // The checkpoint saves every change, then the crash happens before
+            // the WAL is truncated, so replay sees changes already applied
+            std::fs::copy(&wal_path, &saved_wal).unwrap();
+            db.checkpoint().unwrap();
+            std::mem::forget(db);
+            std::fs::copy(&saved_wal, &wal_path).unwrap();
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(names(&mut db), vec!["Alice", "Carol"]);
+            assert_eq!(knows_count(&mut db), 0);
+            let result = db
+                .execute("MATCH (p:Person) WHERE p.name = 'Carol' RETURN p.age")
+                .unwrap();
+            assert_eq!(result.get_row(0).unwrap().get("p.age"), Some(&Value::Int64(96)));
+        }
+    }
+}
+
+// =============================================================================
+// MERGE Tests
+// =============================================================================
+
+mod merge_tests {
+    use ruzu::{Database, DatabaseConfig, RuzuError, Value};
+    use tempfile::TempDir;
+
+    fn setup_schema(db: &mut Database) {
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, seen INT64, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+    }
+
+    const UPSERT_ALICE: &str = "MERGE (p:Person {name: 'Alice'}) \
+        ON CREATE SET p.age = 30, p.seen = 1 \
+        ON MATCH SET p.seen = p.seen + 1";
+
+    fn person(db: &mut Database, name: &str) -> ruzu::Row {
+        let result = db
+            .execute(&format!(
+                "MATCH (p:Person) WHERE p.name = '{name}' RETURN p.name, p.age, p.seen"
+            ))
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        result.get_row(0).unwrap().clone()
+    }
+
+    fn knows(db: &mut Database) -> Vec<Value> {
+        db.execute("MATCH (a:Person)-[r:Knows]->(b:Person) RETURN r.since")
+            .unwrap()
+            .rows
+            .iter()
+            .map(|row| row.get("r.since").cloned().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_node_creates_then_matches() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+
+        db.execute(UPSERT_ALICE).unwrap();
+        let alice = person(&mut db, "Alice");
+        assert_eq!(alice.get("p.age"), Some(&Value::Int64(30)));
+        assert_eq!(alice.get("p.seen"), Some(&Value::Int64(1)));
+
+        db.execute(UPSERT_ALICE).unwrap();
+        db.execute(UPSERT_ALICE).unwrap();
+        let alice = person(&mut db, "Alice");
+        assert_eq!(alice.get("p.age"), Some(&Value::Int64(30)));
+        assert_eq!(alice.get("p.seen"), Some(&Value::Int64(3)));
+
+        let result = db.execute("MATCH (p:Person) RETURN COUNT(*)").unwrap();
+        assert_eq!(result.get_row(0).unwrap().get("COUNT(*)"), Some(&Value::Int64(1)));
+    }
+
+    #[test]
+    fn test_merge_node_without_actions_leaves_unset_columns_null() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+
+        db.execute("MERGE (p:Person {name: 'Bob'})").unwrap();
+        db.execute("MERGE (p:Person {name: 'Bob'})").unwrap();
+
+        let bob = person(&mut db, "Bob");
+        assert_eq!(bob.get("p.age"), Some(&Value::Null));
+        let result = db.execute("MATCH (p:Person) RETURN COUNT(*)").unwrap();
+        assert_eq!(result.get_row(0).unwrap().get("COUNT(*)"), Some(&Value::Int64(1)));
+    }
+
+    #[test]
+    fn test_merge_node_on_non_key_property_matches_all() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+        db.execute("CREATE (:Person {name: 'Alice', age: 30, seen: 0})")
+            .unwrap();
+        db.execute("CREATE (:Person {name: 'Bob', age: 30, seen: 0})")
+            .unwrap();
+
+        db.execute("MERGE (p:Person {age: 30}) ON MATCH SET p.seen = p.seen + 5")
+            .unwrap();
+
+        assert_eq!(person(&mut db, "Alice").get("p.seen"), Some(&Value::Int64(5)));
+        assert_eq!(person(&mut db, "Bob").get("p.seen"), Some(&Value::Int64(5)));
+    }
+
+    #[test]
+    fn test_merge_relationship_only_creates_once() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+        db.execute("MERGE (p:Person {name: 'Alice'})").unwrap();
+        db.execute("MERGE (p:Person {name: 'Bob'})").unwrap();
+
+        let query = "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) \
+            MERGE (a)-[r:Knows]->(b) \
+            ON CREATE SET r.since = 2020 \
+            ON MATCH SET r.since = r.since + 1";
+
+        db.execute(query).unwrap();
+        assert_eq!(knows(&mut db), vec![Value::Int64(2020)]);
+
+        db.execute(query).unwrap();
+        assert_eq!(knows(&mut db), vec![Value::Int64(2021)]);
+
+        // The reverse direction is a different relationship
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) MERGE (b)-[:Knows]->(a)",
+        )
+        .unwrap();
+        assert_eq!(knows(&mut db).len(), 2);
+    }
+
+    #[test]
+    fn test_merge_relationship_with_properties() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+        db.execute("MERGE (p:Person {name: 'Alice'})").unwrap();
+        db.execute("MERGE (p:Person {name: 'Bob'})").unwrap();
+
+        let merge = |since: i64| {
+            format!(
+                "MATCH (a:Person {{name: 'Alice'}}), (b:Person {{name: 'Bob'}}) MERGE (a)-[:Knows {{since: {since}}}]->(b)"
+            )
+        };
+        db.execute(&merge(2010)).unwrap();
+        db.execute(&merge(2010)).unwrap();
+        db.execute(&merge(2015)).unwrap();
+
+        let mut since = knows(&mut db);
+        since.sort_by(|a, b| a.compare(b).unwrap());
+        assert_eq!(since, vec![Value::Int64(2010), Value::Int64(2015)]);
+    }
+
+    #[test]
+    fn test_merge_invalid_targets() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+
+        assert!(matches!(
+            db.execute("MERGE (p:Person {nickname: 'Al'})"),
+            Err(RuzuError::BindError(_))
+        ));
+        assert!(matches!(
+            db.execute("MERGE (p:Person {name: 'Al'}) ON CREATE SET q.age = 1"),
+            Err(RuzuError::BindError(_))
+        ));
+        assert!(matches!(
+            db.execute("MERGE (p:Person {name: 'Al'}) ON CREATE SET p.age = 'old'"),
+            Err(RuzuError::TypeError { .. })
+        ));
+        let result = db.execute("MATCH (p:Person) RETURN COUNT(*)").unwrap();
+        assert_eq!(result.get_row(0).unwrap().get("COUNT(*)"), Some(&Value::Int64(0)));
+    }
+
+    #[test]
+    fn test_set_with_arithmetic() {
+        let mut db = Database::new();
+        setup_schema(&mut db);
+        db.execute("CREATE (:Person {name: 'Alice', age: 30, seen: 4})")
+            .unwrap();
+
+        db.execute("MATCH (p:Person) SET p.age = p.age + 2 * 3, p.seen = (p.seen + 1) * p.age")
+            .unwrap();
+        let alice = person(&mut db, "Alice");
+        assert_eq!(alice.get("p.age"), Some(&Value::Int64(36)));
+        // Later assignments see earlier ones
+        assert_eq!(alice.get("p.seen"), Some(&Value::Int64(180)));
+
+        assert!(db.execute("MATCH (p:Person) SET p.age = p.age / 0").is_err());
+        assert_eq!(person(&mut db, "Alice").get("p.age"), Some(&Value::Int64(36)));
+    }
+
+    #[test]
+    fn test_merge_replayed_from_wal_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_schema(&mut db);
+            db.checkpoint().unwrap();
+
+            db.execute(UPSERT_ALICE).unwrap();
+            db.execute(UPSERT_ALICE).unwrap();
+            db.execute("MERGE (p:Person {name: 'Bob'})").unwrap();
+            db.execute(
+                "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) MERGE (a)-[r:Knows]->(b) ON CREATE SET r.since = 2024",
+            )
+            .unwrap();
+
+            // Simulate crash: drop without close (WAL not truncated)
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            assert_eq!(person(&mut db, "Alice").get("p.seen"), Some(&Value::Int64(2)));
+            assert_eq!(knows(&mut db), vec![Value::Int64(2024)]);
+        }
+    }
+
+    #[test]
+    fn test_multi_row_relationship_merge_is_all_or_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_schema(&mut db);
+            for name in ["Alice", "Bob", "Carol"] {
+                db.execute(&format!("CREATE (:Person {{name: '{name}', age: 1, seen: 0}})"))
+                    .unwrap();
+            }
+            db.execute(
+                "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) \
+                 CREATE (a)-[:Knows {since: 2000}]->(b)",
+            )
+            .unwrap();
+            db.checkpoint().unwrap();
+
+            // Renaming both new sources to the same key fails after the
+            // ON MATCH update and the relationships have been worked out
+            let merge = |on_create: &str| {
+                format!(
+                    "MATCH (a:Person), (b:Person {{name: 'Bob'}}) MERGE (a)-[r:Knows]->(b) \
+                     ON CREATE SET r.since = 2024, {on_create} ON MATCH SET a.seen = 1"
+                )
+            };
+            assert!(db.execute(&merge("a.name = 'Zed'")).is_err());
+            assert_eq!(knows(&mut db), vec![Value::Int64(2000)]);
+            assert_eq!(person(&mut db, "Alice").get("p.seen"), Some(&Value::Int64(0)));
+
+            db.execute(&merge("a.seen = 2")).unwrap();
+
+            // Simulate crash: drop without close (WAL not truncated)
+            std::mem::forget(db);
+        }
+
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            let mut since = knows(&mut db);
+            since.sort_by(|a, b| a.compare(b).unwrap());
+            assert_eq!(since, vec![Value::Int64(2000), Value::Int64(2024), Value::Int64(2024)]);
+            for (name, seen) in [("Alice", 1), ("Bob", 2), ("Carol", 2)] {
+                assert_eq!(person(&mut db, name).get("p.seen"), Some(&Value::Int64(seen)));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// WITH Clause Tests
+// ============================================================================
+
+mod with_clause_tests {
+    use ruzu::{Database, RuzuError, Value};
+    use super::{column, strings};
+
+    /// Alice knows three people, Bob two, Carol one and Dave nobody.
+    fn setup_social_graph() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        for (name, age, city) in [
+            ("Alice", 30, "Oslo"),
+            ("Bob", 25, "Bergen"),
+            ("Carol", 35, "Oslo"),
+            ("Dave", 40, "Bergen"),
+        ] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, city: '{city}'}})"
+            ))
+            .unwrap();
+        }
+        for (src, dst, since) in [
+            ("Alice", "Bob", 2010),
+            ("Alice", "Carol", 2015),
+            ("Alice", "Dave", 2020),
+            ("Bob", "Carol", 2012),
+            ("Bob", "Dave", 2018),
+            ("Carol", "Dave", 2021),
+        ] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) \
+                 CREATE (a)-[:Knows {{since: {since}}}]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_with_aggregate_filters_groups() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person)-[:Knows]->(f:Person) \
+                 WITH p, COUNT(*) AS c WHERE c > 1 \
+                 RETURN p.name, c ORDER BY c DESC",
+            )
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["p.name", "c"]);
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob"]));
+        assert_eq!(column(&result, "c"), vec![Value::Int64(3), Value::Int64(2)]);
+    }
+
+    #[test]
+    fn test_with_groups_on_property_alias() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH p.city AS city, COUNT(*) AS people, AVG(p.age) AS age \
+                 RETURN city, people, age ORDER BY city",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "city"), strings(&["Bergen", "Oslo"]));
+        assert_eq!(column(&result, "people"), vec![Value::Int64(2), Value::Int64(2)]);
+        assert_eq!(column(&result, "age"), vec![Value::Float64(32.5), Value::Float64(32.5)]);
+    }
+
+    #[test]
+    fn test_with_order_by_and_limit_before_return() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH p ORDER BY p.age DESC LIMIT 2 \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "p.name"), strings(&["Carol", "Dave"]));
+    }
+
+    #[test]
+    fn test_with_alias_renames_node_variable() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute(
+                "MATCH (a:Person)-[r:Knows]->(b:Person) WHERE r.since > 2016 \
+                 WITH b AS friend, r.since AS since \
+                 RETURN friend.name, since ORDER BY since",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "friend.name"), strings(&["Dave", "Dave", "Dave"]));
+        assert_eq!(
+            column(&result, "since"),
+            vec![Value::Int64(2018), Value::Int64(2020), Value::Int64(2021)]
+        );
+    }
+
+    #[test]
+    fn test_chained_with_clauses() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person)-[:Knows]->(f:Person) \
+                 WITH f, COUNT(*) AS followers \
+                 WITH f.name AS name, f.city AS city, followers WHERE followers > 1 \
+                 RETURN name, city, followers ORDER BY followers DESC",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "name"), strings(&["Dave", "Carol"]));
+        assert_eq!(column(&result, "city"), strings(&["Bergen", "Oslo"]));
+        assert_eq!(column(&result, "followers"), vec![Value::Int64(3), Value::Int64(2)]);
+    }
+
+    #[test]
+    fn test_with_skip_and_aggregate_in_return() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH p ORDER BY p.age SKIP 1 \
+                 RETURN COUNT(*) AS remaining, MIN(p.age) AS youngest",
+            )
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["remaining", "youngest"]);
+        assert_eq!(column(&result, "remaining"), vec![Value::Int64(3)]);
+        assert_eq!(column(&result, "youngest"), vec![Value::Int64(30)]);
+    }
+
+    #[test]
+    fn test_with_count_over_no_rows() {
+        let mut db = setup_social_graph();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age > 100 WITH COUNT(*) AS c RETURN c")
+            .unwrap();
+
+        assert_eq!(column(&result, "c"), vec![Value::Int64(0)]);
+    }
+
+    #[test]
+    fn test_with_hides_variables_not_carried_over() {
+        let mut db = setup_social_graph();
+        let err = db
+            .execute("MATCH (a:Person)-[:Knows]->(b:Person) WITH a RETURN b.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(ref msg) if msg.contains('b')), "{err}");
+
+        let err = db
+            .execute("MATCH (p:Person) WITH p.name RETURN p.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(ref msg) if msg.contains("AS")), "{err}");
+
+        let err = db
+            .execute("MATCH (p:Person) WITH p RETURN p.salary")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+    }
+}
+
+// =============================================================================
+// OPTIONAL MATCH Tests
+// =============================================================================
+
+mod optional_match_tests {
+    use ruzu::{Database, RuzuError, Value};
+    use super::s;
+
+    /// Alice and Bob work at Acme, Carol at Initech; Dave has no employer.
+    fn setup_employment_graph() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, age INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Company(name STRING, size INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE WorksAt(FROM Person TO Company, since INT64)")
+            .unwrap();
+        for (name, age) in [("Alice", 30), ("Bob", 25), ("Carol", 35), ("Dave", 40)] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}', age: {age}}})"))
+                .unwrap();
+        }
+        for (name, size) in [("Acme", 500), ("Initech", 8)] {
+            db.execute(&format!("CREATE (:Company {{name: '{name}', size: {size}}})"))
+                .unwrap();
+        }
+        for (person, company, since) in [
+            ("Alice", "Acme", 2015),
+            ("Bob", "Acme", 2020),
+            ("Carol", "Initech", 2018),
+        ] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{person}'}}), (b:Company {{name: '{company}'}}) \
+                 CREATE (a)-[:WorksAt {{since: {since}}}]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn pairs(result: &ruzu::QueryResult, left: &str, right: &str) -> Vec<(Value, Value)> {
+        result
+            .rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get(left).cloned().unwrap_or(Value::Null),
+                    row.get(right).cloned().unwrap_or(Value::Null),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_optional_match_returns_nulls_for_missing() {
+        let mut db = setup_employment_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 RETURN p.name, c.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(result.columns, vec!["p.name", "c.name"]);
+        assert_eq!(
+            pairs(&result, "p.name", "c.name"),
+            vec![
+                (s("Alice"), s("Acme")),
+                (s("Bob"), s("Acme")),
+                (s("Carol"), s("Initech")),
+                (s("Dave"), Value::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optional_match_where_applies_inside_pattern() {
+        let mut db = setup_employment_graph();
+        // The WHERE restricts the optional match; people without a large
+        // employer are still returned.
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 WHERE c.size > 100 RETURN p.name, c.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(
+            pairs(&result, "p.name", "c.name"),
+            vec![
+                (s("Alice"), s("Acme")),
+                (s("Bob"), s("Acme")),
+                (s("Carol"), Value::Null),
+                (s("Dave"), Value::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optional_match_rel_properties_null() {
+        let mut db = setup_employment_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[w:WorksAt]->(c:Company) \
+                 RETURN p.name, w.since ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(
+            pairs(&result, "p.name", "w.since"),
+            vec![
+                (s("Alice"), Value::Int64(2015)),
+                (s("Bob"), Value::Int64(2020)),
+                (s("Carol"), Value::Int64(2018)),
+                (s("Dave"), Value::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optional_match_count_skips_nulls() {
+        let mut db = setup_employment_graph();
+        let result = db
+            .execute(
+                "MATCH (c:Company) OPTIONAL MATCH (p:Person)-[:WorksAt]->(c) \
+                 WHERE p.age > 28 WITH c, COUNT(p.name) AS n RETURN c.name, n ORDER BY c.name",
+            )
+            .unwrap();
+        assert_eq!(
+            pairs(&result, "c.name", "n"),
+            vec![(s("Acme"), Value::Int64(1)), (s("Initech"), Value::Int64(1))]
+        );
+
+        let result = db
+            .execute(
+                "MATCH (c:Company) OPTIONAL MATCH (p:Person)-[:WorksAt]->(c) \
+                 WHERE p.age > 100 RETURN COUNT(*), COUNT(p.name)",
+            )
+            .unwrap();
+        assert_eq!(result.rows[0].get("COUNT(*)"), Some(&Value::Int64(2)));
+        assert_eq!(result.rows[0].get("COUNT(p.name)"), Some(&Value::Int64(0)));
+    }
+
+    #[test]
+    fn test_optional_match_after_null_binding() {
+        let mut db = setup_employment_graph();
+        // A variable bound to NULL by one OPTIONAL MATCH never matches in the next.
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Dave' \
+                 OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 OPTIONAL MATCH (q:Person)-[:WorksAt]->(c) \
+                 RETURN p.name, q.name",
+            )
+            .unwrap();
+        assert_eq!(pairs(&result, "p.name", "q.name"), vec![(s("Dave"), Value::Null)]);
+    }
+
+    #[test]
+    fn test_optional_match_joins_colleagues() {
+        let mut db = setup_employment_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Alice' \
+                 OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 OPTIONAL MATCH (q:Person)-[:WorksAt]->(c) \
+                 RETURN q.name ORDER BY q.name",
+            )
+            .unwrap();
+        let names: Vec<_> = result.rows.iter().map(|r| r.get("q.name").cloned()).collect();
+        assert_eq!(names, vec![Some(s("Alice")), Some(s("Bob"))]);
+    }
+
+    #[test]
+    fn test_optional_match_unlabeled_new_variable_rejected() {
+        let mut db = setup_employment_graph();
+        let err = db
+            .execute("MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c) RETURN c.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+    }
+
+    #[test]
+    fn test_optional_match_label_mismatch_rejected() {
+        let mut db = setup_employment_graph();
+        let err = db
+            .execute("MATCH (p:Person) OPTIONAL MATCH (p:Company)-[:WorksAt]->(c:Company) RETURN c.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+
+        let err = db
+            .execute("MATCH (c:Company) OPTIONAL MATCH (c)-[:WorksAt]->(d:Company) RETURN d.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::SchemaError(_) | RuzuError::BindError(_)), "{err}");
+    }
+}
+
+// =============================================================================
+// UNWIND and List Literal Tests
+// =============================================================================
+
+mod unwind_tests {
+    use ruzu::{Database, RuzuError, Value};
+    use super::column;
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, age INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person)")
+            .unwrap();
+        for (name, age) in [("Alice", 30), ("Bob", 25), ("Carol", 35)] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}', age: {age}}})"))
+                .unwrap();
+        }
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) CREATE (a)-[:Knows]->(b)",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_unwind_literal_list() {
+        let mut db = setup_people();
+        let result = db.execute("UNWIND [3, 1, 2] AS x RETURN x").unwrap();
+        assert_eq!(result.columns, vec!["x"]);
+        assert_eq!(
+            column(&result, "x"),
+            vec![Value::Int64(3), Value::Int64(1), Value::Int64(2)]
+        );
+    }
+
+    #[test]
+    fn test_unwind_with_order_and_limit() {
+        let mut db = setup_people();
+        let result = db
+            .execute("UNWIND ['b', 'c', 'a'] AS s RETURN s ORDER BY s DESC LIMIT 2")
+            .unwrap();
+        assert_eq!(
+            column(&result, "s"),
+            vec![Value::String("c".into()), Value::String("b".into())]
+        );
+    }
+
+    #[test]
+    fn test_unwind_batch_lookup() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "UNWIND ['Alice', 'Carol', 'Zed'] AS n MATCH (p:Person {name: n}) \
+                 RETURN n, p.age",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "n"), vec![Value::String("Alice".into()), Value::String("Carol".into())]);
+        assert_eq!(column(&result, "p.age"), vec![Value::Int64(30), Value::Int64(35)]);
+    }
+
+    #[test]
+    fn test_unwind_lookup_with_optional_match() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "UNWIND ['Alice', 'Carol'] AS n MATCH (p:Person {name: n}) \
+                 OPTIONAL MATCH (p)-[:Knows]->(f:Person) RETURN n, f.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "f.name"), vec![Value::String("Bob".into()), Value::Null]);
+    }
+
+    #[test]
+    fn test_unwind_aggregate() {
+        let mut db = setup_people();
+        let result = db
+            .execute("UNWIND [1, 2, 3, 4] AS x RETURN COUNT(*) AS n")
+            .unwrap();
+        assert_eq!(column(&result, "n"), vec![Value::Int64(4)]);
+    }
+
+    #[test]
+    fn test_unwind_after_match() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age > 28 UNWIND [1, 2] AS copy \
+                 RETURN p.name, copy ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(result.row_count(), 4);
+        assert_eq!(
+            column(&result, "copy"),
+            vec![Value::Int64(1), Value::Int64(2), Value::Int64(1), Value::Int64(2)]
+        );
+    }
+
+    #[test]
+    fn test_unwind_empty_and_nested_lists() {
+        let mut db = setup_people();
+        let result = db.execute("UNWIND [] AS x RETURN x").unwrap();
+        assert_eq!(result.row_count(), 0);
+
+        let result = db
+            .execute("UNWIND [[1, 2], [3]] AS xs UNWIND xs AS x RETURN x")
+            .unwrap();
+        assert_eq!(
+            column(&result, "x"),
+            vec![Value::Int64(1), Value::Int64(2), Value::Int64(3)]
+        );
+    }
+
+    #[test]
+    fn test_unwind_list_value_returned() {
+        let mut db = setup_people();
+        let result = db.execute("UNWIND [['a', 'b']] AS xs RETURN xs").unwrap();
+        assert_eq!(
+            column(&result, "xs"),
+            vec![Value::List(vec![Value::String("a".into()), Value::String("b".into())])]
+        );
+    }
+
+    #[test]
+    fn test_unwind_mixed_numeric_list() {
+        let mut db = setup_people();
+        let result = db.execute("UNWIND [1, 2.5] AS x RETURN x").unwrap();
+        assert_eq!(column(&result, "x"), vec![Value::Int64(1), Value::Float64(2.5)]);
+    }
+
+    #[test]
+    fn test_unwind_errors() {
+        let mut db = setup_people();
+        let err = db.execute("UNWIND [1, 'a'] AS x RETURN x").unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+
+        let err = db.execute("UNWIND missing AS x RETURN x").unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+
+        let err = db
+            .execute("MATCH (p:Person) UNWIND [1] AS p RETURN p.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+    }
+
+    #[test]
+    fn test_list_literal_rejected_for_scalar_column() {
+        let mut db = setup_people();
+        let err = db
+            .execute("CREATE (:Person {name: 'Dave', age: [1, 2]})")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::TypeError { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_unwind_parameter_list_create() {
+        let mut db = setup_people();
+        let params = std::collections::HashMap::from([(
+            "names".to_string(),
+            Value::List(vec![Value::String("Dave".into()), Value::String("Erin".into())]),
+        )]);
+        db.execute_with_params("UNWIND $names AS n CREATE (:Person {name: n, age: 20})", &params)
+            .unwrap();
+
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age = 20 RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(
+            column(&result, "p.name"),
+            vec![Value::String("Dave".into()), Value::String("Erin".into())]
+        );
+    }
+
+    #[test]
+    fn test_unwind_merge() {
+        let mut db = setup_people();
+        db.execute(
+            "UNWIND ['Alice', 'Dave', 'Dave'] AS n MERGE (p:Person {name: n}) \
+             ON CREATE SET p.age = 1 ON MATCH SET p.age = p.age + 10",
+        )
+        .unwrap();
+
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name, p.age ORDER BY p.name")
+            .unwrap();
+        assert_eq!(
+            column(&result, "p.name"),
+            vec![
+                Value::String("Alice".into()),
+                Value::String("Bob".into()),
+                Value::String("Carol".into()),
+                Value::String("Dave".into()),
+            ]
+        );
+        assert_eq!(
+            column(&result, "p.age"),
+            vec![Value::Int64(40), Value::Int64(25), Value::Int64(35), Value::Int64(11)]
+        );
+    }
+
+    #[test]
+    fn test_unwind_create_needs_label() {
+        let mut db = setup_people();
+        let err = db
+            .execute("UNWIND ['Dave'] AS n CREATE (p {name: n, age: 1})")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::ParseError { .. }), "{err}");
+    }
+}
+
+// =============================================================================
+// UNION Tests
+// =============================================================================
+
+mod union_tests {
+    use ruzu::{Database, RuzuError, Value};
+
+    fn setup_db() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Company(name STRING, rating FLOAT64, city STRING, PRIMARY KEY(name))")
+            .unwrap();
+        for (name, age, city) in [("Alice", 30, "Oslo"), ("Bob", 25, "Bergen"), ("Carol", 35, "Oslo")] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, city: '{city}'}})"
+            ))
+            .unwrap();
+        }
+        for (name, rating, city) in [("Acme", 4.5, "Oslo"), ("Initech", 2.0, "Tromso")] {
+            db.execute(&format!(
+                "CREATE (:Company {{name: '{name}', rating: {rating}, city: '{city}'}})"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn sorted_column(result: &ruzu::QueryResult, name: &str) -> Vec<String> {
+        let mut values: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| match row.get(name) {
+                Some(Value::String(s)) => s.clone(),
+                other => format!("{other:?}"),
+            })
+            .collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_union_removes_duplicates() {
+        let mut db = setup_db();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.city \
+                 UNION MATCH (c:Company) RETURN c.city",
+            )
+            .unwrap();
+        assert_eq!(result.columns, vec!["p.city"]);
+        assert_eq!(sorted_column(&result, "p.city"), vec!["Bergen", "Oslo", "Tromso"]);
+    }
+
+    #[test]
+    fn test_union_all_keeps_duplicates() {
+        let mut db = setup_db();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.city AS city \
+                 UNION ALL MATCH (c:Company) RETURN c.city AS city",
+            )
+            .unwrap();
+        assert_eq!(result.columns, vec!["city"]);
+        assert_eq!(
+            sorted_column(&result, "city"),
+            vec!["Bergen", "Oslo", "Oslo", "Oslo", "Tromso"]
+        );
+    }
+
+    #[test]
+    fn test_union_columns_named_after_first_query() {
+        let mut db = setup_db();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age > 28 RETURN p.name AS name, p.city \
+                 UNION MATCH (c:Company) RETURN c.name, c.city",
+            )
+            .unwrap();
+        assert_eq!(result.columns, vec!["name", "p.city"]);
+        assert_eq!(sorted_column(&result, "name"), vec!["Acme", "Alice", "Carol", "Initech"]);
+    }
+
+    #[test]
+    fn test_union_of_three_queries_with_limits() {
+        let mut db = setup_db();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name ORDER BY p.name LIMIT 1 \
+                 UNION MATCH (c:Company) RETURN c.name ORDER BY c.name DESC LIMIT 1 \
+                 UNION UNWIND ['Zed', 'Alice'] AS n RETURN n",
+            )
+            .unwrap();
+        let names: Vec<_> = result.rows.iter().map(|r| r.get("p.name").cloned()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some(Value::String("Alice".into())),
+                Some(Value::String("Initech".into())),
+                Some(Value::String("Zed".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_union_promotes_numeric_columns() {
+        let mut db = setup_db();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Bob' RETURN p.age \
+                 UNION ALL MATCH (c:Company) WHERE c.name = 'Acme' RETURN c.rating",
+            )
+            .unwrap();
+        let values: Vec<_> = result.rows.iter().map(|r| r.get("p.age").cloned()).collect();
+        assert_eq!(
+            values,
+            vec![Some(Value::Float64(25.0)), Some(Value::Float64(4.5))]
+        );
+    }
+
+    #[test]
+    fn test_union_with_aggregates() {
+        let mut db = setup_db();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN COUNT(*) AS n \
+                 UNION ALL MATCH (c:Company) RETURN COUNT(*) AS n",
+            )
+            .unwrap();
+        let values: Vec<_> = result.rows.iter().map(|r| r.get("n").cloned()).collect();
+        assert_eq!(values, vec![Some(Value::Int64(3)), Some(Value::Int64(2))]);
+    }
+
+    #[test]
+    fn test_union_column_count_mismatch() {
+        let mut db = setup_db();
+        let err = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name, p.age \
+                 UNION MATCH (c:Company) RETURN c.name",
+            )
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(ref msg) if msg.contains("same number of columns")), "{err}");
+    }
+
+    #[test]
+    fn test_union_type_mismatch() {
+        let mut db = setup_db();
+        let err = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name \
+                 UNION MATCH (c:Company) RETURN c.rating",
+            )
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(ref msg) if msg.contains("Type mismatch")), "{err}");
+    }
+
+    #[test]
+    fn test_union_mixed_with_union_all_rejected() {
+        let mut db = setup_db();
+        let err = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name \
+                 UNION MATCH (c:Company) RETURN c.name \
+                 UNION ALL MATCH (p:Person) RETURN p.city",
+            )
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::ParseError { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_union_binds_every_query_before_running() {
+        let mut db = setup_db();
+        let err = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name \
+                 UNION MATCH (c:Company) RETURN c.missing",
+            )
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+    }
+}
+
+// =============================================================================
+// Backward and undirected relationship pattern tests
+// =============================================================================
+
+mod rel_direction_tests {
+    use ruzu::{Database, RuzuError, Value};
+    use super::{column, strings};
+
+    /// Bob and Carol follow Alice, who follows Dave. Alice and Bob work at
+    /// Acme, Carol at Initech.
+    fn setup_follow_graph() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Company(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Follows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        db.execute("CREATE REL TABLE WorksAt(FROM Person TO Company)")
+            .unwrap();
+        for name in ["Alice", "Bob", "Carol", "Dave"] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}'}})"))
+                .unwrap();
+        }
+        for name in ["Acme", "Initech"] {
+            db.execute(&format!("CREATE (:Company {{name: '{name}'}})"))
+                .unwrap();
+        }
+        for (src, dst, since) in [
+            ("Bob", "Alice", 2019),
+            ("Carol", "Alice", 2021),
+            ("Alice", "Dave", 2020),
+        ] {
+            follow(&mut db, src, dst, since);
+        }
+        for (person, company) in [("Alice", "Acme"), ("Bob", "Acme"), ("Carol", "Initech")] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{person}'}}), (b:Company {{name: '{company}'}}) \
+                 CREATE (a)-[:WorksAt]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn follow(db: &mut Database, src: &str, dst: &str, since: i64) {
+        db.execute(&format!(
+            "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) \
+             CREATE (a)-[:Follows {{since: {since}}}]->(b)"
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_backward_pattern_finds_followers() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (me:Person {name: 'Alice'})<-[:Follows]-(f:Person) \
+                 RETURN f.name ORDER BY f.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "f.name"), strings(&["Bob", "Carol"]));
+    }
+
+    #[test]
+    fn test_backward_pattern_binds_relationship_properties() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (me:Person {name: 'Alice'})<-[r:Follows]-(f:Person) \
+                 WHERE r.since > 2020 RETURN f.name, r.since",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "f.name"), strings(&["Carol"]));
+        assert_eq!(column(&result, "r.since"), vec![Value::Int64(2021)]);
+    }
+
+    #[test]
+    fn test_undirected_pattern_matches_both_ways() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (me:Person {name: 'Alice'})-[:Follows]-(p:Person) \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Bob", "Carol", "Dave"]));
+    }
+
+    #[test]
+    fn test_undirected_self_loop_matched_once() {
+        let mut db = setup_follow_graph();
+        follow(&mut db, "Dave", "Dave", 2022);
+        let result = db
+            .execute(
+                "MATCH (d:Person {name: 'Dave'})-[:Follows]-(p:Person) \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Dave"]));
+    }
+
+    #[test]
+    fn test_undirected_pattern_across_tables() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (c:Company {name: 'Acme'})-[:WorksAt]-(p:Person) \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob"]));
+    }
+
+    #[test]
+    fn test_backward_pattern_with_wrong_labels_fails() {
+        let mut db = setup_follow_graph();
+        let err = db
+            .execute("MATCH (p:Person)<-[:WorksAt]-(c:Company) RETURN c.name")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::SchemaError(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_variable_length_backward() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (d:Person {name: 'Dave'})<-[:Follows*1..2]-(p:Person) \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob", "Carol"]));
+    }
+
+    #[test]
+    fn test_variable_length_undirected() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (b:Person {name: 'Bob'})-[:Follows*2..2]-(p:Person) \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol", "Dave"]));
+    }
+
+    #[test]
+    fn test_chain_does_not_reuse_a_relationship() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (p:Person {name: 'Bob'})-[:WorksAt]->(c:Company)<-[:WorksAt]-(q:Person) \
+                 RETURN q.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "q.name"), strings(&["Alice"]));
+    }
+
+    #[test]
+    fn test_backward_hop_in_optional_match_chain() {
+        let mut db = setup_follow_graph();
+        let result = db
+            .execute(
+                "MATCH (me:Person) WHERE me.name = 'Alice' \
+                 OPTIONAL MATCH (me)<-[:Follows]-(f:Person)-[:WorksAt]->(c:Company) \
+                 RETURN f.name, c.name ORDER BY f.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "f.name"), strings(&["Bob", "Carol"]));
+        assert_eq!(column(&result, "c.name"), strings(&["Acme", "Initech"]));
+    }
+
+    #[test]
+    fn test_delete_relationship_matched_backward() {
+        let mut db = setup_follow_graph();
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'})<-[r:Follows]-(b:Person {name: 'Bob'}) DELETE r",
+        )
+        .unwrap();
+
+        let result = db
+            .execute("MATCH (b:Person {name: 'Bob'})-[:Follows]->(p:Person) RETURN p.name")
+            .unwrap();
+        assert_eq!(result.row_count(), 0);
+        let result = db
+            .execute("MATCH (a:Person {name: 'Alice'})<-[:Follows]-(f:Person) RETURN f.name")
+            .unwrap();
+        assert_eq!(column(&result, "f.name"), strings(&["Carol"]));
+    }
+}
+
+// ============================================================================
+// Boolean WHERE Tests (AND / OR / NOT / parentheses)
+// ============================================================================
+
+mod boolean_where_tests {
+    use ruzu::{Database, RuzuError};
+    use super::{column, strings};
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, active BOOL, \
+             PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE NODE TABLE City(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE LivesIn(FROM Person TO City, since INT64)")
+            .unwrap();
+        for (name, age, city, active) in [
+            ("Alice", 35, "Oslo", true),
+            ("Bob", 28, "Oslo", true),
+            ("Carol", 41, "Bergen", true),
+            ("Dave", 25, "Bergen", false),
+            ("Eve", 45, "Oslo", false),
+        ] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, city: '{city}', active: {active}}})"
+            ))
+            .unwrap();
+        }
+        for city in ["Oslo", "Bergen"] {
+            db.execute(&format!("CREATE (:City {{name: '{city}'}})"))
+                .unwrap();
+        }
+        for (person, city, since) in [
+            ("Alice", "Oslo", 2010),
+            ("Bob", "Oslo", 2020),
+            ("Carol", "Bergen", 2015),
+        ] {
+            db.execute(&format!(
+                "MATCH (p:Person {{name: '{person}'}}), (c:City {{name: '{city}'}}) \
+                 CREATE (p)-[:LivesIn {{since: {since}}}]->(c)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_where_and() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age > 30 AND p.city = 'Oslo' \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Eve"]));
+    }
+
+    #[test]
+    fn test_where_or() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age < 26 OR p.age > 44 \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Dave", "Eve"]));
+    }
+
+    #[test]
+    fn test_where_not_boolean_property() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WHERE NOT p.active RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Dave", "Eve"]));
+    }
+
+    #[test]
+    fn test_where_parenthesized_request_example() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE (p.age > 30 AND p.city = 'Oslo') OR NOT p.active \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Dave", "Eve"]));
+    }
+
+    #[test]
+    fn test_where_and_binds_tighter_than_or() {
+        let mut db = setup_people();
+        // Parsed as p.city = 'Bergen' OR (p.age > 40 AND p.active)
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.city = 'Bergen' OR p.age > 40 AND p.active \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol", "Dave"]));
+
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE (p.city = 'Bergen' OR p.age > 40) AND p.active \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol"]));
+    }
+
+    #[test]
+    fn test_where_keywords_are_case_insensitive() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) where not (p.city = 'Oslo' or p.age < 30) and p.active \
+                 RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol"]));
+    }
+
+    #[test]
+    fn test_where_on_relationship_pattern() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person)-[r:LivesIn]->(c:City) \
+                 WHERE r.since < 2012 OR (c.name = 'Oslo' AND p.age < 30) \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob"]));
+    }
+
+    #[test]
+    fn test_with_where_boolean() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH p.city AS city, count(*) AS n \
+                 WHERE n > 2 OR city = 'Bergen' RETURN city ORDER BY city",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "city"), strings(&["Bergen", "Oslo"]));
+    }
+
+    #[test]
+    fn test_not_of_null_is_not_true() {
+        let mut db = setup_people();
+        // Dave and Eve have no LivesIn edge, so c.name is NULL for them and
+        // NOT (c.name = 'Oslo') is unknown rather than true.
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:LivesIn]->(c:City) \
+                 WITH p, c WHERE NOT c.name = 'Oslo' RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol"]));
+
+        // ... but OR with a true operand is still true
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:LivesIn]->(c:City) \
+                 WITH p, c WHERE c.name = 'Oslo' OR p.age < 26 \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob", "Dave"]));
+    }
+
+    #[test]
+    fn test_set_with_boolean_where() {
+        let mut db = setup_people();
+        db.execute("MATCH (p:Person) WHERE p.city = 'Oslo' AND NOT p.active SET p.active = true")
+            .unwrap();
+        let result = db
+            .execute("MATCH (p:Person) WHERE NOT p.active RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Dave"]));
+    }
+
+    #[test]
+    fn test_delete_with_boolean_where() {
+        let mut db = setup_people();
+        db.execute("MATCH (p:Person) WHERE p.age < 26 OR p.age > 44 DELETE p")
+            .unwrap();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob", "Carol"]));
+    }
+
+    #[test]
+    fn test_non_boolean_predicate_is_rejected() {
+        let mut db = setup_people();
+        let err = db
+            .execute("MATCH (p:Person) WHERE p.age AND p.active RETURN p.name")
+            .unwrap_err();
+        assert!(
+            matches!(err, RuzuError::BindError(_) | RuzuError::TypeError { .. }),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn test_or_prefix_property_is_not_a_keyword() {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Item(name STRING, orders INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE (:Item {name: 'a', orders: 3})").unwrap();
+        db.execute("CREATE (:Item {name: 'b', orders: 1})").unwrap();
+        let result = db
+            .execute("MATCH (i:Item) WHERE i.orders > 2 RETURN i.name ORDER BY i.name")
+            .unwrap();
+        assert_eq!(column(&result, "i.name"), strings(&["a"]));
+    }
+
+    #[test]
+    fn test_boolean_literal_predicates() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age > 30 OR true RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(
+            column(&result, "p.name"),
+            strings(&["Alice", "Bob", "Carol", "Dave", "Eve"])
+        );
+
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age > 40 AND NOT false RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol", "Eve"]));
+
+        let result = db
+            .execute("MATCH (p:Person) WITH p WHERE false OR p.age < 26 RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Dave"]));
+    }
+
+    #[test]
+    fn test_boolean_value_expression_predicate() {
+        let mut db = setup_people();
+        db.execute("MATCH (p:Person {name: 'Eve'}) SET p.active = NULL")
+            .unwrap();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE coalesce(p.active, true) AND p.city = 'Oslo' \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob", "Eve"]));
+    }
+}
+
+// ============================================================================
+// Expression Tests (property-to-property comparisons, arithmetic in RETURN)
+// ============================================================================
+
+mod expression_tests {
+    use ruzu::{Database, RuzuError, Value};
+    use super::{column, strings};
+
+    fn setup_accounts() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Account(id STRING, balance INT64, PRIMARY KEY(id))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Pays(FROM Account TO Account, amount INT64)")
+            .unwrap();
+        for (id, balance) in [("a", 100), ("b", 40), ("c", 10)] {
+            db.execute(&format!("CREATE (:Account {{id: '{id}', balance: {balance}}})"))
+                .unwrap();
+        }
+        for (src, dst, amount) in [("a", "b", 50), ("b", "c", 5), ("c", "a", 1)] {
+            db.execute(&format!(
+                "MATCH (s:Account {{id: '{src}'}}), (d:Account {{id: '{dst}'}}) \
+                 CREATE (s)-[:Pays {{amount: {amount}}}]->(d)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn setup_products() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Product(name STRING, price FLOAT64, qty INT64, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        for (name, price, qty) in [("pen", 1.5, 10), ("ink", 4.0, 3), ("pad", 2.25, 4)] {
+            db.execute(&format!(
+                "CREATE (:Product {{name: '{name}', price: {price}, qty: {qty}}})"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_where_property_to_property_with_arithmetic() {
+        let mut db = setup_accounts();
+        let result = db
+            .execute(
+                "MATCH (a:Account)-[:Pays]->(b:Account) WHERE a.balance > b.balance * 2 \
+                 RETURN a.id, b.id ORDER BY a.id",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.id"), strings(&["a", "b"]));
+        assert_eq!(column(&result, "b.id"), strings(&["b", "c"]));
+    }
+
+    #[test]
+    fn test_where_relationship_property_against_node_property() {
+        let mut db = setup_accounts();
+        let result = db
+            .execute(
+                "MATCH (a:Account)-[r:Pays]->(b:Account) WHERE r.amount >= b.balance \
+                 RETURN a.id",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.id"), strings(&["a"]));
+    }
+
+    #[test]
+    fn test_where_arithmetic_on_both_sides() {
+        let mut db = setup_accounts();
+        let result = db
+            .execute(
+                "MATCH (a:Account) WHERE a.balance - 30 > 2 * 5 RETURN a.id ORDER BY a.id",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.id"), strings(&["a"]));
+
+        let result = db
+            .execute("MATCH (a:Account) WHERE 50 < a.balance + 20 RETURN a.id ORDER BY a.id")
+            .unwrap();
+        assert_eq!(column(&result, "a.id"), strings(&["a", "b"]));
+    }
+
+    #[test]
+    fn test_where_int_and_float_expressions_compare() {
+        let mut db = setup_products();
+        let result = db
+            .execute("MATCH (p:Product) WHERE p.price * p.qty > 10 RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["ink", "pen"]));
+    }
+
+    #[test]
+    fn test_return_expression_with_alias() {
+        let mut db = setup_products();
+        let result = db
+            .execute("MATCH (p:Product) RETURN p.name, p.price * p.qty AS total ORDER BY p.name")
+            .unwrap();
+        assert_eq!(result.columns, vec!["p.name", "total"]);
+        assert_eq!(
+            column(&result, "total"),
+            vec![Value::Float64(12.0), Value::Float64(9.0), Value::Float64(15.0)]
+        );
+    }
+
+    #[test]
+    fn test_return_expression_without_alias() {
+        let mut db = setup_products();
+        let result = db
+            .execute("MATCH (p:Product) WHERE p.name = 'pad' RETURN (p.qty + 1) * 2")
+            .unwrap();
+        assert_eq!(result.columns, vec!["(p.qty + 1) * 2"]);
+        assert_eq!(column(&result, "(p.qty + 1) * 2"), vec![Value::Int64(10)]);
+    }
+
+    #[test]
+    fn test_order_by_expression_alias() {
+        let mut db = setup_products();
+        let result = db
+            .execute("MATCH (p:Product) RETURN p.name, p.price * p.qty AS total ORDER BY total DESC")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["pen", "ink", "pad"]));
+    }
+
+    #[test]
+    fn test_group_by_expression() {
+        let mut db = setup_products();
+        let result = db
+            .execute(
+                "MATCH (p:Product) RETURN p.qty % 2 AS parity, COUNT(*) AS n ORDER BY parity",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "parity"), vec![Value::Int64(0), Value::Int64(1)]);
+        assert_eq!(column(&result, "n"), vec![Value::Int64(2), Value::Int64(1)]);
+    }
+
+    #[test]
+    fn test_with_alias_in_arithmetic() {
+        let mut db = setup_accounts();
+        let result = db
+            .execute(
+                "MATCH (a:Account) WITH a, a.balance / 10 AS tenth \
+                 WHERE tenth * 10 = a.balance AND tenth > 1 \
+                 RETURN a.id, tenth + 1 AS next ORDER BY a.id",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.id"), strings(&["a", "b"]));
+        assert_eq!(column(&result, "next"), vec![Value::Int64(11), Value::Int64(5)]);
+    }
+
+    #[test]
+    fn test_unwind_alias_in_expressions() {
+        let mut db = Database::new();
+        let result = db
+            .execute("UNWIND [1, 2, 3, 4] AS x WITH x WHERE x * x > x + 2 RETURN x * 10 AS y")
+            .unwrap();
+        assert_eq!(column(&result, "y"), vec![Value::Int64(30), Value::Int64(40)]);
+    }
+
+    #[test]
+    fn test_null_arithmetic_comparison_is_not_true() {
+        let mut db = setup_accounts();
+        let result = db
+            .execute(
+                "MATCH (a:Account) OPTIONAL MATCH (a)-[r:Pays]->(b:Account {id: 'c'}) \
+                 WITH a, r WHERE r.amount + 1 > 0 OR a.id = 'c' RETURN a.id ORDER BY a.id",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "a.id"), strings(&["b", "c"]));
+    }
+
+    #[test]
+    fn test_set_from_other_property() {
+        let mut db = setup_accounts();
+        db.execute(
+            "MATCH (a:Account)-[r:Pays]->(b:Account) WHERE r.amount < b.balance \
+             SET b.balance = b.balance - r.amount",
+        )
+        .unwrap();
+        let result = db
+            .execute("MATCH (a:Account) RETURN a.balance ORDER BY a.id")
+            .unwrap();
+        assert_eq!(
+            column(&result, "a.balance"),
+            vec![Value::Int64(99), Value::Int64(40), Value::Int64(5)]
+        );
+    }
+
+    #[test]
+    fn test_set_value_rejects_unbound_variable() {
+        let mut db = setup_accounts();
+        let err = db
+            .execute("MATCH (a:Account) SET a.balance = bonus")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "unexpected error: {err:?}");
+    }
+
+    #[test]
+    fn test_return_expression_on_string_is_type_error() {
+        let mut db = setup_accounts();
+        let err = db
+            .execute("MATCH (a:Account) RETURN a.id * 2 AS x")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::TypeError { .. }), "unexpected error: {err:?}");
+    }
+
+    #[test]
+    fn test_return_expression_unknown_property() {
+        let mut db = setup_accounts();
+        let err = db
+            .execute("MATCH (a:Account) RETURN a.nope + 1 AS x")
+            .unwrap_err();
+        assert!(matches!(err, RuzuError::BindError(_)), "unexpected error: {err:?}");
+    }
+
+    #[test]
+    fn test_unary_minus() {
+        let mut db = setup_products();
+        let result = db
+            .execute("MATCH (p:Product) RETURN p.name, -p.qty, -(p.price * 2) ORDER BY -p.qty")
+            .unwrap();
+        assert_eq!(result.columns, vec!["p.name", "-p.qty", "-(p.price * 2)"]);
+        assert_eq!(column(&result, "p.name"), strings(&["pen", "pad", "ink"]));
+        assert_eq!(
+            column(&result, "-p.qty"),
+            vec![Value::Int64(-10), Value::Int64(-4), Value::Int64(-3)]
+        );
+        assert_eq!(
+            column(&result, "-(p.price * 2)"),
+            vec![Value::Float64(-3.0), Value::Float64(-4.5), Value::Float64(-8.0)]
+        );
+
+        let result = db
+            .execute("MATCH (p:Product) WHERE 5 - -p.qty > 10 RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["pen"]));
+    }
+
+    #[test]
+    fn test_set_unary_minus() {
+        let mut db = setup_accounts();
+        db.execute("MATCH (a:Account {id: 'b'}) SET a.balance = -a.balance")
+            .unwrap();
+        let result = db
+            .execute("MATCH (a:Account {id: 'b'}) RETURN a.balance")
+            .unwrap();
+        assert_eq!(column(&result, "a.balance"), vec![Value::Int64(-40)]);
+    }
+
+    #[test]
+    fn test_unary_minus_on_string_is_rejected() {
+        let mut db = setup_accounts();
+        let err = db
+            .execute("MATCH (a:Account) RETURN -a.id AS x")
+            .unwrap_err();
+        assert!(
+            matches!(err, RuzuError::BindError(_) | RuzuError::TypeError { .. }),
+            "unexpected error: {err:?}"
+        );
+    }
+}
+
+// ============================================================================
+// NULL Semantics Tests (IS [NOT] NULL, three-valued logic)
+// ============================================================================
+
+mod null_semantics_tests {
+    use ruzu::{Database, Value};
+    use tempfile::TempDir;
+    use super::{column, strings};
+
+    /// Bob has no email and Carol no age (empty CSV fields are NULL); Carol
+    /// works nowhere and Bob's `WorksAt` has no `since`.
+    fn setup_people() -> (TempDir, Database) {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let csv_path = temp_dir.path().join("people.csv");
+        std::fs::write(
+            &csv_path,
+            "name,email,age\nAlice,alice@example.com,30\nBob,,40\nCarol,carol@example.com,\n",
+        )
+        .unwrap();
+
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, email STRING, age INT64, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE NODE TABLE Company(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE WorksAt(FROM Person TO Company, since INT64, role STRING)")
+            .unwrap();
+        db.execute(&format!("COPY Person FROM '{}'", csv_path.display()))
+            .unwrap();
+        db.execute("CREATE (:Company {name: 'Acme'})").unwrap();
+        db.execute(
+            "MATCH (p:Person {name: 'Alice'}), (c:Company {name: 'Acme'}) \
+             CREATE (p)-[:WorksAt {role: 'dev', since: 2020}]->(c)",
+        )
+        .unwrap();
+        db.execute(
+            "MATCH (p:Person {name: 'Bob'}), (c:Company {name: 'Acme'}) \
+             CREATE (p)-[:WorksAt {role: 'ops'}]->(c)",
+        )
+        .unwrap();
+        (temp_dir, db)
+    }
+
+    #[test]
+    fn test_empty_csv_fields_are_null() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name, p.email, p.age ORDER BY p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.email")[1], Value::Null);
+        assert_eq!(column(&result, "p.age")[2], Value::Null);
+    }
+
+    #[test]
+    fn test_omitted_relationship_properties_are_null() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person)-[r:WorksAt]->(c:Company) RETURN p.name, r.role, r.since \
+                 ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "r.role"), strings(&["dev", "ops"]));
+        assert_eq!(column(&result, "r.since"), vec![Value::Int64(2020), Value::Null]);
+    }
+
+    #[test]
+    fn test_is_null() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.email IS NULL RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Bob"]));
+    }
+
+    #[test]
+    fn test_is_not_null() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age IS NOT NULL RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob"]));
+    }
+
+    #[test]
+    fn test_is_null_combined_with_boolean_operators() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age IS NULL OR p.age > 35 \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Bob", "Carol"]));
+
+        let result = db
+            .execute("MATCH (p:Person) WHERE NOT p.email IS NULL AND p.age < 35 RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice"]));
+    }
+
+    #[test]
+    fn test_comparison_with_null_is_unknown() {
+        let (_dir, mut db) = setup_people();
+        // Carol's age is NULL: neither `= 30`, `<> 30` nor their negations hold
+        for predicate in ["p.age = 30", "p.age <> 30", "NOT p.age = 30", "NOT p.age <> 30"] {
+            let result = db
+                .execute(&format!(
+                    "MATCH (p:Person) WHERE {predicate} AND p.name = 'Carol' RETURN p.name"
+                ))
+                .unwrap();
+            assert!(result.rows.is_empty(), "{predicate} matched Carol");
+        }
+    }
+
+    #[test]
+    fn test_unknown_and_false_is_false_under_not() {
+        let (_dir, mut db) = setup_people();
+        // For Carol: p.age > 100 is unknown, p.name = 'Bob' is false, so the
+        // conjunction is false and its negation true
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE NOT (p.age > 100 AND p.name = 'Bob') \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob", "Carol"]));
+    }
+
+    #[test]
+    fn test_is_null_on_relationship_property() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person)-[r:WorksAt]->(c:Company) WHERE r.since IS NULL \
+                 RETURN p.name, r.role",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Bob"]));
+        assert_eq!(column(&result, "r.role"), strings(&["ops"]));
+    }
+
+    #[test]
+    fn test_is_null_on_optional_match_variable() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 WITH p, c WHERE c IS NULL RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol"]));
+
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 WITH p, c WHERE c IS NOT NULL RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob"]));
+    }
+
+    #[test]
+    fn test_aggregates_skip_nulls() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN COUNT(*), COUNT(p.email), COUNT(p.age), SUM(p.age), \
+                 AVG(p.age), MIN(p.age), MAX(p.age)",
+            )
+            .unwrap();
+        let row = &result.rows[0];
+        assert_eq!(row.get("COUNT(*)"), Some(&Value::Int64(3)));
+        assert_eq!(row.get("COUNT(p.email)"), Some(&Value::Int64(2)));
+        assert_eq!(row.get("COUNT(p.age)"), Some(&Value::Int64(2)));
+        assert_eq!(row.get("SUM(p.age)"), Some(&Value::Int64(70)));
+        assert_eq!(row.get("AVG(p.age)"), Some(&Value::Float64(35.0)));
+        assert_eq!(row.get("MIN(p.age)"), Some(&Value::Int64(30)));
+        assert_eq!(row.get("MAX(p.age)"), Some(&Value::Int64(40)));
+    }
+
+    #[test]
+    fn test_count_skips_null_extended_rows() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                 WITH p.name AS name, COUNT(c.name) AS jobs, COUNT(*) AS rows \
+                 RETURN name, jobs, rows ORDER BY name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "name"), strings(&["Alice", "Bob", "Carol"]));
+        assert_eq!(
+            column(&result, "jobs"),
+            vec![Value::Int64(1), Value::Int64(1), Value::Int64(0)]
+        );
+        assert_eq!(
+            column(&result, "rows"),
+            vec![Value::Int64(1), Value::Int64(1), Value::Int64(1)]
+        );
+    }
+
+    #[test]
+    fn test_aggregates_over_only_nulls() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age IS NULL \
+                 RETURN COUNT(p.age), SUM(p.age), AVG(p.age), MIN(p.age), MAX(p.age)",
+            )
+            .unwrap();
+        let row = &result.rows[0];
+        assert_eq!(row.get("COUNT(p.age)"), Some(&Value::Int64(0)));
+        assert_eq!(row.get("AVG(p.age)"), Some(&Value::Null));
+        assert_eq!(row.get("MIN(p.age)"), Some(&Value::Null));
+        assert_eq!(row.get("MAX(p.age)"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_order_by_puts_nulls_last_ascending_and_first_descending() {
+        let (_dir, mut db) = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name, p.age ORDER BY p.age")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Alice", "Bob", "Carol"]));
+
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name, p.age ORDER BY p.age DESC")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Carol", "Bob", "Alice"]));
+
+        let result = db
+            .execute("MATCH (p:Person) WITH p.age AS age, p.name AS name RETURN name ORDER BY age")
+            .unwrap();
+        assert_eq!(column(&result, "name"), strings(&["Alice", "Bob", "Carol"]));
+    }
+}
+
+// ============================================================================
+// String predicates: STARTS WITH, ENDS WITH, CONTAINS and =~
+// ============================================================================
+
+mod string_predicate_tests {
+    use ruzu::{Database, RuzuError, Value};
+    use tempfile::TempDir;
+    use super::{column, strings};
+
+    /// Dave has no email (an empty CSV field is NULL).
+    fn setup_people() -> (TempDir, Database) {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let csv_path = temp_dir.path().join("people.csv");
+        std::fs::write(
+            &csv_path,
+            "name,email,age\n\
+             Alice,alice@corp.com,30\n\
+             alan,alan@corp.com,25\n\
+             Bob,bob@home.org,40\n\
+             Dave,,35\n",
+        )
+        .unwrap();
+
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, email STRING, age INT64, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE NODE TABLE City(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE LivesIn(FROM Person TO City)")
+            .unwrap();
+        db.execute(&format!("COPY Person FROM '{}'", csv_path.display()))
+            .unwrap();
+        db.execute("CREATE (:City {name: 'Oslo'})").unwrap();
+        db.execute("CREATE (:City {name: 'Bergen'})").unwrap();
+        for (person, city) in [("Alice", "Oslo"), ("Bob", "Bergen"), ("alan", "Bergen")] {
+            db.execute(&format!(
+                "MATCH (p:Person {{name: '{person}'}}), (c:City {{name: '{city}'}}) \
+                 CREATE (p)-[:LivesIn]->(c)"
+            ))
+            .unwrap();
+        }
+        (temp_dir, db)
+    }
+
+    fn names(db: &mut Database, query: &str) -> Vec<Value> {
+        column(&db.execute(query).unwrap(), "p.name")
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.name STARTS WITH 'A' RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Alice"]));
+    }
+
+    #[test]
+    fn test_ends_with_skips_null() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.email ENDS WITH '@corp.com' RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "alan"]));
+    }
+
+    #[test]
+    fn test_contains() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.email CONTAINS 'home' RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Bob"]));
+    }
+
+    #[test]
+    fn test_keywords_are_case_insensitive() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.name starts  with 'B' OR p.name contains 'av' \
+             RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Bob", "Dave"]));
+    }
+
+    #[test]
+    fn test_regex_with_inline_flags() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.name =~ '(?i)al.*' RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "alan"]));
+    }
+
+    #[test]
+    fn test_regex_must_match_whole_string() {
+        let (_dir, mut db) = setup_people();
+        let found = names(&mut db, "MATCH (p:Person) WHERE p.name =~ 'li' RETURN p.name");
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_regex_is_error() {
+        let (_dir, mut db) = setup_people();
+        let result = db.execute("MATCH (p:Person) WHERE p.name =~ '(' RETURN p.name");
+        assert!(matches!(result, Err(RuzuError::ExecutionError(_))));
+    }
+
+    #[test]
+    fn test_not_string_predicate_skips_null() {
+        let (_dir, mut db) = setup_people();
+        // Dave's NULL email makes the predicate unknown either way
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE NOT p.email ENDS WITH '.com' RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Bob"]));
+    }
+
+    #[test]
+    fn test_property_operand_as_pattern() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.email STARTS WITH 'a' + 'l' RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "alan"]));
+    }
+
+    #[test]
+    fn test_string_predicate_on_relationship_pattern() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person)-[:LivesIn]->(c:City) WHERE c.name ENDS WITH 'en' \
+             RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Bob", "alan"]));
+    }
+
+    #[test]
+    fn test_string_predicate_on_integer_is_error() {
+        let (_dir, mut db) = setup_people();
+        assert!(db
+            .execute("MATCH (p:Person) WHERE p.age CONTAINS '3' RETURN p.name")
+            .is_err());
+        assert!(db
+            .execute("MATCH (p:Person) WHERE p.age + 1 STARTS WITH '3' RETURN p.name AS n")
+            .is_err());
+    }
+}
+
+// ============================================================================
+// IN list membership
+// ============================================================================
+
+mod in_list_tests {
+    use ruzu::{Database, Value};
+    use tempfile::TempDir;
+    use super::{column, strings};
+
+    /// Dave has no age (an empty CSV field is NULL); Alice and Bob live in
+    /// Oslo.
+    fn setup_people() -> (TempDir, Database) {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let csv_path = temp_dir.path().join("people.csv");
+        std::fs::write(
+            &csv_path,
+            "id,name,age\n1,Alice,30\n2,Bob,40\n3,Carol,25\n4,Dave,\n",
+        )
+        .unwrap();
+
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(id INT64, name STRING, age INT64, PRIMARY KEY(id))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE City(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE LivesIn(FROM Person TO City)")
+            .unwrap();
+        db.execute(&format!("COPY Person FROM '{}'", csv_path.display()))
+            .unwrap();
+        db.execute("CREATE (:City {name: 'Oslo'})").unwrap();
+        for id in [1, 2] {
+            db.execute(&format!(
+                "MATCH (p:Person {{id: {id}}}), (c:City {{name: 'Oslo'}}) \
+                 CREATE (p)-[:LivesIn]->(c)"
+            ))
+            .unwrap();
+        }
+        (temp_dir, db)
+    }
+
+    fn names(db: &mut Database, query: &str) -> Vec<Value> {
+        column(&db.execute(query).unwrap(), "p.name")
+    }
+
+    #[test]
+    fn test_in_string_list() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.name IN ['Alice', 'Carol', 'Zed'] \
+             RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "Carol"]));
+    }
+
+    #[test]
+    fn test_not_in_skips_null() {
+        let (_dir, mut db) = setup_people();
+        // Dave's NULL age is neither in nor out of the list
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.age NOT IN [30, 40] RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Carol"]));
+    }
+
+    #[test]
+    fn test_not_before_in() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE NOT p.age IN [30, 40] RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Carol"]));
+    }
+
+    #[test]
+    fn test_empty_list() {
+        let (_dir, mut db) = setup_people();
+        assert!(names(&mut db, "MATCH (p:Person) WHERE p.name IN [] RETURN p.name").is_empty());
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.age NOT IN [] RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "Bob", "Carol", "Dave"]));
+    }
+
+    #[test]
+    fn test_int_matches_float_element() {
+        let (_dir, mut db) = setup_people();
+        let found = names(&mut db, "MATCH (p:Person) WHERE p.age IN [25.0] RETURN p.name");
+        assert_eq!(found, strings(&["Carol"]));
+    }
+
+    #[test]
+    fn test_primary_key_lookup_keeps_list_order_and_filters() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.id IN [3, 1, 9, 3] RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Carol", "Alice"]));
+
+        // The rest of the WHERE still applies to the looked-up nodes
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.id IN [1, 2, 3] AND p.age > 28 RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "Bob"]));
+    }
+
+    #[test]
+    fn test_primary_key_lookup_skips_deleted_nodes() {
+        let (_dir, mut db) = setup_people();
+        db.execute("MATCH (p:Person) WHERE p.id = 3 DELETE p").unwrap();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WHERE p.id IN [1, 3] RETURN p.name",
+        );
+        assert_eq!(found, strings(&["Alice"]));
+    }
+
+    #[test]
+    fn test_primary_key_lookup_with_float_keys() {
+        let (_dir, mut db) = setup_people();
+        let found = names(&mut db, "MATCH (p:Person) WHERE p.id IN [2.0] RETURN p.name");
+        assert_eq!(found, strings(&["Bob"]));
+    }
+
+    #[test]
+    fn test_in_on_relationship_pattern() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person)-[:LivesIn]->(c:City) WHERE p.id IN [2, 3] RETURN p.name, c.name",
+        );
+        assert_eq!(found, strings(&["Bob"]));
+    }
+
+    #[test]
+    fn test_in_after_with() {
+        let (_dir, mut db) = setup_people();
+        let found = names(
+            &mut db,
+            "MATCH (p:Person) WITH p WHERE p.id IN [4, 1] RETURN p.name ORDER BY p.name",
+        );
+        assert_eq!(found, strings(&["Alice", "Dave"]));
+    }
+
+    #[test]
+    fn test_null_list_element() {
+        let (_dir, mut db) = setup_people();
+        // A NULL element makes a value not found in the list unknown
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name, \
+                 CASE WHEN p.age IN [30, NULL] THEN 'in' \
+                 WHEN p.age NOT IN [30, NULL] THEN 'out' ELSE 'unknown' END AS found \
+                 ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "found"), strings(&["in", "unknown", "unknown", "unknown"]));
+
+        let found = names(&mut db, "MATCH (p:Person) WHERE p.age IN [30, NULL] RETURN p.name");
+        assert_eq!(found, strings(&["Alice"]));
+        let found = names(&mut db, "MATCH (p:Person) WHERE p.age NOT IN [30, NULL] RETURN p.name");
+        assert!(found.is_empty());
+        let found = names(&mut db, "MATCH (p:Person) WHERE p.id IN [NULL, 2] RETURN p.name");
+        assert_eq!(found, strings(&["Bob"]));
+    }
+
+    #[test]
+    fn test_in_type_errors() {
+        let (_dir, mut db) = setup_people();
+        assert!(db
+            .execute("MATCH (p:Person) WHERE p.age IN ['30'] RETURN p.name AS n")
+            .is_err());
+        assert!(db
+            .execute("MATCH (p:Person) WHERE p.age IN 30 RETURN p.name")
+            .is_err());
+    }
+}
+
+// ============================================================================
+// Query Parameter Tests
+// ============================================================================
+
+mod parameter_tests {
+    use std::collections::HashMap;
+
+    use ruzu::{Database, RuzuError, Value};
+    use super::column;
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(id INT64, name STRING, age INT64, PRIMARY KEY(id))")
+            .unwrap();
+        for (id, name, age) in [(1, "Alice", 30), (2, "Bob", 40), (3, "Carol", 25)] {
+            db.execute(&format!(
+                "CREATE (:Person {{id: {id}, name: '{name}', age: {age}}})"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn params(entries: &[(&str, Value)]) -> HashMap<String, Value> {
+        entries
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_string_parameter_needs_no_escaping() {
+        let mut db = setup_people();
+        let name = Value::String("O'Brien \\ 'quoted'".into());
+        db.execute_with_params(
+            "CREATE (:Person {id: $id, name: $name, age: $age})",
+            &params(&[
+                ("id", Value::Int64(4)),
+                ("name", name.clone()),
+                ("age", Value::Int64(50)),
+            ]),
+        )
+        .unwrap();
+
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person) WHERE p.name = $name RETURN p.id",
+                &params(&[("name", name)]),
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.id"), vec![Value::Int64(4)]);
+    }
+
+    #[test]
+    fn test_parameter_is_not_parsed_as_cypher() {
+        let mut db = setup_people();
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person) WHERE p.name = $name RETURN p.name",
+                &params(&[("name", Value::String("x' OR 1 = 1 OR p.name = '".into()))]),
+            )
+            .unwrap();
+        assert!(result.rows.is_empty());
+    }
+
+    #[test]
+    fn test_parameters_in_where_and_inline_filter() {
+        let mut db = setup_people();
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person) WHERE p.age > $min AND p.age < $max RETURN p.name",
+                &params(&[("min", Value::Int64(26)), ("max", Value::Int64(35))]),
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), vec![Value::String("Alice".into())]);
+
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person {id: $id}) RETURN p.name",
+                &params(&[("id", Value::Int64(2))]),
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), vec![Value::String("Bob".into())]);
+    }
+
+    #[test]
+    fn test_list_parameter_in_predicate() {
+        let mut db = setup_people();
+        let ids = Value::List(vec![Value::Int64(3), Value::Int64(1), Value::Int64(9)]);
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person) WHERE p.id IN $ids RETURN p.name ORDER BY p.name",
+                &params(&[("ids", ids)]),
+            )
+            .unwrap();
+        assert_eq!(
+            column(&result, "p.name"),
+            vec![Value::String("Alice".into()), Value::String("Carol".into())]
+        );
+    }
+
+    #[test]
+    fn test_null_parameter_in_set() {
+        let mut db = setup_people();
+        db.execute_with_params(
+            "MATCH (p:Person) WHERE p.id = $id SET p.age = $age",
+            &params(&[("id", Value::Int64(1)), ("age", Value::Null)]),
+        )
+        .unwrap();
+
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age IS NULL RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), vec![Value::String("Alice".into())]);
+    }
+
+    #[test]
+    fn test_unwind_list_parameter() {
+        let mut db = setup_people();
+        let result = db
+            .execute_with_params(
+                "UNWIND $xs AS x RETURN x * 2 AS doubled",
+                &params(&[("xs", Value::List(vec![Value::Int64(1), Value::Int64(5)]))]),
+            )
+            .unwrap();
+        assert_eq!(
+            column(&result, "doubled"),
+            vec![Value::Int64(2), Value::Int64(10)]
+        );
+    }
+
+    #[test]
+    fn test_missing_parameter_is_error() {
+        let mut db = setup_people();
+        let err = db
+            .execute("MATCH (p:Person) WHERE p.name = $name RETURN p.id")
+            .unwrap_err();
+        assert!(err.to_string().contains("$name"), "{err}");
+
+        let err = db
+            .execute_with_params(
+                "MATCH (p:Person) WHERE p.age > $min RETURN p.id",
+                &params(&[("max", Value::Int64(1))]),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("$min"), "{err}");
+    }
+
+    #[test]
+    fn test_parameter_type_is_checked() {
+        let mut db = setup_people();
+        let result = db.execute_with_params(
+            "MATCH (p:Person) WHERE p.name STARTS WITH $prefix RETURN p.name AS n",
+            &params(&[("prefix", Value::Int64(1))]),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_map_parameter_is_unsupported() {
+        let mut db = setup_people();
+        let result = db.execute_with_params(
+            "MATCH (p:Person) WHERE p.age = $m RETURN p.name",
+            &params(&[("m", Value::Map(std::collections::BTreeMap::new()))]),
+        );
+        assert!(matches!(result, Err(RuzuError::UnsupportedOperation(_))));
+    }
+
+    #[test]
+    fn test_prepared_statement_reused() {
+        let mut db = setup_people();
+        let insert = db
+            .prepare("CREATE (:Person {id: $id, name: $name, age: $age})")
+            .unwrap();
+        assert_eq!(insert.parameters(), ["id", "name", "age"]);
+        for id in 10..20 {
+            db.execute_prepared(
+                &insert,
+                &params(&[
+                    ("id", Value::Int64(id)),
+                    ("name", Value::String(format!("person '{id}'"))),
+                    ("age", Value::Int64(id * 2)),
+                ]),
+            )
+            .unwrap();
+        }
+
+        let lookup = db
+            .prepare("MATCH (p:Person) WHERE p.id = $id RETURN p.name")
+            .unwrap();
+        for id in 10..20 {
+            let result = db
+                .execute_prepared(&lookup, &params(&[("id", Value::Int64(id))]))
+                .unwrap();
+            assert_eq!(
+                column(&result, "p.name"),
+                vec![Value::String(format!("person '{id}'"))]
+            );
+        }
+    }
+
+    #[test]
+    fn test_prepare_reports_bind_errors() {
+        let db = setup_people();
+        assert!(db
+            .prepare("MATCH (p:Person) WHERE p.salary > $min RETURN p.name AS n")
+            .is_err());
+        assert!(db.prepare("MATCH (p:Person) RETURN p.name AS n LIMIT").is_err());
+    }
+
+    #[test]
+    fn test_prepare_binds_every_statement_form() {
+        let db = setup_people();
+        for query in [
+            "MATCH (p:Person) WHERE p.nope = $a RETURN p.name",
+            "MATCH (p:Person {nope: $a}) RETURN p.name",
+            "MATCH (p:Person) WHERE p.id = $id SET p.nope = $a",
+            "MATCH (p:Person) WHERE q.id = $id DELETE p",
+            "CREATE (:Person {id: $id, nope: $a})",
+            "MERGE (p:Person {id: $id}) ON CREATE SET p.nope = $a",
+            "UNWIND $ids AS i MERGE (p:Person {nope: i})",
+        ] {
+            let err = db.prepare(query).unwrap_err();
+            assert!(matches!(err, RuzuError::BindError(_)), "{query}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_prepared_parameter_of_wrong_type_is_rejected() {
+        let mut db = setup_people();
+        let older = db
+            .prepare("MATCH (p:Person) WHERE p.age > $a RETURN p.name")
+            .unwrap();
+        let err = db
+            .execute_prepared(&older, &params(&[("a", Value::String("30".into()))]))
+            .unwrap_err();
+        assert!(err.to_string().contains("$a"), "{err}");
+
+        // Integers and floats mix, and NULL fits any type
+        let result = db
+            .execute_prepared(&older, &params(&[("a", Value::Float64(35.5))]))
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), vec![Value::String("Bob".into())]);
+        let result = db
+            .execute_prepared(&older, &params(&[("a", Value::Null)]))
+            .unwrap();
+        assert_eq!(result.row_count(), 0);
+
+        let rename = db
+            .prepare("MATCH (p:Person {id: $id}) SET p.name = $name")
+            .unwrap();
+        assert!(db
+            .execute_prepared(
+                &rename,
+                &params(&[("id", Value::Int64(1)), ("name", Value::Int64(7))]),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_skip_and_limit_parameters() {
+        let mut db = setup_people();
+        let page = db
+            .prepare("MATCH (p:Person) RETURN p.name ORDER BY p.name SKIP $offset LIMIT $n")
+            .unwrap();
+        assert_eq!(page.parameters(), ["offset", "n"]);
+        let result = db
+            .execute_prepared(
+                &page,
+                &params(&[("offset", Value::Int64(1)), ("n", Value::Int64(1))]),
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), vec![Value::String("Bob".into())]);
+
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person) WITH p ORDER BY p.age LIMIT $n RETURN p.name",
+                &params(&[("n", Value::Int64(2))]),
+            )
+            .unwrap();
+        assert_eq!(
+            column(&result, "p.name"),
+            vec![Value::String("Carol".into()), Value::String("Alice".into())]
+        );
+
+        // A count must be a non-negative integer
+        for value in [Value::Int64(-1), Value::Float64(1.0), Value::Null] {
+            let err = db
+                .execute_prepared(&page, &params(&[("offset", Value::Int64(0)), ("n", value)]))
+                .unwrap_err();
+            assert!(matches!(err, RuzuError::BindError(_)), "{err}");
+            assert!(err.to_string().contains("$n"), "{err}");
+        }
+    }
+}
+
+// ============================================================================
+// RETURN Projection Tests
+// ============================================================================
+
+mod return_projection_tests {
+    use std::collections::BTreeMap;
+
+    use ruzu::{Database, Value};
+    use super::column;
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        for (name, age, city) in [("Alice", 30, "Oslo"), ("Bob", 25, "Bergen"), ("Carol", 35, "Oslo")] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, city: '{city}'}})"
+            ))
+            .unwrap();
+        }
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) \
+             CREATE (a)-[:Knows {since: 2010}]->(b)",
+        )
+        .unwrap();
+        db
+    }
+
+    fn person(name: &str, age: i64, city: &str) -> Value {
+        Value::Map(BTreeMap::from([
+            ("age".to_string(), Value::Int64(age)),
+            ("city".to_string(), Value::String(city.into())),
+            ("name".to_string(), Value::String(name.into())),
+        ]))
+    }
+
+    #[test]
+    fn test_return_alias_names_column() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.name AS name, p.age AS age ORDER BY age")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["name", "age"]);
+        assert_eq!(
+            column(&result, "name"),
+            vec![
+                Value::String("Bob".into()),
+                Value::String("Alice".into()),
+                Value::String("Carol".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_return_node_as_map() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.name = 'Alice' RETURN p")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["p"]);
+        assert_eq!(column(&result, "p"), vec![person("Alice", 30, "Oslo")]);
+    }
+
+    #[test]
+    fn test_return_relationship_as_map() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (a:Person)-[r:Knows]->(b:Person) RETURN r AS knows, b.name")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["knows", "b.name"]);
+        assert_eq!(
+            column(&result, "knows"),
+            vec![Value::Map(BTreeMap::from([(
+                "since".to_string(),
+                Value::Int64(2010)
+            )]))]
+        );
+    }
+
+    #[test]
+    fn test_return_unmatched_optional_node_is_null() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) OPTIONAL MATCH (p)-[:Knows]->(f:Person) \
+                 RETURN p.name, f ORDER BY p.name",
+            )
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "f"),
+            vec![person("Bob", 25, "Bergen"), Value::Null, Value::Null]
+        );
+    }
+
+    #[test]
+    fn test_return_star() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (b:Person)<-[r:Knows]-(a:Person) RETURN *")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["a", "b", "r"]);
+        assert_eq!(column(&result, "a"), vec![person("Alice", 30, "Oslo")]);
+        assert_eq!(column(&result, "b"), vec![person("Bob", 25, "Bergen")]);
+
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH p.city AS city, COUNT(*) AS people \
+                 RETURN *, people * 2 AS doubled ORDER BY city",
+            )
+            .unwrap();
+        assert_eq!(result.columns, vec!["city", "people", "doubled"]);
+        assert_eq!(column(&result, "doubled"), vec![Value::Int64(2), Value::Int64(4)]);
+    }
+
+    #[test]
+    fn test_return_distinct() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN DISTINCT p.city ORDER BY p.city")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["p.city"]);
+        assert_eq!(
+            column(&result, "p.city"),
+            vec![Value::String("Bergen".into()), Value::String("Oslo".into())]
+        );
+
+        let result = db
+            .execute("MATCH (p:Person) RETURN DISTINCT p.city AS city, p.age / 100 AS bucket")
+            .unwrap();
+        assert_eq!(result.row_count(), 2);
+    }
+
+    #[test]
+    fn test_with_distinct_before_aggregate() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WITH DISTINCT p.city AS city RETURN COUNT(*) AS cities")
+            .unwrap();
+
+        assert_eq!(column(&result, "cities"), vec![Value::Int64(2)]);
+    }
+
+    #[test]
+    fn test_return_distinct_nodes_by_identity() {
+        let mut db = setup_people();
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Carol'}) \
+             CREATE (a)-[:Knows {since: 2015}]->(b)",
+        )
+        .unwrap();
+        let result = db
+            .execute("MATCH (a:Person)-[:Knows]->(b:Person) RETURN DISTINCT a")
+            .unwrap();
+
+        assert_eq!(column(&result, "a"), vec![person("Alice", 30, "Oslo")]);
+    }
+}
+
+// ============================================================================
+// Implicit Grouping Tests
+// ============================================================================
+
+mod implicit_group_by_tests {
+    use ruzu::{Database, Value};
+    use super::column;
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        for (name, age, city) in [
+            ("Alice", 30, "Oslo"),
+            ("Bob", 25, "Bergen"),
+            ("Carol", 35, "Oslo"),
+            ("Dave", 40, "Oslo"),
+        ] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, city: '{city}'}})"
+            ))
+            .unwrap();
+        }
+        for (src, dst) in [("Alice", "Bob"), ("Alice", "Carol"), ("Bob", "Carol")] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) \
+                 CREATE (a)-[:Knows {{since: 2020}}]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_count_grouped_by_property() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.city, COUNT(*) ORDER BY p.city")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["p.city", "COUNT(*)"]);
+        assert_eq!(
+            column(&result, "p.city"),
+            vec![Value::String("Bergen".into()), Value::String("Oslo".into())]
+        );
+        assert_eq!(column(&result, "COUNT(*)"), vec![Value::Int64(1), Value::Int64(3)]);
+    }
+
+    #[test]
+    fn test_grouped_aggregates_with_filter() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.age > 26 \
+                 RETURN p.city, MIN(p.age), MAX(p.age), SUM(p.age)",
+            )
+            .unwrap();
+
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(column(&result, "MIN(p.age)"), vec![Value::Int64(30)]);
+        assert_eq!(column(&result, "MAX(p.age)"), vec![Value::Int64(40)]);
+        assert_eq!(column(&result, "SUM(p.age)"), vec![Value::Int64(105)]);
+    }
+
+    #[test]
+    fn test_grouped_aggregates_order_by_and_limit() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.city, AVG(p.age) ORDER BY p.city DESC LIMIT 1")
+            .unwrap();
+
+        assert_eq!(column(&result, "p.city"), vec![Value::String("Oslo".into())]);
+        assert_eq!(column(&result, "AVG(p.age)"), vec![Value::Float64(35.0)]);
+    }
+
+    #[test]
+    fn test_order_by_grouping_item_with_aliased_aggregate() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.city, COUNT(*) AS n ORDER BY p.city")
+            .unwrap();
+        assert_eq!(result.columns, vec!["p.city", "n"]);
+        assert_eq!(
+            column(&result, "p.city"),
+            vec![Value::String("Bergen".into()), Value::String("Oslo".into())]
+        );
+        assert_eq!(column(&result, "n"), vec![Value::Int64(1), Value::Int64(3)]);
+
+        // The grouping item may itself be aliased, or be part of the sort key
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.age % 2 AS parity, COUNT(*) AS n \
+                 ORDER BY p.age % 2 DESC",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "parity"), vec![Value::Int64(1), Value::Int64(0)]);
+        assert_eq!(column(&result, "n"), vec![Value::Int64(2), Value::Int64(2)]);
+
+        // Properties that are not grouped are gone after aggregation
+        assert!(db
+            .execute("MATCH (p:Person) RETURN p.city, COUNT(*) AS n ORDER BY p.age")
+            .is_err());
+    }
+
+    #[test]
+    fn test_order_by_unaliased_aggregate() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.city, COUNT(*) ORDER BY COUNT(*) DESC")
+            .unwrap();
+        assert_eq!(result.columns, vec!["p.city", "COUNT(*)"]);
+        assert_eq!(
+            column(&result, "p.city"),
+            vec![Value::String("Oslo".into()), Value::String("Bergen".into())]
+        );
+        assert_eq!(column(&result, "COUNT(*)"), vec![Value::Int64(3), Value::Int64(1)]);
+
+        // An aliased aggregate is found by its text as well
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.city, sum(p.age) AS total ORDER BY SUM(p.age)")
+            .unwrap();
+        assert_eq!(
+            column(&result, "p.city"),
+            vec![Value::String("Bergen".into()), Value::String("Oslo".into())]
+        );
+
+        // The aggregate must be one of the projected columns
+        assert!(db
+            .execute("MATCH (p:Person) RETURN p.city, COUNT(*) ORDER BY MAX(p.age)")
+            .is_err());
+    }
+
+    #[test]
+    fn test_count_grouped_over_relationships() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (a:Person)-[:Knows]->(b:Person) \
+                 RETURN a.name, COUNT(*), MAX(b.age) ORDER BY a.name",
+            )
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "a.name"),
+            vec![Value::String("Alice".into()), Value::String("Bob".into())]
+        );
+        assert_eq!(column(&result, "COUNT(*)"), vec![Value::Int64(2), Value::Int64(1)]);
+        assert_eq!(column(&result, "MAX(b.age)"), vec![Value::Int64(35), Value::Int64(35)]);
+    }
+
+    #[test]
+    fn test_aggregate_without_grouping_over_relationships() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (a:Person)-[:Knows]->(b:Person) RETURN COUNT(*)")
+            .unwrap();
+
+        assert_eq!(column(&result, "COUNT(*)"), vec![Value::Int64(3)]);
+    }
+
+    #[test]
+    fn test_aggregate_over_no_rows() {
+        let mut db = setup_people();
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age > 100 RETURN COUNT(*)")
+            .unwrap();
+        assert_eq!(column(&result, "COUNT(*)"), vec![Value::Int64(0)]);
+
+        // With grouping items there are no groups, hence no rows
+        let result = db
+            .execute("MATCH (p:Person) WHERE p.age > 100 RETURN p.city, COUNT(*)")
+            .unwrap();
+        assert_eq!(result.row_count(), 0);
+    }
+}
+
+// ============================================================================
+// Distinct and Statistical Aggregate Tests
+// ============================================================================
+
+mod statistical_aggregate_tests {
+    use ruzu::{Database, Value};
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, city STRING, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        for (name, age, city) in [
+            ("Alice", 20, "Oslo"),
+            ("Bob", 30, "Bergen"),
+            ("Carol", 40, "Oslo"),
+            ("Dave", 50, "Oslo"),
+        ] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, city: '{city}'}})"
+            ))
+            .unwrap();
+        }
+        for (src, dst) in [("Alice", "Bob"), ("Alice", "Carol"), ("Bob", "Carol")] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) \
+                 CREATE (a)-[:Knows {{since: 2020}}]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn single(db: &mut Database, query: &str) -> Value {
+        let result = db.execute(query).unwrap();
+        assert_eq!(result.row_count(), 1, "{query}");
+        result.rows[0].get(&result.columns[0]).cloned().unwrap()
+    }
+
+    #[test]
+    fn test_count_distinct() {
+        let mut db = setup_people();
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN COUNT(DISTINCT p.city)"),
+            Value::Int64(2)
+        );
+        assert_eq!(
+            single(
+                &mut db,
+                "MATCH (a:Person)-[:Knows]->(b:Person) RETURN count(DISTINCT b.name) AS targets"
+            ),
+            Value::Int64(2)
+        );
+    }
+
+    #[test]
+    fn test_collect_returns_list() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH p ORDER BY p.age \
+                 RETURN p.city AS city, collect(p.name) AS names ORDER BY city",
+            )
+            .unwrap();
+
+        let names: Vec<Value> = result.rows.iter().map(|row| row.get("names").unwrap().clone()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Value::List(vec![Value::String("Bob".into())]),
+                Value::List(vec![
+                    Value::String("Alice".into()),
+                    Value::String("Carol".into()),
+                    Value::String("Dave".into())
+                ]),
+            ]
+        );
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN collect(DISTINCT p.city)"),
+            Value::List(vec![Value::String("Oslo".into()), Value::String("Bergen".into())])
+        );
+    }
+
+    #[test]
+    fn test_standard_deviation() {
+        let mut db = setup_people();
+        let Value::Float64(sample) = single(&mut db, "MATCH (p:Person) RETURN stDev(p.age)") else {
+            panic!("Expected FLOAT64");
+        };
+        assert!((sample - 12.909_944_487_358_056).abs() < 1e-9);
+
+        let Value::Float64(population) = single(&mut db, "MATCH (p:Person) RETURN stDevP(p.age)")
+        else {
+            panic!("Expected FLOAT64");
+        };
+        assert!((population - 11.180_339_887_498_949).abs() < 1e-9);
+
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) WHERE p.age > 45 RETURN stDev(p.age)"),
+            Value::Float64(0.0)
+        );
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut db = setup_people();
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN percentileCont(p.age, 0.5)"),
+            Value::Float64(35.0)
+        );
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN percentileDisc(p.age, 0.5)"),
+            Value::Int64(30)
+        );
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN percentileDisc(p.age, 1)"),
+            Value::Int64(50)
+        );
+        assert!(db
+            .execute("MATCH (p:Person) RETURN percentileCont(p.age, 1.5)")
+            .is_err());
+    }
+
+    #[test]
+    fn test_aggregate_over_expression() {
+        let mut db = setup_people();
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN SUM(p.age * 2)"),
+            Value::Int64(280)
+        );
+        assert_eq!(
+            single(&mut db, "MATCH (p:Person) RETURN AVG(p.age / 10.0) AS avg"),
+            Value::Float64(3.5)
+        );
+        assert_eq!(
+            single(
+                &mut db,
+                "MATCH (a:Person)-[:Knows]->(b:Person) RETURN MAX(b.age - a.age)"
+            ),
+            Value::Int64(20)
+        );
+    }
+}
+
+// ============================================================================
+// CASE Expression Tests
+// ============================================================================
+
+mod case_expression_tests {
+    use ruzu::{Database, Value};
+    use super::{column, strings};
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, age INT64, tier INT64, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64)")
+            .unwrap();
+        for (name, age, tier) in [("Alice", 15, 1), ("Bob", 34, 2), ("Carol", 70, 3)] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', age: {age}, tier: {tier}}})"
+            ))
+            .unwrap();
+        }
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) \
+             CREATE (a)-[:Knows {since: 2010}]->(b)",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_searched_case_in_return() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name AS name, \
+                 CASE WHEN p.age < 18 THEN 'minor' WHEN p.age < 65 THEN 'adult' ELSE 'senior' END \
+                 AS band ORDER BY name",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "band"), strings(&["minor", "adult", "senior"]));
+    }
+
+    #[test]
+    fn test_simple_case_without_else_is_null() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN CASE p.tier WHEN 1 THEN 'gold' WHEN 2 THEN 'silver' END \
+                 AS medal ORDER BY p.name",
+            )
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "medal"),
+            vec![Value::String("gold".into()), Value::String("silver".into()), Value::Null]
+        );
+    }
+
+    #[test]
+    fn test_case_in_where_and_order_by() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE CASE WHEN p.tier > 1 THEN p.age ELSE 0 END > 30 \
+                 RETURN p.name ORDER BY CASE p.name WHEN 'Carol' THEN 0 ELSE 1 END, p.name",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "p.name"), strings(&["Carol", "Bob"]));
+    }
+
+    #[test]
+    fn test_case_over_relationship_match() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (a:Person)-[k:Knows]->(b:Person) \
+                 WHERE CASE WHEN k.since < 2015 THEN true ELSE false END = true \
+                 RETURN a.name, b.name",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "a.name"), strings(&["Alice"]));
+    }
+
+    #[test]
+    fn test_case_as_grouping_key() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) \
+                 RETURN CASE WHEN p.age >= 18 THEN 'adult' ELSE 'minor' END AS band, COUNT(*) AS n \
+                 ORDER BY band",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "band"), strings(&["adult", "minor"]));
+        assert_eq!(column(&result, "n"), vec![Value::Int64(2), Value::Int64(1)]);
+    }
+
+    #[test]
+    fn test_case_branch_types_must_agree() {
+        let mut db = setup_people();
+        assert!(db
+            .execute("MATCH (p:Person) RETURN CASE WHEN p.age > 18 THEN 1 ELSE 'x' END AS v")
+            .is_err());
+
+        // Integers and floats mix
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Alice' \
+                 RETURN CASE WHEN p.age > 18 THEN 1 ELSE 0.5 END AS v",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "v"), vec![Value::Float64(0.5)]);
+
+        // Every row has the CASE type, whichever branch it took
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN p.name, CASE WHEN p.age > 18 THEN 1 ELSE 2.5 END AS v \
+                 ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(
+            column(&result, "v"),
+            vec![Value::Float64(2.5), Value::Float64(1.0), Value::Float64(1.0)]
+        );
+    }
+
+    #[test]
+    fn test_boolean_case_as_where_predicate() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE CASE WHEN p.tier > 1 THEN true ELSE false END \
+                 RETURN p.name ORDER BY p.name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), strings(&["Bob", "Carol"]));
+
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE NOT CASE p.name WHEN 'Bob' THEN true ELSE false END \
+                 RETURN p.name AS name ORDER BY name",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "name"), strings(&["Alice", "Carol"]));
+    }
+
+    #[test]
+    fn test_set_property_from_case() {
+        let mut db = setup_people();
+        db.execute(
+            "MATCH (p:Person) SET p.tier = CASE WHEN p.age < 18 THEN 0 ELSE p.tier + 10 END",
+        )
+        .unwrap();
+        let result = db
+            .execute("MATCH (p:Person) RETURN p.tier ORDER BY p.tier")
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "p.tier"),
+            vec![Value::Int64(0), Value::Int64(12), Value::Int64(13)]
+        );
+    }
+}
+
+// ============================================================================
+// Scalar Function Tests
+// ============================================================================
+
+mod scalar_function_tests {
+    use ruzu::{Database, Value};
+    use super::{column, s};
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, nick STRING, age INT64, score FLOAT64, \
+             PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE (:Person {name: ' Alice ', nick: 'Al', age: 30, score: 2.5})")
+            .unwrap();
+        db.execute("CREATE (:Person {name: 'Bob', nick: '', age: -25, score: -1.25})")
+            .unwrap();
+        db.execute("MATCH (p:Person) WHERE p.name = 'Bob' SET p.nick = NULL")
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_string_functions() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN upper(trim(p.name)) AS up, lower(p.name) AS low, \
+                 size(p.name) AS len, substring(trim(p.name), 1, 3) AS sub, \
+                 replace(p.name, 'o', '0') AS rep ORDER BY len",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "up"), vec![s("BOB"), s("ALICE")]);
+        assert_eq!(column(&result, "low"), vec![s("bob"), s(" alice ")]);
+        assert_eq!(column(&result, "len"), vec![Value::Int64(3), Value::Int64(7)]);
+        assert_eq!(column(&result, "sub"), vec![s("ob"), s("lic")]);
+        assert_eq!(column(&result, "rep"), vec![s("B0b"), s(" Alice ")]);
+    }
+
+    #[test]
+    fn test_split_and_size_of_list() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Bob' \
+                 RETURN split('a,b,c', ',') AS parts, size(split('a,b,c', ',')) AS n",
+            )
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "parts"),
+            vec![Value::List(vec![s("a"), s("b"), s("c")])]
+        );
+        assert_eq!(column(&result, "n"), vec![Value::Int64(3)]);
+    }
+
+    #[test]
+    fn test_math_functions() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN abs(p.age) AS a, abs(p.score) AS b, round(p.score) AS r, \
+                 round(p.score, 1) AS r1, floor(p.score) AS f, ceil(p.score) AS c \
+                 ORDER BY p.name",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "a"), vec![Value::Int64(30), Value::Int64(25)]);
+        assert_eq!(column(&result, "b"), vec![Value::Float64(2.5), Value::Float64(1.25)]);
+        // Half away from zero
+        assert_eq!(column(&result, "r"), vec![Value::Float64(3.0), Value::Float64(-1.0)]);
+        assert_eq!(column(&result, "r1"), vec![Value::Float64(2.5), Value::Float64(-1.3)]);
+        assert_eq!(column(&result, "f"), vec![Value::Float64(2.0), Value::Float64(-2.0)]);
+        assert_eq!(column(&result, "c"), vec![Value::Float64(3.0), Value::Float64(-1.0)]);
+
+        let result = db
+            .execute("MATCH (p:Person) WHERE sqrt(abs(p.age)) > 5 RETURN p.name")
+            .unwrap();
+        assert_eq!(column(&result, "p.name"), vec![s(" Alice ")]);
+    }
+
+    #[test]
+    fn test_coalesce_and_null_arguments() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) RETURN coalesce(p.nick, trim(p.name)) AS shown, \
+                 upper(p.nick) AS loud ORDER BY p.name",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "shown"), vec![s("Al"), s("Bob")]);
+        assert_eq!(column(&result, "loud"), vec![s("AL"), Value::Null]);
+    }
+
+    #[test]
+    fn test_conversion_functions() {
+        let mut db = setup_people();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Bob' \
+                 RETURN toInteger('42') AS i, toInteger(3.9) AS t, toInteger('x') AS bad, \
+                 toFloat('1.5') AS fl, toString(p.score) AS str, toString(p.age) AS age, \
+                 toBoolean('TRUE') AS yes, toBoolean(0) AS no",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "i"), vec![Value::Int64(42)]);
+        assert_eq!(column(&result, "t"), vec![Value::Int64(3)]);
+        assert_eq!(column(&result, "bad"), vec![Value::Null]);
+        assert_eq!(column(&result, "fl"), vec![Value::Float64(1.5)]);
+        assert_eq!(column(&result, "str"), vec![s("-1.25")]);
+        assert_eq!(column(&result, "age"), vec![s("-25")]);
+        assert_eq!(column(&result, "yes"), vec![Value::Bool(true)]);
+        assert_eq!(column(&result, "no"), vec![Value::Bool(false)]);
+    }
+
+    #[test]
+    fn test_functions_in_with_and_set() {
+        let mut db = setup_people();
+        db.execute("MATCH (p:Person) SET p.nick = coalesce(p.nick, lower(p.name))")
+            .unwrap();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WITH upper(p.nick) AS nick RETURN nick ORDER BY size(nick), nick",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "nick"), vec![s("AL"), s("BOB")]);
+    }
+
+    #[test]
+    fn test_function_errors() {
+        let mut db = setup_people();
+        let err = db
+            .execute("MATCH (p:Person) RETURN nosuch(p.name)")
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown function: nosuch"));
+
+        let err = db
+            .execute("MATCH (p:Person) RETURN substring(p.name)")
+            .unwrap_err();
+        assert!(err.to_string().contains("Wrong number of arguments to substring"));
+
+        // Argument types are checked by the binder
+        assert!(db.execute("MATCH (p:Person) RETURN lower(p.age)").is_err());
+        assert!(db.execute("MATCH (p:Person) RETURN sqrt(p.name)").is_err());
+        assert!(db
+            .execute("MATCH (p:Person) RETURN coalesce(p.age, p.name)")
+            .is_err());
+
+        assert!(db
+            .execute("MATCH (p:Person) RETURN substring(p.name, -1)")
+            .is_err());
+    }
+}
+
+// ============================================================================
+// DATE, TIMESTAMP and FLOAT32 Tests
+// ============================================================================
+
+mod temporal_type_tests {
+    use std::collections::HashMap;
+
+    use ruzu::types::temporal;
+    use ruzu::{Database, DatabaseConfig, RuzuError, Value};
+    use tempfile::TempDir;
+    use super::{column, s};
+
+    fn setup_events(db: &mut Database) {
+        db.execute(
+            "CREATE NODE TABLE Event(name STRING, day DATE, at TIMESTAMP, weight FLOAT32, \
+             PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Before(FROM Event TO Event, noted DATE)")
+            .unwrap();
+        db.execute(
+            "CREATE (:Event {name: 'launch', day: date('2024-01-31'), \
+             at: timestamp('2024-01-31 12:30:00'), weight: 1.1})",
+        )
+        .unwrap();
+        db.execute(
+            "CREATE (:Event {name: 'party', day: date('2023-12-25'), \
+             at: timestamp('2023-12-25T08:00:00.5Z'), weight: 2})",
+        )
+        .unwrap();
+        db.execute(
+            "MATCH (a:Event {name: 'party'}), (b:Event {name: 'launch'}) \
+             CREATE (a)-[:Before {noted: date('2024-02-01')}]->(b)",
+        )
+        .unwrap();
+    }
+
+    fn date(s: &str) -> Value {
+        Value::Date(temporal::parse_date(s).unwrap())
+    }
+
+    #[test]
+    fn test_temporal_and_float32_columns_store_typed_values() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let result = db
+            .execute("MATCH (e:Event) RETURN e.day, e.at, e.weight ORDER BY e.name")
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "e.day"),
+            vec![date("2024-01-31"), date("2023-12-25")]
+        );
+        assert_eq!(
+            column(&result, "e.at"),
+            vec![
+                Value::Timestamp(1_706_704_200_000_000),
+                Value::Timestamp(1_703_491_200_500_000)
+            ]
+        );
+        assert_eq!(
+            column(&result, "e.weight"),
+            vec![Value::Float32(1.1), Value::Float32(2.0)]
+        );
+    }
+
+    #[test]
+    fn test_compare_and_order_temporal_values() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let result = db
+            .execute("MATCH (e:Event) WHERE e.day >= date('2024-01-01') RETURN e.name")
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("launch")]);
+
+        let result = db
+            .execute("MATCH (e:Event) WHERE e.at < timestamp('2024-01-01') RETURN e.name")
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("party")]);
+
+        let result = db
+            .execute("MATCH (e:Event) RETURN e.name, e.at ORDER BY e.at")
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("party"), s("launch")]);
+
+        let result = db
+            .execute("MATCH (a:Event)-[b:Before]->(c:Event) WHERE b.noted > c.day RETURN a.name")
+            .unwrap();
+        assert_eq!(column(&result, "a.name"), vec![s("party")]);
+    }
+
+    #[test]
+    fn test_float32_compares_with_float64_literals() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let result = db
+            .execute("MATCH (e:Event) WHERE e.weight = 1.1 RETURN e.name")
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("launch")]);
+
+        let result = db
+            .execute(
+                "MATCH (e:Event) WHERE e.weight > 1 RETURN e.name, e.weight ORDER BY e.weight DESC",
+            )
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("party"), s("launch")]);
+    }
+
+    #[test]
+    fn test_date_parts_and_difference() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let result = db
+            .execute(
+                "MATCH (e:Event) RETURN year(e.day) AS y, month(e.at) AS m, day(e.day) AS d, \
+                 e.day - date('2024-01-01') AS diff, e.day + 7 AS later ORDER BY e.day",
+            )
+            .unwrap();
+
+        assert_eq!(
+            column(&result, "y"),
+            vec![Value::Int64(2023), Value::Int64(2024)]
+        );
+        assert_eq!(
+            column(&result, "m"),
+            vec![Value::Int64(12), Value::Int64(1)]
+        );
+        assert_eq!(
+            column(&result, "d"),
+            vec![Value::Int64(25), Value::Int64(31)]
+        );
+        assert_eq!(
+            column(&result, "diff"),
+            vec![Value::Int64(-7), Value::Int64(30)]
+        );
+        assert_eq!(
+            column(&result, "later"),
+            vec![date("2024-01-01"), date("2024-02-07")]
+        );
+    }
+
+    #[test]
+    fn test_date_part_accessors() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let result = db
+            .execute(
+                "MATCH (e:Event) WITH e.day AS d, e.at AS t \
+                 RETURN d.year() AS y, t.month() AS m, d.day() AS dd, d.year() - 1 AS prev \
+                 ORDER BY d",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "y"), vec![Value::Int64(2023), Value::Int64(2024)]);
+        assert_eq!(column(&result, "m"), vec![Value::Int64(12), Value::Int64(1)]);
+        assert_eq!(column(&result, "dd"), vec![Value::Int64(25), Value::Int64(31)]);
+        assert_eq!(column(&result, "prev"), vec![Value::Int64(2022), Value::Int64(2023)]);
+
+        // Properties take accessors too, and they match the functions
+        let result = db
+            .execute("MATCH (e:Event) WHERE e.day.month() = 12 RETURN e.name")
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("party")]);
+        assert!(db.execute("MATCH (e:Event) RETURN e.name.year() AS y").is_err());
+    }
+
+    #[test]
+    fn test_temporal_conversions() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let result = db
+            .execute(
+                "MATCH (e:Event) WHERE e.name = 'party' \
+                 RETURN toString(e.day) AS ds, toString(e.at) AS ts, date(e.at) AS d, \
+                 timestamp(e.day) AS midnight, date(toString(e.day)) AS parsed",
+            )
+            .unwrap();
+
+        assert_eq!(column(&result, "ds"), vec![s("2023-12-25")]);
+        assert_eq!(column(&result, "ts"), vec![s("2023-12-25 08:00:00.500")]);
+        assert_eq!(column(&result, "d"), vec![date("2023-12-25")]);
+        assert_eq!(
+            column(&result, "midnight"),
+            vec![Value::Timestamp(1_703_462_400_000_000)]
+        );
+        assert_eq!(column(&result, "parsed"), vec![date("2023-12-25")]);
+    }
+
+    #[test]
+    fn test_invalid_temporal_values_are_rejected() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+
+        let err = db
+            .execute("MATCH (e:Event)\nWHERE e.day = date('2024-02-30') RETURN e.name")
+            .unwrap_err();
+        assert!(
+            matches!(err, RuzuError::ParseError { line: 2, col: 15, .. }),
+            "{err}"
+        );
+        assert!(db.execute("MATCH (e:Event) RETURN date(e.name)").is_err());
+        assert!(db.execute("MATCH (e:Event) RETURN year(e.name)").is_err());
+        // A string is not implicitly converted to a DATE column value
+        assert!(db
+            .execute(
+                "CREATE (:Event {name: 'x', day: '2024-01-01', at: timestamp('2024-01-01'), \
+                 weight: 1.0})"
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_copy_temporal_and_float32_columns() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("events.csv");
+        std::fs::write(
+            &csv_path,
+            "name,day,at,weight\n\
+             launch,2024-01-31,2024-01-31 12:30:00,1.5\n\
+             party,2023-12-25,1703491200000000,\n",
+        )
+        .unwrap();
+
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Event(name STRING, day DATE, at TIMESTAMP, weight FLOAT32, \
+             PRIMARY KEY(name))",
+        )
+        .unwrap();
+        let path = csv_path.to_string_lossy().replace('\\', "/");
+        db.execute(&format!("COPY Event FROM '{path}'")).unwrap();
+
+        let result = db
+            .execute("MATCH (e:Event) RETURN e.day, e.at, e.weight ORDER BY e.day")
+            .unwrap();
+        assert_eq!(
+            column(&result, "e.day"),
+            vec![date("2023-12-25"), date("2024-01-31")]
+        );
+        assert_eq!(
+            column(&result, "e.at"),
+            vec![
+                Value::Timestamp(1_703_491_200_000_000),
+                Value::Timestamp(1_706_704_200_000_000)
+            ]
+        );
+        assert_eq!(
+            column(&result, "e.weight"),
+            vec![Value::Null, Value::Float32(1.5)]
+        );
+
+        std::fs::write(&csv_path, "name,day,at,weight\nbad,31/01/2024,,\n").unwrap();
+        assert!(db.execute(&format!("COPY Event FROM '{path}'")).is_err());
+    }
+
+    #[test]
+    fn test_temporal_values_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_db");
+        {
+            let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+            setup_events(&mut db);
+        }
+
+        let mut db = Database::open(&db_path, DatabaseConfig::default()).unwrap();
+        let result = db
+            .execute("MATCH (e:Event) WHERE e.day = date('2024-01-31') RETURN e.at, e.weight")
+            .unwrap();
+        assert_eq!(
+            column(&result, "e.at"),
+            vec![Value::Timestamp(1_706_704_200_000_000)]
+        );
+        assert_eq!(column(&result, "e.weight"), vec![Value::Float32(1.1)]);
+    }
+
+    #[test]
+    fn test_temporal_parameters() {
+        let mut db = Database::new();
+        setup_events(&mut db);
+        let params = HashMap::from([("cutoff".to_string(), date("2024-01-01"))]);
+        let result = db
+            .execute_with_params(
+                "MATCH (e:Event) WHERE e.day < $cutoff RETURN e.name",
+                &params,
+            )
+            .unwrap();
+        assert_eq!(column(&result, "e.name"), vec![s("party")]);
+    }
+}
+
+// ============================================================================
+// Inline Property Filter Tests
+// ============================================================================
+
+mod inline_filter_tests {
+    use std::collections::HashMap;
+
+    use ruzu::{Database, Value};
+
+    fn setup_people() -> Database {
+        let mut db = Database::new();
+        db.execute(
+            "CREATE NODE TABLE Person(name STRING, city STRING, active BOOL, PRIMARY KEY(name))",
+        )
+        .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person, since INT64, close BOOL)")
+            .unwrap();
+        for (name, city, active) in [
+            ("Alice", "Oslo", true),
+            ("Bob", "Oslo", false),
+            ("Carol", "Rome", true),
+            ("Dave", "Oslo", true),
+        ] {
+            db.execute(&format!(
+                "CREATE (:Person {{name: '{name}', city: '{city}', active: {active}}})"
+            ))
+            .unwrap();
+        }
+        for (src, dst, since, close) in [
+            ("Alice", "Bob", 2020, true),
+            ("Alice", "Carol", 2021, true),
+            ("Alice", "Dave", 2020, false),
+            ("Bob", "Carol", 2020, true),
+        ] {
+            db.execute(&format!(
+                "MATCH (a:Person {{name: '{src}'}}), (b:Person {{name: '{dst}'}}) \
+                 CREATE (a)-[:Knows {{since: {since}, close: {close}}}]->(b)"
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn names(db: &mut Database, query: &str, column: &str) -> Vec<String> {
+        let result = db.execute(query).unwrap();
+        let mut names: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| match row.get(column) {
+                Some(Value::String(s)) => s.clone(),
+                other => panic!("Expected string in {column}, got {other:?}"),
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_multiple_node_filters() {
+        let mut db = setup_people();
+        assert_eq!(
+            names(&mut db, "MATCH (p:Person {city: 'Oslo', active: true}) RETURN p.name", "p.name"),
+            ["Alice", "Dave"]
+        );
+        assert_eq!(
+            names(
+                &mut db,
+                "MATCH (a:Person {city: 'Oslo', active: false})-[:Knows]->(b:Person {city: 'Rome', active: true}) \
+                 RETURN b.name",
+                "b.name"
+            ),
+            ["Carol"]
+        );
+        // A primary key among the filters does not skip the others
+        assert!(names(
+            &mut db,
+            "MATCH (p:Person {name: 'Bob', active: true}) RETURN p.name",
+            "p.name"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_relationship_filters() {
+        let mut db = setup_people();
+        assert_eq!(
+            names(
+                &mut db,
+                "MATCH (a:Person)-[r:Knows {since: 2020, close: true}]->(b:Person) RETURN b.name",
+                "b.name"
+            ),
+            ["Bob", "Carol"]
+        );
+        assert_eq!(
+            names(
+                &mut db,
+                "MATCH (a:Person {name: 'Alice'})-[:Knows {since: 2020}]->(b:Person) RETURN b.name",
+                "b.name"
+            ),
+            ["Bob", "Dave"]
+        );
+        assert_eq!(
+            names(
+                &mut db,
+                "MATCH (c:Person)<-[:Knows {close: true}]-(a:Person) RETURN DISTINCT c.name",
+                "c.name"
+            ),
+            ["Bob", "Carol"]
+        );
+        // Multi-hop pipeline pattern with an anonymous filtered relationship
+        assert_eq!(
+            names(
+                &mut db,
+                "MATCH (a:Person)-[:Knows {since: 2020}]->(b:Person)-[:Knows {since: 2020}]->(c:Person) \
+                 RETURN c.name AS n",
+                "n"
+            ),
+            ["Carol"]
+        );
+    }
+
+    #[test]
+    fn test_filters_in_updating_match() {
+        let mut db = setup_people();
+        db.execute("MATCH (a:Person)-[r:Knows {since: 2020, close: false}]->(b:Person) SET r.since = 2019")
+            .unwrap();
+        assert_eq!(
+            names(
+                &mut db,
+                "MATCH (a:Person)-[r:Knows {since: 2019}]->(b:Person) RETURN b.name",
+                "b.name"
+            ),
+            ["Dave"]
+        );
+
+        db.execute("MATCH (p:Person {city: 'Oslo', active: true}) DETACH DELETE p")
+            .unwrap();
+        assert_eq!(
+            names(&mut db, "MATCH (p:Person) RETURN p.name", "p.name"),
+            ["Bob", "Carol"]
+        );
+    }
+
+    #[test]
+    fn test_composite_primary_key_filter() {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Flat(city STRING, num INT64, rent FLOAT64, PRIMARY KEY(city, num))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Near(FROM Flat TO Flat)").unwrap();
+        for (city, num, rent) in [("Oslo", 1, 900.0), ("Oslo", 2, 1200.0), ("Rome", 1, 700.0)] {
+            db.execute(&format!(
+                "CREATE (:Flat {{city: '{city}', num: {num}, rent: {rent:.1}}})"
+            ))
+            .unwrap();
+        }
+
+        let result = db
+            .execute("MATCH (f:Flat {num: 2, city: 'Oslo'}) RETURN f.rent")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].get("f.rent"), Some(&Value::Float64(1200.0)));
+
+        // A partial key scans
+        let result = db.execute("MATCH (f:Flat {num: 1}) RETURN f.city").unwrap();
+        assert_eq!(result.rows.len(), 2);
+
+        db.execute(
+            "MATCH (a:Flat {city: 'Oslo', num: 1}), (b:Flat {city: 'Rome', num: 1}) \
+             CREATE (a)-[:Near]->(b)",
+        )
+        .unwrap();
+        let result = db
+            .execute("MATCH (a:Flat {city: 'Oslo', num: 1})-[:Near]->(b:Flat) RETURN b.rent")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].get("b.rent"), Some(&Value::Float64(700.0)));
+    }
+
+    #[test]
+    fn test_filter_parameters() {
+        let mut db = setup_people();
+        let params = HashMap::from([
+            ("city".to_string(), Value::String("Oslo".into())),
+            ("since".to_string(), Value::Int64(2021)),
+        ]);
+        let result = db
+            .execute_with_params(
+                "MATCH (a:Person {city: $city, active: true})-[:Knows {since: $since}]->(b:Person) \
+                 RETURN b.name",
+                &params,
+            )
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(
+            result.rows[0].get("b.name"),
+            Some(&Value::String("Carol".into()))
+        );
+    }
+}
+
+// ============================================================================
+// Comma-Separated and Repeated MATCH Join Tests
+// ============================================================================
+
+mod match_join_tests {
+    use ruzu::{Database, Value};
+
+    fn setup_cities() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, city STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE City(name STRING, population INT64, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Knows(FROM Person TO Person)").unwrap();
+        for (name, city) in [("Alice", "Oslo"), ("Bob", "Rome"), ("Carol", "Oslo"), ("Dave", "")] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}', city: '{city}'}})"))
+                .unwrap();
+        }
+        // Dave's city is unknown
+        db.execute("MATCH (p:Person {name: 'Dave'}) SET p.city = NULL").unwrap();
+        for (name, population) in [("Oslo", 700), ("Rome", 2800), ("Paris", 2100)] {
+            db.execute(&format!(
+                "CREATE (:City {{name: '{name}', population: {population}}})"
+            ))
+            .unwrap();
+        }
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) CREATE (a)-[:Knows]->(b)",
+        )
+        .unwrap();
+        db
+    }
+
+    fn pairs(db: &mut Database, query: &str, left: &str, right: &str) -> Vec<(String, Value)> {
+        let result = db.execute(query).unwrap();
+        let mut pairs: Vec<(String, Value)> = result
+            .rows
+            .iter()
+            .map(|row| match row.get(left) {
+                Some(Value::String(s)) => (s.clone(), row.get(right).cloned().unwrap()),
+                other => panic!("Expected string in {left}, got {other:?}"),
+            })
+            .collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        pairs
+    }
+
+    #[test]
+    fn test_comma_patterns_join_on_where_equality() {
+        let mut db = setup_cities();
+        let expected = [
+            ("Alice".to_string(), Value::Int64(700)),
+            ("Bob".to_string(), Value::Int64(2800)),
+            ("Carol".to_string(), Value::Int64(700)),
+        ];
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (p:Person), (c:City) WHERE p.city = c.name RETURN p.name, c.population",
+                "p.name",
+                "c.population",
+            ),
+            expected
+        );
+        // Either side of the equality may come first
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (c:City), (p:Person) WHERE c.name = p.city RETURN p.name, c.population",
+                "p.name",
+                "c.population",
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_join_on_large_adjacent_ids() {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Account(id INT64, PRIMARY KEY(id))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Payment(name STRING, account INT64, PRIMARY KEY(name))")
+            .unwrap();
+        // Above 2^24, where FLOAT32 can no longer tell adjacent integers apart
+        let base = (1_i64 << 24) + 1;
+        for id in [base, base + 1] {
+            db.execute(&format!("CREATE (:Account {{id: {id}}})")).unwrap();
+        }
+        for (name, account) in [("first", base), ("second", base + 1), ("third", base + 1)] {
+            db.execute(&format!(
+                "CREATE (:Payment {{name: '{name}', account: {account}}})"
+            ))
+            .unwrap();
+        }
+
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (p:Payment), (a:Account) WHERE p.account = a.id RETURN p.name, a.id",
+                "p.name",
+                "a.id",
+            ),
+            [
+                ("first".to_string(), Value::Int64(base)),
+                ("second".to_string(), Value::Int64(base + 1)),
+                ("third".to_string(), Value::Int64(base + 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_match_joins_with_extra_predicates() {
+        let mut db = setup_cities();
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (p:Person) MATCH (c:City) WHERE c.name = p.city AND c.population > 1000 \
+                 RETURN p.name, c.name",
+                "p.name",
+                "c.name",
+            ),
+            [("Bob".to_string(), Value::String("Rome".into()))]
+        );
+    }
+
+    #[test]
+    fn test_comma_patterns_without_predicate_are_cartesian_product() {
+        let mut db = setup_cities();
+        let result = db
+            .execute("MATCH (p:Person), (c:City) RETURN COUNT(*) AS n")
+            .unwrap();
+        assert_eq!(result.rows[0].get("n"), Some(&Value::Int64(12)));
+
+        let result = db
+            .execute("MATCH (p:Person) MATCH (c:City) WHERE p.name < c.name RETURN COUNT(*) AS n")
+            .unwrap();
+        // Every name but Paris and Rome sorts before each city name
+        assert_eq!(result.rows[0].get("n"), Some(&Value::Int64(12)));
+    }
+
+    #[test]
+    fn test_comma_patterns_share_variables() {
+        let mut db = setup_cities();
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (a:Person)-[:Knows]->(b:Person), (c:City), (b)-[:Knows]->(d:Person) \
+                 WHERE a.city = c.name RETURN a.name, d.name",
+                "a.name",
+                "d.name",
+            ),
+            []
+        );
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (a:Person)-[:Knows]->(b:Person), (c:City), (d:City) \
+                 WHERE a.city = c.name AND b.city = d.name RETURN a.name, d.population",
+                "a.name",
+                "d.population",
+            ),
+            [("Alice".to_string(), Value::Int64(2800))]
+        );
+    }
+
+    #[test]
+    fn test_optional_match_of_comma_patterns() {
+        let mut db = setup_cities();
+        assert_eq!(
+            pairs(
+                &mut db,
+                "MATCH (p:Person) OPTIONAL MATCH (c:City), (d:City) \
+                 WHERE p.city = c.name AND d.population > c.population \
+                 RETURN p.name, d.name ORDER BY d.name",
+                "p.name",
+                "d.name",
+            ),
+            [
+                ("Alice".to_string(), Value::String("Paris".into())),
+                ("Alice".to_string(), Value::String("Rome".into())),
+                ("Bob".to_string(), Value::Null),
+                ("Carol".to_string(), Value::String("Paris".into())),
+                ("Carol".to_string(), Value::String("Rome".into())),
+                ("Dave".to_string(), Value::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_join_keys_compare_like_equality() {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Item(id INT64, price INT64, PRIMARY KEY(id))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Offer(id INT64, amount FLOAT64, PRIMARY KEY(id))")
+            .unwrap();
+        db.execute("CREATE (:Item {id: 1, price: 10})").unwrap();
+        db.execute("CREATE (:Item {id: 2, price: 0})").unwrap();
+        db.execute("MATCH (i:Item {id: 2}) SET i.price = NULL").unwrap();
+        db.execute("CREATE (:Offer {id: 1, amount: 10.0})").unwrap();
+        db.execute("CREATE (:Offer {id: 2, amount: 10.5})").unwrap();
+        db.execute("CREATE (:Offer {id: 3, amount: 0.0})").unwrap();
+        db.execute("MATCH (o:Offer {id: 3}) SET o.amount = NULL").unwrap();
+
+        // INT64 and FLOAT64 keys match when equal; NULL keys never match
+        let result = db
+            .execute("MATCH (i:Item), (o:Offer) WHERE i.price = o.amount RETURN i.id, o.id")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].get("i.id"), Some(&Value::Int64(1)));
+        assert_eq!(result.rows[0].get("o.id"), Some(&Value::Int64(1)));
+    }
+}
+
+// ============================================================================
+// EXISTS Subquery and Pattern Predicate Tests
+// ============================================================================
+
+mod exists_subquery_tests {
+    use std::collections::HashMap;
+
+    use ruzu::{Database, QueryResult, Value};
+
+    fn setup_owners() -> Database {
+        let mut db = Database::new();
+        db.execute("CREATE NODE TABLE Person(name STRING, PRIMARY KEY(name))")
+            .unwrap();
+        db.execute("CREATE NODE TABLE Car(id INT64, year INT64, PRIMARY KEY(id))")
+            .unwrap();
+        db.execute("CREATE REL TABLE Owns(FROM Person TO Car)").unwrap();
+        db.execute("CREATE REL TABLE Blocked(FROM Person TO Person)").unwrap();
+        for name in ["Alice", "Bob", "Carol", "Dave"] {
+            db.execute(&format!("CREATE (:Person {{name: '{name}'}})"))
+                .unwrap();
+        }
+        for (id, year) in [(1, 2019), (2, 2022), (3, 2023)] {
+            db.execute(&format!("CREATE (:Car {{id: {id}, year: {year}}})"))
+                .unwrap();
+        }
+        for (name, id) in [("Alice", 1), ("Alice", 2), ("Bob", 3)] {
+            db.execute(&format!(
+                "MATCH (p:Person {{name: '{name}'}}), (c:Car {{id: {id}}}) CREATE (p)-[:Owns]->(c)"
+            ))
+            .unwrap();
+        }
+        db.execute(
+            "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Carol'}) \
+             CREATE (a)-[:Blocked]->(b)",
+        )
+        .unwrap();
+        db
+    }
+
+    fn names(result: &QueryResult, column: &str) -> Vec<String> {
+        let mut names: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| match row.get(column) {
+                Some(Value::String(s)) => s.clone(),
+                other => panic!("Expected string in {column}, got {other:?}"),
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_exists_subquery() {
+        let mut db = setup_owners();
+        let result = db
+            .execute("MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:Owns]->(:Car) } RETURN p.name")
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Alice", "Bob"]);
+
+        // Each person is returned once, however many matches the subquery has
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:Owns]->(c:Car) WHERE c.year > 2020 } \
+                 RETURN p.name, p.name AS again",
+            )
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Alice", "Bob"]);
+
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:Owns]->(c:Car) WHERE c.year < 2020 } \
+                 RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Alice"]);
+    }
+
+    #[test]
+    fn test_not_pattern_predicate() {
+        let mut db = setup_owners();
+        let result = db
+            .execute("MATCH (p:Person) WHERE NOT (p)-[:Blocked]->(:Person) RETURN p.name")
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Bob", "Carol", "Dave"]);
+
+        // The pattern is expanded from whichever end is bound
+        let result = db
+            .execute("MATCH (p:Person) WHERE (:Person)-[:Blocked]->(p) RETURN p.name")
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Carol"]);
+
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE NOT EXISTS { MATCH (p)-[:Owns]->(:Car) } \
+                 AND NOT (p)<-[:Blocked]-(:Person) RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Dave"]);
+    }
+
+    #[test]
+    fn test_pattern_predicate_between_bound_nodes() {
+        let mut db = setup_owners();
+        let result = db
+            .execute(
+                "MATCH (p:Person), (c:Car) WHERE c.year > 2020 AND NOT (p)-[:Owns]->(c) \
+                 RETURN p.name, c.id",
+            )
+            .unwrap();
+        // Alice has car 2 (her second edge) and Bob car 3, out of the two newer cars
+        assert_eq!(result.rows.len(), 6);
+    }
+
+    #[test]
+    fn test_subquery_in_disjunction_and_nested() {
+        let mut db = setup_owners();
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE p.name = 'Dave' OR (p)-[:Blocked]->(:Person) \
+                 RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Alice", "Dave"]);
+
+        // People owning a car nobody else owns, with the MATCH keyword left out
+        let result = db
+            .execute(
+                "MATCH (p:Person) WHERE EXISTS { (p)-[:Owns]->(c:Car) WHERE c.year > 2022 AND \
+                 NOT EXISTS { MATCH (c)<-[:Owns]-(:Person {name: 'Alice'}) } } RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Bob"]);
+    }
+
+    #[test]
+    fn test_subquery_with_parameters() {
+        let mut db = setup_owners();
+        let params = HashMap::from([("year".to_string(), Value::Int64(2022))]);
+        let result = db
+            .execute_with_params(
+                "MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:Owns]->(c:Car) WHERE c.year > $year } \
+                 RETURN p.name",
+                &params,
+            )
+            .unwrap();
+        assert_eq!(names(&result, "p.name"), ["Bob"]);
+    }
+
+    #[test]
+    fn test_delete_and_set_with_pattern_predicate() {
+        let mut db = setup_owners();
+        db.execute(
+            "MATCH (p:Person) WHERE NOT (p)-[:Owns]->(:Car) AND NOT (p)<-[:Blocked]-(:Person) \
+             DELETE p",
+        )
+        .unwrap();
+        let result = db.execute("MATCH (p:Person) RETURN p.name").unwrap();
+        assert_eq!(names(&result, "p.name"), ["Alice", "Bob", "Carol"]);
+
+        db.execute("MATCH (c:Car) WHERE (:Person {name: 'Alice'})-[:Owns]->(c) SET c.year = 2000")
+            .unwrap();
+        let result = db
+            .execute("MATCH (c:Car) WHERE c.year = 2000 RETURN COUNT(*) AS n")
+            .unwrap();
+        assert_eq!(result.rows[0].get("n"), Some(&Value::Int64(2)));
+    }
+
+    #[test]
+    fn test_subquery_outside_match_where_is_rejected() {
+        let mut db = setup_owners();
+        let err = db
+            .execute(
+                "MATCH (p:Person) WITH p WHERE EXISTS { MATCH (p)-[:Owns]->(:Car) } RETURN p.name",
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("only allowed in the WHERE clause of a MATCH"));
+
+        let err = db
+            .execute("MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:Owns]->() } RETURN p.name")
+            .unwrap_err();
+        assert!(err.to_string().contains("needs a label"));
+    }
+}
@@ -7,11 +7,15 @@
 
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, BooleanArray, Int64Array, StringArray};
+use arrow::array::{
+    ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int64Array, StringArray,
+};
 use arrow::datatypes::{DataType as ArrowDataType, Field, Schema};
 use arrow::record_batch::RecordBatch;
 
-use ruzu::binder::{AggregateFunction, BoundExpression, ComparisonOp};
+use ruzu::binder::{
+    AggregateFunction, ArithmeticOp, BoundExpression, ComparisonOp, ScalarFunction,
+};
 use ruzu::executor::vectorized::{SelectionVector, VectorizedBatch, VectorizedEvaluator};
 use ruzu::types::{DataType, Value};
 
@@ -163,6 +167,281 @@ fn test_evaluate_comparison_expression() {
     assert!(!bool_array.value(4)); // 28 > 30 = false
 }
 
+/// Evaluates `p.name <op> '<pattern>'` over the test batch.
+fn evaluate_name_predicate(op: ComparisonOp, pattern: &str) -> Vec<bool> {
+    let vbatch = VectorizedBatch::new(create_test_batch());
+    let left =
+        BoundExpression::property_access("p".to_string(), "name".to_string(), DataType::String);
+    let right = BoundExpression::literal(Value::String(pattern.to_string()));
+    let predicate = BoundExpression::comparison(left, op, right);
+
+    let result = VectorizedEvaluator::evaluate(&predicate, &vbatch).unwrap();
+    let bool_array = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+    bool_array.iter().map(|b| b.unwrap()).collect()
+}
+
+#[test]
+fn test_evaluate_string_predicates() {
+    // Contract: STARTS WITH, ENDS WITH and CONTAINS use the Arrow string kernels
+    assert_eq!(
+        evaluate_name_predicate(ComparisonOp::StartsWith, "Ch"),
+        vec![false, false, true, false, false]
+    );
+    assert_eq!(
+        evaluate_name_predicate(ComparisonOp::EndsWith, "e"),
+        vec![true, false, true, false, true]
+    );
+    assert_eq!(
+        evaluate_name_predicate(ComparisonOp::Contains, "li"),
+        vec![true, false, true, false, false]
+    );
+}
+
+#[test]
+fn test_evaluate_regex_match_is_anchored() {
+    // Contract: =~ matches the whole string, not a substring
+    assert_eq!(
+        evaluate_name_predicate(ComparisonOp::RegexMatch, "li"),
+        vec![false; 5]
+    );
+    assert_eq!(
+        evaluate_name_predicate(ComparisonOp::RegexMatch, "(?i)[a-d].*"),
+        vec![true, true, true, true, false]
+    );
+}
+
+#[test]
+fn test_string_predicate_on_integers_returns_error() {
+    // Contract: string predicates require string operands
+    let vbatch = VectorizedBatch::new(create_test_batch());
+    let left =
+        BoundExpression::property_access("p".to_string(), "age".to_string(), DataType::Int64);
+    let right = BoundExpression::literal(Value::Int64(3));
+    let predicate = BoundExpression::comparison(left, ComparisonOp::Contains, right);
+
+    assert!(VectorizedEvaluator::evaluate(&predicate, &vbatch).is_err());
+}
+
+/// Evaluates `p.age IN <items>` (or `NOT IN`) over the test batch.
+fn evaluate_age_in(items: Vec<Value>, negated: bool) -> Vec<Option<bool>> {
+    let vbatch = VectorizedBatch::new(create_test_batch());
+    let age =
+        BoundExpression::property_access("p".to_string(), "age".to_string(), DataType::Int64);
+    let list = BoundExpression::literal(Value::List(items));
+    let predicate = BoundExpression::in_list(age, list, negated);
+
+    let result = VectorizedEvaluator::evaluate(&predicate, &vbatch).unwrap();
+    let bool_array = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+    bool_array.iter().collect()
+}
+
+#[test]
+fn test_evaluate_in_list() {
+    // Contract: IN tests membership in a literal list; NOT IN negates it
+    let list = || vec![Value::Int64(30), Value::Int64(40)];
+    assert_eq!(
+        evaluate_age_in(list(), false),
+        vec![Some(false), Some(true), Some(false), Some(true), Some(false)]
+    );
+    assert_eq!(
+        evaluate_age_in(list(), true),
+        vec![Some(true), Some(false), Some(true), Some(false), Some(true)]
+    );
+}
+
+#[test]
+fn test_evaluate_in_list_with_null_element() {
+    // Contract: a NULL element makes non-matches unknown rather than false
+    assert_eq!(
+        evaluate_age_in(vec![Value::Int64(25), Value::Null], false),
+        vec![Some(true), None, None, None, None]
+    );
+}
+
+#[test]
+fn test_evaluate_case_expression() {
+    // Contract: each row takes the result of the first condition that holds,
+    // else the ELSE result or NULL; results are cast to the CASE type
+    let vbatch = VectorizedBatch::new(create_test_batch());
+    let age_below = |limit: i64| {
+        BoundExpression::comparison(
+            BoundExpression::property_access("p".to_string(), "age".to_string(), DataType::Int64),
+            ComparisonOp::Lt,
+            BoundExpression::literal(Value::Int64(limit)),
+        )
+    };
+
+    let band = BoundExpression::Case {
+        branches: vec![
+            (age_below(30), BoundExpression::literal(Value::String("young".into()))),
+            (age_below(36), BoundExpression::literal(Value::String("mid".into()))),
+        ],
+        default: None,
+        data_type: DataType::String,
+    };
+    let result = VectorizedEvaluator::evaluate(&band, &vbatch).unwrap();
+    let strings = result.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(
+        strings.iter().collect::<Vec<_>>(),
+        vec![Some("young"), Some("mid"), Some("mid"), None, Some("young")]
+    );
+
+    let score = BoundExpression::Case {
+        branches: vec![(age_below(30), BoundExpression::literal(Value::Int64(1)))],
+        default: Some(Box::new(BoundExpression::literal(Value::Float64(2.5)))),
+        data_type: DataType::Float64,
+    };
+    let result = VectorizedEvaluator::evaluate(&score, &vbatch).unwrap();
+    let floats = result.as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(floats.values().to_vec(), vec![1.0, 2.5, 2.5, 2.5, 1.0]);
+}
+
+/// Creates a call to `function` with the given bound arguments.
+fn call(function: ScalarFunction, args: Vec<BoundExpression>, data_type: DataType) -> BoundExpression {
+    BoundExpression::Function {
+        function,
+        args,
+        data_type,
+    }
+}
+
+#[test]
+fn test_evaluate_string_functions() {
+    // Contract: string functions apply per row and agree with the row
+    // implementation, e.g. 0-based substring
+    let vbatch = VectorizedBatch::new(create_test_batch());
+    let name = BoundExpression::property_access("p".to_string(), "name".to_string(), DataType::String);
+
+    let upper = call(ScalarFunction::Upper, vec![name.clone()], DataType::String);
+    let result = VectorizedEvaluator::evaluate(&upper, &vbatch).unwrap();
+    let strings = result.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(strings.value(2), "CHARLIE");
+
+    let prefix = call(
+        ScalarFunction::Substring,
+        vec![
+            name.clone(),
+            BoundExpression::literal(Value::Int64(1)),
+            BoundExpression::literal(Value::Int64(2)),
+        ],
+        DataType::String,
+    );
+    let result = VectorizedEvaluator::evaluate(&prefix, &vbatch).unwrap();
+    let strings = result.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(
+        strings.iter().collect::<Vec<_>>(),
+        vec![Some("li"), Some("ob"), Some("ha"), Some("ia"), Some("ve")]
+    );
+
+    let size = call(ScalarFunction::Size, vec![name], DataType::Int64);
+    let result = VectorizedEvaluator::evaluate(&size, &vbatch).unwrap();
+    let ints = result.as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(ints.values().to_vec(), vec![5, 3, 7, 5, 3]);
+}
+
+#[test]
+fn test_evaluate_math_and_conversion_functions() {
+    // Contract: math functions compute over FLOAT64, conversions yield NULL
+    // for unparsable strings, and coalesce takes the first non-NULL value
+    let vbatch = VectorizedBatch::new(create_test_batch());
+    let age = BoundExpression::property_access("p".to_string(), "age".to_string(), DataType::Int64);
+
+    let sqrt = call(ScalarFunction::Sqrt, vec![age.clone()], DataType::Float64);
+    let result = VectorizedEvaluator::evaluate(&sqrt, &vbatch).unwrap();
+    let floats = result.as_any().downcast_ref::<Float64Array>().unwrap();
+    assert!((floats.value(0) - 5.0).abs() < f64::EPSILON);
+
+    let parsed = call(
+        ScalarFunction::ToInteger,
+        vec![BoundExpression::literal(Value::String("4.9".into()))],
+        DataType::Int64,
+    );
+    let result = VectorizedEvaluator::evaluate(&parsed, &vbatch).unwrap();
+    let ints = result.as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(ints.value(0), 4);
+
+    let invalid = call(
+        ScalarFunction::ToFloat,
+        vec![BoundExpression::literal(Value::String("abc".into()))],
+        DataType::Float64,
+    );
+    let result = VectorizedEvaluator::evaluate(&invalid, &vbatch).unwrap();
+    assert_eq!(result.null_count(), 5);
+
+    let coalesce = call(
+        ScalarFunction::Coalesce,
+        vec![BoundExpression::literal(Value::Null), age],
+        DataType::Int64,
+    );
+    let result = VectorizedEvaluator::evaluate(&coalesce, &vbatch).unwrap();
+    let ints = result.as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(ints.values().to_vec(), vec![25, 30, 35, 40, 28]);
+}
+
+#[test]
+fn test_evaluate_temporal_expressions() {
+    // Contract: dates compare and subtract per row, FLOAT32 compares with
+    // FLOAT64 at FLOAT32 precision, and date parts are extracted
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("e.day", ArrowDataType::Date32, true),
+        Field::new("e.w", ArrowDataType::Float32, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Date32Array::from(vec![Some(19_753), Some(19_716), None])) as ArrayRef,
+            Arc::new(Float32Array::from(vec![1.1, 2.0, 3.5])) as ArrayRef,
+        ],
+    )
+    .unwrap();
+    let vbatch = VectorizedBatch::new(batch);
+    let day = BoundExpression::property_access("e".to_string(), "day".to_string(), DataType::Date);
+    let new_year = BoundExpression::literal(Value::Date(19_723)); // 2024-01-01
+
+    let after = BoundExpression::comparison(day.clone(), ComparisonOp::Gt, new_year.clone());
+    let result = VectorizedEvaluator::evaluate(&after, &vbatch).unwrap();
+    let bools = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+    assert_eq!(
+        bools.iter().collect::<Vec<_>>(),
+        vec![Some(true), Some(false), None]
+    );
+
+    let diff = BoundExpression::Arithmetic {
+        left: Box::new(day.clone()),
+        op: ArithmeticOp::Sub,
+        right: Box::new(new_year),
+        data_type: DataType::Int64,
+    };
+    let result = VectorizedEvaluator::evaluate(&diff, &vbatch).unwrap();
+    let ints = result.as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(
+        ints.iter().collect::<Vec<_>>(),
+        vec![Some(30), Some(-7), None]
+    );
+
+    let month = call(ScalarFunction::Month, vec![day], DataType::Int64);
+    let result = VectorizedEvaluator::evaluate(&month, &vbatch).unwrap();
+    let ints = result.as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(
+        ints.iter().collect::<Vec<_>>(),
+        vec![Some(1), Some(12), None]
+    );
+
+    let weight =
+        BoundExpression::property_access("e".to_string(), "w".to_string(), DataType::Float32);
+    let light = BoundExpression::comparison(
+        weight,
+        ComparisonOp::Eq,
+        BoundExpression::literal(Value::Float64(1.1)),
+    );
+    let result = VectorizedEvaluator::evaluate(&light, &vbatch).unwrap();
+    let bools = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+    assert_eq!(
+        bools.iter().collect::<Vec<_>>(),
+        vec![Some(true), Some(false), Some(false)]
+    );
+}
+
 #[test]
 fn test_evaluate_logical_and() {
     // Contract: Logical AND produces correct results
@@ -5,10 +5,10 @@
 //! - Filter preserves input schema
 //! - Project produces declared output schema
 
-use ruzu::binder::{BoundExpression, BoundNode, BoundQuery, BoundReturn, QueryGraph};
+use ruzu::binder::{BoundExpression, BoundNode, BoundQuery, BoundReturn, ComparisonOp, QueryGraph};
 use ruzu::catalog::{Catalog, ColumnDef, Direction, NodeTableSchema, RelTableSchema};
 use ruzu::planner::{JoinType, LogicalPlan, Planner};
-use ruzu::types::DataType;
+use ruzu::types::{DataType, Value};
 
 /// Creates a test catalog with a Person table.
 fn create_test_catalog() -> Catalog {
@@ -328,6 +328,71 @@ fn test_hash_join_has_correct_children() {
     assert_eq!(children.len(), 2, "HashJoin should have 2 children");
 }
 
+/// Finds the first `HashJoin` in a plan, depth first.
+fn find_hash_join(plan: &LogicalPlan) -> Option<&LogicalPlan> {
+    if matches!(plan, LogicalPlan::HashJoin { .. }) {
+        return Some(plan);
+    }
+    plan.children().into_iter().find_map(find_hash_join)
+}
+
+#[test]
+fn test_planner_joins_disconnected_patterns() {
+    // Contract: MATCH (p:Person), (c:Company) WHERE p.id = c.id plans a HashJoin
+    //           of the two scans keyed on the equality; without it, on no keys
+    let catalog = create_join_test_catalog();
+    let planner = Planner::without_optimization(&catalog);
+
+    let mut query_graph = QueryGraph::new();
+    query_graph.add_node(BoundNode::new("p".to_string(), catalog.get_table("Person").unwrap()));
+    query_graph.add_node(BoundNode::new("c".to_string(), catalog.get_table("Company").unwrap()));
+    let return_clause = BoundReturn::new(vec![(
+        "p.name".to_string(),
+        BoundExpression::property_access("p".to_string(), "name".to_string(), DataType::String),
+    )]);
+
+    let mut bound_query = BoundQuery::new(query_graph, return_clause);
+    let plan = planner.plan(&bound_query).unwrap();
+    let Some(LogicalPlan::HashJoin {
+        left_keys,
+        right_keys,
+        ..
+    }) = find_hash_join(&plan)
+    else {
+        panic!("Expected a HashJoin: {plan}");
+    };
+    assert!(left_keys.is_empty() && right_keys.is_empty());
+
+    bound_query.where_clause = Some(BoundExpression::and(vec![
+        BoundExpression::comparison(
+            BoundExpression::property_access("c".to_string(), "id".to_string(), DataType::Int64),
+            ComparisonOp::Eq,
+            BoundExpression::property_access("p".to_string(), "id".to_string(), DataType::Int64),
+        ),
+        BoundExpression::comparison(
+            BoundExpression::property_access("p".to_string(), "age".to_string(), DataType::Int64),
+            ComparisonOp::Gt,
+            BoundExpression::literal(Value::Int64(30)),
+        ),
+    ]));
+    let plan = planner.plan(&bound_query).unwrap();
+    let Some(LogicalPlan::HashJoin {
+        left,
+        right,
+        left_keys,
+        right_keys,
+        join_type,
+    }) = find_hash_join(&plan)
+    else {
+        panic!("Expected a HashJoin: {plan}");
+    };
+    assert_eq!(left_keys, &["p.id"]);
+    assert_eq!(right_keys, &["c.id"]);
+    assert_eq!(*join_type, JoinType::Inner);
+    assert!(matches!(**left, LogicalPlan::NodeScan { .. }));
+    assert!(matches!(**right, LogicalPlan::NodeScan { .. }));
+}
+
 // =============================================================================
 // Phase 9: EXPLAIN Contract Tests (T110-T112)
 // =============================================================================
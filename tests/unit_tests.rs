@@ -1,7 +1,10 @@
 //! Unit tests for ruzu.
 
 use ruzu::catalog::{Catalog, ColumnDef, NodeTableSchema};
-use ruzu::parser::ast::{ComparisonOp, Literal, Statement};
+use ruzu::parser::ast::{
+    ArithmeticOp, AstAggregateFunction, Clause, ComparisonOp, Expression, Literal, MatchPattern,
+    RelDirection, ReturnItem, RowCount, Statement, ValueExpr,
+};
 use ruzu::parser::parse_query;
 use ruzu::storage::{ColumnStorage, NodeTable};
 use ruzu::types::{DataType, Value};
@@ -364,6 +367,143 @@ mod storage_tests {
         let result = table.insert(&row);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_node_table_update_in_place() {
+        let schema = create_person_schema();
+        let mut table = NodeTable::new(schema);
+
+        let mut row = HashMap::new();
+        row.insert("name".into(), Value::String("Alice".into()));
+        row.insert("age".into(), Value::Int64(25));
+        table.insert(&row).unwrap();
+
+        table.update(0, 1, Value::Int64(26)).unwrap();
+        assert_eq!(table.get(0, "age"), Some(Value::Int64(26)));
+
+        // Renaming the primary key moves the index entry
+        table.update(0, 0, Value::String("Alicia".into())).unwrap();
+        assert_eq!(table.find_by_pk("name", &Value::String("Alicia".into())), Some(0));
+        assert_eq!(table.find_by_pk("name", &Value::String("Alice".into())), None);
+    }
+
+    #[test]
+    fn test_node_table_update_rejects_invalid_values() {
+        let schema = create_person_schema();
+        let mut table = NodeTable::new(schema);
+
+        for (name, age) in [("Alice", 25), ("Bob", 30)] {
+            let mut row = HashMap::new();
+            row.insert("name".into(), Value::String(name.into()));
+            row.insert("age".into(), Value::Int64(age));
+            table.insert(&row).unwrap();
+        }
+
+        assert!(table.update(0, 1, Value::String("old".into())).is_err());
+        assert!(table.update(0, 0, Value::Null).is_err());
+        assert!(table.update(0, 0, Value::String("Bob".into())).is_err());
+        assert!(table.update(5, 1, Value::Int64(1)).is_err());
+        assert_eq!(table.get(0, "name"), Some(Value::String("Alice".into())));
+    }
+
+    #[test]
+    fn test_node_table_delete_leaves_tombstone() {
+        let schema = create_person_schema();
+        let mut table = NodeTable::new(Arc::clone(&schema));
+
+        for (name, age) in [("Alice", 25), ("Bob", 30), ("Carol", 35)] {
+            let mut row = HashMap::new();
+            row.insert("name".into(), Value::String(name.into()));
+            row.insert("age".into(), Value::Int64(age));
+            table.insert(&row).unwrap();
+        }
+
+        table.delete(1).unwrap();
+        assert!(table.is_deleted(1));
+        assert!(table.delete(1).is_err());
+        assert!(table.update(1, 1, Value::Int64(1)).is_err());
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.live_offsets().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(table.find_by_pk("name", &Value::String("Bob".into())), None);
+        assert_eq!(table.find_by_pk("name", &Value::String("Carol".into())), Some(2));
+
+        // Tombstones survive a serialization round trip
+        let restored = NodeTable::from_data(schema, table.to_data());
+        assert!(restored.is_deleted(1));
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.find_by_pk("name", &Value::String("Bob".into())), None);
+
+        // The deleted key can be inserted again at a new offset
+        let mut row = HashMap::new();
+        row.insert("name".into(), Value::String("Bob".into()));
+        row.insert("age".into(), Value::Int64(31));
+        table.insert(&row).unwrap();
+        assert_eq!(table.find_by_pk("name", &Value::String("Bob".into())), Some(3));
+    }
+
+    #[test]
+    fn test_node_table_find_by_pks() {
+        let schema = create_person_schema();
+        let mut table = NodeTable::new(schema);
+
+        for (name, age) in [("Alice", 25), ("Bob", 30), ("Carol", 35)] {
+            let mut row = HashMap::new();
+            row.insert("name".into(), Value::String(name.into()));
+            row.insert("age".into(), Value::Int64(age));
+            table.insert(&row).unwrap();
+        }
+        table.delete(1).unwrap();
+
+        let keys: Vec<Value> = ["Carol", "Bob", "Zed", "Alice", "Carol"]
+            .iter()
+            .map(|name| Value::String((*name).into()))
+            .collect();
+        assert_eq!(table.find_by_pks("name", &keys), vec![2, 0]);
+        assert!(table.find_by_pks("age", &[Value::Int64(25)]).is_empty());
+    }
+
+    #[test]
+    fn test_node_table_find_by_composite_key() {
+        let schema = Arc::new(
+            NodeTableSchema::new(
+                "Flat".into(),
+                vec![
+                    ColumnDef::new("city".into(), DataType::String).unwrap(),
+                    ColumnDef::new("num".into(), DataType::Int64).unwrap(),
+                ],
+                vec!["city".into(), "num".into()],
+            )
+            .unwrap(),
+        );
+        let mut table = NodeTable::new(schema);
+
+        for (city, num) in [("Oslo", 1), ("Oslo", 2), ("Rome", 1)] {
+            let mut row = HashMap::new();
+            row.insert("city".into(), Value::String(city.into()));
+            row.insert("num".into(), Value::Int64(num));
+            table.insert(&row).unwrap();
+        }
+        table.delete(2).unwrap();
+
+        let key = |city: &str, num| [Value::String(city.into()), Value::Int64(num)];
+        assert_eq!(table.find_by_key(&key("Oslo", 2)), Some(1));
+        assert_eq!(table.find_by_key(&key("Oslo", 3)), None);
+        assert_eq!(table.find_by_key(&key("Rome", 1)), None);
+        assert_eq!(table.find_by_key(&[Value::String("Oslo".into())]), None);
+    }
+
+    #[test]
+    fn test_node_table_rejects_null_primary_key() {
+        let schema = create_person_schema();
+        let mut table = NodeTable::new(schema);
+
+        let mut row = HashMap::new();
+        row.insert("name".into(), Value::Null);
+        row.insert("age".into(), Value::Int64(25));
+        assert!(table.insert(&row).is_err());
+        assert!(table.is_empty());
+    }
 }
 
 // =============================================================================
@@ -393,6 +533,33 @@ mod parser_tests {
         }
     }
 
+    #[test]
+    fn test_parse_temporal_and_float32_types() {
+        let query = "CREATE NODE TABLE Event(name STRING, day date, at TIMESTAMP, w FLOAT32, \
+                     PRIMARY KEY(name))";
+        let Ok(Statement::CreateNodeTable { columns, .. }) = parse_query(query) else {
+            panic!("Expected CreateNodeTable statement");
+        };
+        let types: Vec<&str> = columns.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(types, vec!["STRING", "DATE", "TIMESTAMP", "FLOAT32"]);
+
+        let query = "MATCH (e:Event) WHERE e.day > date('2024-01-31') \
+                     AND e.at < TIMESTAMP('2024-01-31 12:30:00') RETURN e.name";
+        let Ok(Statement::Match {
+            filter: Some(filter),
+            ..
+        }) = parse_query(query)
+        else {
+            panic!("Expected Match statement with a filter");
+        };
+        assert_eq!(
+            filter.to_string(),
+            "e.day > date('2024-01-31') AND e.at < timestamp('2024-01-31 12:30:00')"
+        );
+
+        assert!(parse_query("MATCH (e:Event) WHERE e.day = date('2024-13-01') RETURN e").is_err());
+    }
+
     #[test]
     fn test_parse_create_node_table_with_semicolon() {
         let query = "CREATE NODE TABLE Person(name STRING, PRIMARY KEY(name));";
@@ -446,6 +613,297 @@ mod parser_tests {
         }
     }
 
+    #[test]
+    fn test_parse_boolean_where_precedence() {
+        let query = "MATCH (p:Person) WHERE p.age > 30 AND p.city = 'Oslo' OR NOT p.active \
+                     RETURN p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        // NOT binds tighter than AND, which binds tighter than OR
+        let Some(Expression::Or(left, right)) = filter else {
+            panic!("Expected OR at the top level");
+        };
+        let Expression::And(age, city) = *left else {
+            panic!("Expected AND on the left of OR");
+        };
+        assert!(matches!(*age, Expression::Comparison { op: ComparisonOp::Gt, .. }));
+        assert!(matches!(*city, Expression::Comparison { op: ComparisonOp::Eq, .. }));
+        let Expression::Not(active) = *right else {
+            panic!("Expected NOT on the right of OR");
+        };
+        assert!(matches!(*active, Expression::Value(ValueExpr::Property { .. })));
+    }
+
+    #[test]
+    fn test_parse_boolean_where_parentheses() {
+        let query = "MATCH (p:Person) WHERE p.age > 30 AND (p.city = 'Oslo' OR p.city = 'Bergen') \
+                     RETURN p.name ORDER BY p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let Some(Expression::And(_, right)) = filter else {
+            panic!("Expected AND at the top level");
+        };
+        assert!(matches!(*right, Expression::Or(_, _)));
+    }
+
+    #[test]
+    fn test_parse_boolean_literal_predicate() {
+        let query = "MATCH (p:Person) WHERE p.age > 30 OR true RETURN p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let Some(Expression::Or(_, right)) = filter else {
+            panic!("Expected OR at the top level");
+        };
+        assert!(matches!(*right, Expression::Value(ValueExpr::Literal(Literal::Bool(true)))));
+    }
+
+    #[test]
+    fn test_parse_is_null_predicates() {
+        let query = "MATCH (p:Person) WHERE p.email IS NULL OR NOT p.age is not null RETURN p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let Some(Expression::Or(left, right)) = filter else {
+            panic!("Expected OR at the top level");
+        };
+        assert!(matches!(
+            *left,
+            Expression::IsNull { operand: ValueExpr::Property { .. }, negated: false }
+        ));
+        let Expression::Not(operand) = *right else {
+            panic!("Expected NOT on the right of OR");
+        };
+        assert!(matches!(*operand, Expression::IsNull { negated: true, .. }));
+    }
+
+    #[test]
+    fn test_parse_string_predicates() {
+        let query = "MATCH (p:Person) WHERE p.email ends  with '@corp.com' AND p.name =~ '(?i)al.*' \
+                     RETURN p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let Some(Expression::And(left, right)) = filter else {
+            panic!("Expected AND at the top level");
+        };
+        assert!(matches!(*left, Expression::Comparison { op: ComparisonOp::EndsWith, .. }));
+        assert!(matches!(*right, Expression::Comparison { op: ComparisonOp::RegexMatch, .. }));
+
+        assert_eq!(ComparisonOp::parse("STARTS WITH"), Some(ComparisonOp::StartsWith));
+        assert_eq!(ComparisonOp::parse("contains"), Some(ComparisonOp::Contains));
+    }
+
+    #[test]
+    fn test_parse_in_list_predicates() {
+        let query = "MATCH (p:Person) WHERE p.name IN ['Alice', 'Bob'] OR p.age NOT IN [1, 2] \
+                     RETURN p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let Some(Expression::Or(left, right)) = filter else {
+            panic!("Expected OR at the top level");
+        };
+        let Expression::In { operand, list, negated } = *left else {
+            panic!("Expected IN on the left of OR");
+        };
+        assert!(matches!(operand, ValueExpr::Property { ref property, .. } if property == "name"));
+        assert!(matches!(list, ValueExpr::Literal(Literal::List(ref items)) if items.len() == 2));
+        assert!(!negated);
+        assert!(matches!(*right, Expression::In { negated: true, .. }));
+    }
+
+    #[test]
+    fn test_parse_parameters() {
+        let query = "MATCH (p:Person) WHERE p.age > $min AND p.id IN $ids RETURN p.name";
+        let Ok(Statement::Match { filter, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let Some(Expression::And(left, right)) = filter else {
+            panic!("Expected AND at the top level");
+        };
+        let Expression::Comparison { right: min, .. } = *left else {
+            panic!("Expected comparison on the left of AND");
+        };
+        assert!(matches!(min, ValueExpr::Literal(Literal::Parameter(ref name)) if name == "min"));
+        let Expression::In { list, .. } = *right else {
+            panic!("Expected IN on the right of AND");
+        };
+        assert!(matches!(list, ValueExpr::Literal(Literal::Parameter(ref name)) if name == "ids"));
+    }
+
+    #[test]
+    fn test_parse_property_to_property_comparison() {
+        let query = "MATCH (a:Account)-[:Pays]->(b:Account) WHERE a.balance > b.balance * 2 \
+                     RETURN a.id";
+        let Ok(Statement::MatchRel { filter, .. }) = parse_query(query) else {
+            panic!("Expected MatchRel statement");
+        };
+
+        let Some(Expression::Comparison { left, op, right }) = filter else {
+            panic!("Expected comparison filter");
+        };
+        assert!(matches!(left, ValueExpr::Property { ref var, .. } if var == "a"));
+        assert_eq!(op, ComparisonOp::Gt);
+        let ValueExpr::Arithmetic { left, op, right } = right else {
+            panic!("Expected arithmetic on the right");
+        };
+        assert!(matches!(*left, ValueExpr::Property { ref var, .. } if var == "b"));
+        assert_eq!(op, ArithmeticOp::Mul);
+        assert!(matches!(*right, ValueExpr::Literal(Literal::Int64(2))));
+    }
+
+    #[test]
+    fn test_parse_return_expression_with_alias() {
+        let query = "MATCH (p:Product) RETURN p.name, p.price * p.qty AS total";
+        let Ok(Statement::Query { projection, .. }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+
+        assert_eq!(projection.items.len(), 2);
+        assert!(matches!(projection.items[0].item, ReturnItem::Projection { .. }));
+        assert!(matches!(
+            projection.items[1].item,
+            ReturnItem::Expression(ValueExpr::Arithmetic { op: ArithmeticOp::Mul, .. })
+        ));
+        assert_eq!(projection.items[1].alias.as_deref(), Some("total"));
+    }
+
+    #[test]
+    fn test_parse_return_distinct_and_star() {
+        let query = "MATCH (p:Person) RETURN DISTINCT p.city";
+        let Ok(Statement::Query { projection, .. }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+        assert!(projection.distinct);
+        assert!(!projection.star);
+        assert_eq!(projection.items.len(), 1);
+
+        let query = "MATCH (p:Person) WITH * RETURN *, p.age + 1 AS next";
+        let Ok(Statement::Query { clauses, projection }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+        let Clause::With(with) = &clauses[1] else {
+            panic!("Expected WITH clause");
+        };
+        assert!(with.star && with.items.is_empty());
+        assert!(projection.star && !projection.distinct);
+        assert_eq!(projection.items[0].alias.as_deref(), Some("next"));
+    }
+
+    #[test]
+    fn test_parse_distinct_and_statistical_aggregates() {
+        let query = "MATCH (p:Person) RETURN COUNT(DISTINCT p.city), collect(p.age * 2), \
+                     stDevP(p.age), percentileDisc(p.age, 0.5)";
+        let Ok(Statement::Match { projections, .. }) = parse_query(query) else {
+            panic!("Expected Match statement");
+        };
+
+        let names: Vec<String> = projections.iter().map(ReturnItem::column_name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "COUNT(DISTINCT p.city)",
+                "COLLECT(p.age * 2)",
+                "STDEVP(p.age)",
+                "PERCENTILEDISC(p.age, 0.5)"
+            ]
+        );
+        let ReturnItem::Aggregate(count) = &projections[0] else {
+            panic!("Expected aggregate");
+        };
+        assert_eq!(count.function, AstAggregateFunction::Count);
+        assert!(count.distinct);
+
+        // Percentiles need the percentile argument, other aggregates reject it
+        assert!(parse_query("MATCH (p:Person) RETURN percentileCont(p.age)").is_err());
+        assert!(parse_query("MATCH (p:Person) RETURN SUM(p.age, 1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_case_expressions() {
+        let query = "MATCH (p:Person) RETURN CASE p.tier WHEN 1 THEN 'gold' ELSE 'basic' END, \
+                     case when p.age < 18 then 'minor' when p.age IS NULL then null end \
+                     ORDER BY CASE WHEN p.vip THEN 0 ELSE 1 END";
+        let Ok(Statement::Query { projection, .. }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+
+        let names: Vec<String> = projection
+            .items
+            .iter()
+            .map(|item| item.item.column_name())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "CASE p.tier WHEN 1 THEN 'gold' ELSE 'basic' END",
+                "CASE WHEN p.age < 18 THEN 'minor' WHEN p.age IS NULL THEN NULL END"
+            ]
+        );
+        let ReturnItem::Expression(ValueExpr::Case(case)) = &projection.items[1].item else {
+            panic!("Expected CASE expression");
+        };
+        assert!(case.operand.is_none() && case.default.is_none());
+        assert_eq!(case.branches.len(), 2);
+        assert!(matches!(
+            &projection.order_by.as_deref().unwrap()[0].expr,
+            ValueExpr::Case(_)
+        ));
+
+        assert!(parse_query("MATCH (p:Person) RETURN CASE WHEN p.age < 18 THEN 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_function_calls() {
+        let query = "MATCH (p:Person) WHERE lower(p.name) = 'alice' \
+                     RETURN substring(p.name, 0, toInteger('2')), coalesce(p.nick, 'anon') AS nick";
+        let Ok(Statement::Query { projection, .. }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+
+        assert_eq!(
+            projection.items[0].item.column_name(),
+            "substring(p.name, 0, toInteger('2'))"
+        );
+        let ReturnItem::Expression(ValueExpr::Function { name, args }) = &projection.items[1].item
+        else {
+            panic!("Expected function call");
+        };
+        assert_eq!(name, "coalesce");
+        assert_eq!(args.len(), 2);
+
+        assert!(parse_query("MATCH (p:Person) RETURN lower(p.name").is_err());
+    }
+
+    #[test]
+    fn test_return_expression_column_name() {
+        let query = "MATCH (p:Product) RETURN (p.qty - 1) * 2, p.qty - (1 - 2), p.qty - 1 - 2, \
+                     'x' + p.name";
+        let Ok(Statement::Query { projection, .. }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+
+        let names: Vec<String> = projection
+            .items
+            .iter()
+            .map(|item| item.item.column_name())
+            .collect();
+        assert_eq!(
+            names,
+            ["(p.qty - 1) * 2", "p.qty - (1 - 2)", "p.qty - 1 - 2", "'x' + p.name"]
+        );
+    }
+
     #[test]
     fn test_parse_match_with_where() {
         let query = "MATCH (p:Person) WHERE p.age > 20 RETURN p.name, p.age";
@@ -459,15 +917,576 @@ mod parser_tests {
         } = result.unwrap()
         {
             assert!(filter.is_some());
-            let f = filter.unwrap();
-            assert_eq!(f.op, ComparisonOp::Gt);
-            assert!(matches!(f.value, Literal::Int64(20)));
+            let Some(Expression::Comparison { op, right, .. }) = filter else {
+                panic!("Expected comparison filter");
+            };
+            assert_eq!(op, ComparisonOp::Gt);
+            assert!(matches!(right, ValueExpr::Literal(Literal::Int64(20))));
             assert_eq!(projections.len(), 2);
         } else {
             panic!("Expected Match statement");
         }
     }
 
+    #[test]
+    fn test_parse_match_set() {
+        let query = "MATCH (p:Person) WHERE p.name = 'Alice' SET p.age = 31, p.city = 'Oslo'";
+        let result = parse_query(query);
+        assert!(result.is_ok());
+
+        if let Statement::MatchSet {
+            pattern,
+            filter,
+            items,
+        } = result.unwrap()
+        {
+            assert!(matches!(pattern, MatchPattern::Node(ref n) if n.var == "p" && n.label == "Person"));
+            assert!(filter.is_some());
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[0].property, "age");
+            assert!(matches!(items[0].value, ValueExpr::Literal(Literal::Int64(31))));
+            assert!(matches!(&items[1].value, ValueExpr::Literal(Literal::String(s)) if s == "Oslo"));
+        } else {
+            panic!("Expected MatchSet statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_match_set_relationship() {
+        let query = "MATCH (a:Person)-[r:Knows]->(b:Person) SET r.since = 2020";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchSet { pattern, items, .. }) = result {
+            assert!(matches!(
+                pattern,
+                MatchPattern::Rel { ref rel_var, ref rel_type, .. }
+                    if rel_var.as_deref() == Some("r") && rel_type == "Knows"
+            ));
+            assert_eq!(items[0].var, "r");
+        } else {
+            panic!("Expected MatchSet statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_match_delete() {
+        let query = "MATCH (p:Person) WHERE p.age > 90 DELETE p";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchDelete {
+            pattern,
+            filter,
+            vars,
+            detach,
+        }) = result
+        {
+            assert!(matches!(pattern, MatchPattern::Node(ref node) if node.label == "Person"));
+            assert!(filter.is_some());
+            assert_eq!(vars, vec!["p".to_string()]);
+            assert!(!detach);
+        } else {
+            panic!("Expected MatchDelete statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_match_detach_delete() {
+        let query = "MATCH (p:Person {name: 'Alice'}) DETACH DELETE p";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchDelete { vars, detach, .. }) = result {
+            assert_eq!(vars, vec!["p".to_string()]);
+            assert!(detach);
+        } else {
+            panic!("Expected MatchDelete statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_match_delete_relationship() {
+        let query = "MATCH (a:Person)-[r:Knows]->(b:Person) WHERE r.since < 2000 DELETE r";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchDelete { pattern, vars, .. }) = result {
+            assert!(matches!(pattern, MatchPattern::Rel { ref rel_var, .. } if rel_var.as_deref() == Some("r")));
+            assert_eq!(vars, vec!["r".to_string()]);
+        } else {
+            panic!("Expected MatchDelete statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_set_arithmetic_precedence() {
+        let query = "MATCH (p:Person) SET p.age = p.age + 2 * 3";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchSet { items, .. }) = result {
+            let ValueExpr::Arithmetic { left, op, right } = &items[0].value else {
+                panic!("Expected arithmetic expression");
+            };
+            assert_eq!(*op, ArithmeticOp::Add);
+            assert!(matches!(left.as_ref(), ValueExpr::Property { var, property } if var == "p" && property == "age"));
+            assert!(matches!(right.as_ref(), ValueExpr::Arithmetic { op: ArithmeticOp::Mul, .. }));
+        } else {
+            panic!("Expected MatchSet statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_unary_minus() {
+        let query = "MATCH (p:Person) SET p.x = 1 - -p.y";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchSet { items, .. }) = result {
+            let ValueExpr::Arithmetic { op, right, .. } = &items[0].value else {
+                panic!("Expected arithmetic expression");
+            };
+            assert_eq!(*op, ArithmeticOp::Sub);
+            let ValueExpr::Negate(operand) = right.as_ref() else {
+                panic!("Expected negation");
+            };
+            assert!(matches!(operand.as_ref(), ValueExpr::Property { var, property } if var == "p" && property == "y"));
+            assert_eq!(items[0].value.to_string(), "1 - -p.y");
+        } else {
+            panic!("Expected MatchSet statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_date_part_accessor() {
+        let query = "MATCH (e:Event) SET e.y = e.day.year() + d.month()";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchSet { items, .. }) = result {
+            let ValueExpr::Arithmetic { left, right, .. } = &items[0].value else {
+                panic!("Expected arithmetic expression");
+            };
+            let ValueExpr::Function { name, args } = left.as_ref() else {
+                panic!("Expected function call");
+            };
+            assert_eq!(name, "year");
+            assert!(matches!(&args[..], [ValueExpr::Property { var, property }] if var == "e" && property == "day"));
+            assert!(matches!(right.as_ref(), ValueExpr::Function { name, args } if name == "month" && matches!(&args[..], [ValueExpr::Variable(v)] if v == "d")));
+        } else {
+            panic!("Expected MatchSet statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_merge_node() {
+        let query = "MERGE (p:Person {name: 'Alice'}) ON CREATE SET p.age = 30 ON MATCH SET p.seen = p.seen + 1";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Merge {
+            var,
+            label,
+            properties,
+            on_create,
+            on_match,
+        }) = result
+        {
+            assert_eq!(var, "p");
+            assert_eq!(label, "Person");
+            assert_eq!(properties.len(), 1);
+            assert_eq!(on_create.len(), 1);
+            assert_eq!(on_create[0].property, "age");
+            assert_eq!(on_match.len(), 1);
+            assert!(matches!(on_match[0].value, ValueExpr::Arithmetic { op: ArithmeticOp::Add, .. }));
+        } else {
+            panic!("Expected Merge statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_match_merge_relationship() {
+        let query = "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) MERGE (a)-[r:Knows]->(b) ON CREATE SET r.since = 2020";
+        let result = parse_query(query);
+
+        if let Ok(Statement::MatchMerge {
+            rel_var,
+            rel_type,
+            src_var,
+            dst_var,
+            on_create,
+            on_match,
+            ..
+        }) = result
+        {
+            assert_eq!(rel_var.as_deref(), Some("r"));
+            assert_eq!(rel_type, "Knows");
+            assert_eq!((src_var.as_str(), dst_var.as_str()), ("a", "b"));
+            assert_eq!(on_create.len(), 1);
+            assert!(on_match.is_empty());
+        } else {
+            panic!("Expected MatchMerge statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_match_with() {
+        let query = "MATCH (p:Person)-[:Knows]->(f:Person) WITH p, COUNT(*) AS c WHERE c > 10 \
+                     ORDER BY c DESC LIMIT 5 RETURN p.name, c";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Query { clauses, projection }) = result {
+            assert_eq!(clauses.len(), 2);
+            assert!(matches!(clauses[0], Clause::Match { filter: None, .. }));
+            let Clause::With(with) = &clauses[1] else {
+                panic!("Expected WITH clause");
+            };
+            assert_eq!(with.items.len(), 2);
+            assert!(matches!(&with.items[0].item, ReturnItem::Variable(v) if v == "p"));
+            assert!(with.items[0].alias.is_none());
+            assert!(matches!(with.items[1].item, ReturnItem::Aggregate(_)));
+            assert_eq!(with.items[1].alias.as_deref(), Some("c"));
+            let Some(Expression::Comparison { left, op, .. }) = &with.filter else {
+                panic!("Expected comparison filter");
+            };
+            assert!(matches!(left, ValueExpr::Variable(v) if v == "c"));
+            assert_eq!(*op, ComparisonOp::Gt);
+            let order_by = with.order_by.as_ref().unwrap();
+            assert!(!order_by[0].ascending);
+            assert_eq!(with.limit, Some(RowCount::Count(5)));
+
+            assert_eq!(projection.items.len(), 2);
+            assert!(projection.filter.is_none());
+            assert!(projection.limit.is_none());
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_skip_and_limit_parameters() {
+        let result = parse_query("MATCH (p:Person) RETURN p.name SKIP $offset LIMIT $n");
+        if let Ok(Statement::Match { skip, limit, .. }) = result {
+            assert_eq!(skip, Some(RowCount::Parameter("offset".into())));
+            assert_eq!(limit, Some(RowCount::Parameter("n".into())));
+        } else {
+            panic!("Expected Match statement");
+        }
+
+        let result = parse_query("MATCH (p:Person) WITH p LIMIT $n RETURN p.name SKIP 2");
+        if let Ok(Statement::Query { clauses, projection }) = result {
+            let Clause::With(with) = &clauses[1] else {
+                panic!("Expected WITH clause");
+            };
+            assert_eq!(with.limit, Some(RowCount::Parameter("n".into())));
+            assert_eq!(projection.skip, Some(RowCount::Count(2)));
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_chained_with() {
+        let query = "MATCH (p:Person) WITH p.city AS city, COUNT(*) AS n WITH city WHERE city <> 'Oslo' RETURN city";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            assert_eq!(clauses.len(), 3);
+            assert!(matches!(clauses[2], Clause::With(_)));
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_optional_match() {
+        let query = "MATCH (p:Person) OPTIONAL MATCH (p)-[:WorksAt]->(c:Company) \
+                     WHERE c.size > 10 RETURN p.name, c.name";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Query { clauses, projection }) = result {
+            assert_eq!(clauses.len(), 2);
+            assert!(matches!(clauses[0], Clause::Match { optional: false, .. }));
+            let Clause::Match {
+                patterns,
+                filter,
+                optional: true,
+            } = &clauses[1]
+            else {
+                panic!("Expected OPTIONAL MATCH clause");
+            };
+            let pattern = &patterns[0];
+            assert_eq!(pattern.start.var, "p");
+            assert!(pattern.start.label.is_empty());
+            assert_eq!(pattern.hops.len(), 1);
+            assert_eq!(pattern.hops[0].node.label, "Company");
+            assert!(filter.is_some());
+            assert_eq!(projection.items.len(), 2);
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_chained_pattern() {
+        let query = "MATCH (a:Person {name: 'Alice'})-[k:Knows]->(b:Person)-[:WorksAt]->(c:Company)\
+                     -[l:LocatedIn]->(d:City {name: 'Oslo'}) RETURN a.name, d.name";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            let Clause::Match { patterns, .. } = &clauses[0] else {
+                panic!("Expected MATCH clause");
+            };
+            let pattern = &patterns[0];
+            assert_eq!(pattern.start.var, "a");
+            assert_eq!(pattern.start.property_filters.len(), 1);
+            assert_eq!(pattern.hops.len(), 3);
+
+            let rel_vars: Vec<_> = pattern.hops.iter().map(|h| h.rel_var.as_deref()).collect();
+            assert_eq!(rel_vars, [Some("k"), None, Some("l")]);
+            let rel_types: Vec<_> = pattern.hops.iter().map(|h| h.rel_type.as_str()).collect();
+            assert_eq!(rel_types, ["Knows", "WorksAt", "LocatedIn"]);
+            let labels: Vec<_> = pattern.hops.iter().map(|h| h.node.label.as_str()).collect();
+            assert_eq!(labels, ["Person", "Company", "City"]);
+
+            assert!(pattern.hops[0].node.property_filters.is_empty());
+            let (key, _) = &pattern.hops[2].node.property_filters[0];
+            assert_eq!(key, "name");
+            let vars: Vec<_> = pattern.variables().map(String::as_str).collect();
+            assert_eq!(vars, ["a", "k", "b", "c", "l", "d"]);
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_comma_separated_patterns() {
+        let query = "MATCH (p:Person)-[:LivesIn]->(c:City), (q:Person) WHERE p.age = q.age \
+                     OPTIONAL MATCH (q)-[:Knows]->(f:Person), (g:Company) RETURN p.name";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            assert_eq!(clauses.len(), 2);
+            let Clause::Match {
+                patterns,
+                filter,
+                optional: false,
+            } = &clauses[0]
+            else {
+                panic!("Expected MATCH clause");
+            };
+            assert!(filter.is_some());
+            let starts: Vec<_> = patterns.iter().map(|p| p.start.var.as_str()).collect();
+            assert_eq!(starts, ["p", "q"]);
+            assert_eq!(patterns[0].hops.len(), 1);
+            assert!(patterns[1].hops.is_empty());
+
+            let Clause::Match {
+                patterns,
+                optional: true,
+                ..
+            } = &clauses[1]
+            else {
+                panic!("Expected OPTIONAL MATCH clause");
+            };
+            assert_eq!(patterns.len(), 2);
+            assert_eq!(patterns[1].start.label, "Company");
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_exists_subquery_and_pattern_predicate() {
+        let query = "MATCH (p:Person) WHERE EXISTS { MATCH (p)-[:Owns]->(c:Car) WHERE c.year > 2020 } \
+                     AND NOT (p)-[:Blocked]->(:Person) RETURN p.name";
+        let result = parse_query(query);
+
+        // Queries with subqueries run as multi-stage queries
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            let Clause::Match {
+                filter: Some(Expression::And(left, right)),
+                ..
+            } = &clauses[0]
+            else {
+                panic!("Expected MATCH clause with a conjunction");
+            };
+            let Expression::Exists { pattern, filter } = left.as_ref() else {
+                panic!("Expected EXISTS subquery");
+            };
+            assert_eq!(pattern.start.var, "p");
+            assert_eq!(pattern.hops[0].node.var, "c");
+            assert!(filter.is_some());
+
+            let Expression::Not(negated) = right.as_ref() else {
+                panic!("Expected NOT");
+            };
+            let Expression::Exists { pattern, filter } = negated.as_ref() else {
+                panic!("Expected pattern predicate");
+            };
+            assert_eq!(pattern.hops[0].rel_type, "Blocked");
+            assert!(pattern.hops[0].node.is_anonymous());
+            assert!(filter.is_none());
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_multiple_inline_property_filters() {
+        let keys = |filters: &[(String, ValueExpr)]| {
+            filters.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>()
+        };
+
+        let query = "MATCH (p:Person {city: 'Oslo', active: true})-[r:Knows {since: 2020}]->\
+                     (f:Person {age: 30, city: 'Rome'}) RETURN p.name, f.name";
+        let Ok(Statement::MatchRel {
+            src_node,
+            rel_filters,
+            dst_node,
+            ..
+        }) = parse_query(query)
+        else {
+            panic!("Expected MatchRel statement");
+        };
+        assert_eq!(keys(&src_node.property_filters), ["city", "active"]);
+        assert_eq!(keys(&rel_filters), ["since"]);
+        assert!(matches!(rel_filters[0].1, ValueExpr::Literal(Literal::Int64(2020))));
+        assert_eq!(keys(&dst_node.property_filters), ["age", "city"]);
+
+        let query = "MATCH (p:Person {name: n, city: 'Oslo'})<-[:Knows {since: 2020, weight: $w}]-\
+                     (f:Person) RETURN f.name";
+        let Ok(Statement::Query { clauses, .. }) = parse_query(query) else {
+            panic!("Expected Query statement");
+        };
+        let Clause::Match { patterns, .. } = &clauses[0] else {
+            panic!("Expected MATCH clause");
+        };
+        let pattern = &patterns[0];
+        assert_eq!(keys(&pattern.start.property_filters), ["name", "city"]);
+        assert_eq!(keys(&pattern.hops[0].rel_filters), ["since", "weight"]);
+        assert_eq!(pattern.hops[0].direction, RelDirection::Backward);
+
+        let result = parse_query("MATCH (a:Person)-[:Knows*1..2 {since: 2020}]->(b:Person) RETURN b.name");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_relationship_directions() {
+        let direction = |query: &str| match parse_query(query) {
+            Ok(Statement::MatchRel { direction, .. }) => direction,
+            other => panic!("Expected MatchRel statement, got {other:?}"),
+        };
+        assert_eq!(
+            direction("MATCH (a:Person)-[:Follows]->(b:Person) RETURN b.name"),
+            RelDirection::Forward
+        );
+        assert_eq!(
+            direction("MATCH (a:Person)<-[:Follows]-(b:Person) RETURN b.name"),
+            RelDirection::Backward
+        );
+        assert_eq!(
+            direction("MATCH (a:Person)-[:Follows*1..2]-(b:Person) RETURN b.name"),
+            RelDirection::Both
+        );
+        assert!(parse_query("MATCH (a:Person)<-[:Follows]->(b:Person) RETURN b.name").is_err());
+
+        let result = parse_query(
+            "MATCH (a:Person)-[:WorksAt]->(c:Company)<-[w:WorksAt]-(b:Person)-[:Knows]-(d:Person) \
+             RETURN d.name",
+        );
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            let Clause::Match { patterns, .. } = &clauses[0] else {
+                panic!("Expected MATCH clause");
+            };
+            let pattern = &patterns[0];
+            let directions: Vec<_> = pattern.hops.iter().map(|h| h.direction).collect();
+            assert_eq!(
+                directions,
+                [RelDirection::Forward, RelDirection::Backward, RelDirection::Both]
+            );
+            assert_eq!(pattern.hops[1].rel_var.as_deref(), Some("w"));
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_unwind_list_literal() {
+        let query = "UNWIND ['Alice', 'Bob'] AS n MATCH (p:Person {name: n}) RETURN p.age";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            assert_eq!(clauses.len(), 2);
+            let Clause::Unwind { list, alias } = &clauses[0] else {
+                panic!("Expected UNWIND clause");
+            };
+            assert_eq!(alias, "n");
+            let ValueExpr::Literal(Literal::List(items)) = list else {
+                panic!("Expected list literal");
+            };
+            assert!(matches!(&items[..], [Literal::String(a), Literal::String(b)] if a == "Alice" && b == "Bob"));
+            let Clause::Match { patterns, .. } = &clauses[1] else {
+                panic!("Expected MATCH clause");
+            };
+            let pattern = &patterns[0];
+            assert!(pattern.hops.is_empty());
+            let (key, value) = &pattern.start.property_filters[0];
+            assert_eq!(key, "name");
+            assert!(matches!(value, ValueExpr::Variable(v) if v == "n"));
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_nested_and_empty_list_literals() {
+        let result = parse_query("UNWIND [[1, 2.5], []] AS xs RETURN xs");
+
+        if let Ok(Statement::Query { clauses, .. }) = result {
+            let Clause::Unwind {
+                list: ValueExpr::Literal(Literal::List(items)),
+                ..
+            } = &clauses[0]
+            else {
+                panic!("Expected UNWIND of a list literal");
+            };
+            assert!(matches!(&items[0], Literal::List(inner) if inner.len() == 2));
+            assert!(matches!(&items[1], Literal::List(inner) if inner.is_empty()));
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_unwind_create() {
+        let result = parse_query("UNWIND $names AS n CREATE (:Person {name: n, age: 20})");
+
+        if let Ok(Statement::UnwindWrite {
+            list, alias, node, merge, ..
+        }) = result
+        {
+            assert!(matches!(list, ValueExpr::Literal(Literal::Parameter(p)) if p == "names"));
+            assert_eq!(alias, "n");
+            assert!(!merge);
+            assert_eq!(node.label, "Person");
+            assert!(matches!(&node.property_filters[0].1, ValueExpr::Variable(v) if v == "n"));
+        } else {
+            panic!("Expected UnwindWrite statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_union() {
+        let query = "MATCH (p:Person) RETURN p.name UNION ALL MATCH (c:Company) RETURN c.name AS name";
+        let result = parse_query(query);
+
+        if let Ok(Statement::Union { queries, all }) = result {
+            assert!(all);
+            assert_eq!(queries.len(), 2);
+            assert!(matches!(queries[0].clauses[0], Clause::Match { .. }));
+            assert_eq!(queries[1].projection.items[0].alias.as_deref(), Some("name"));
+        } else {
+            panic!("Expected Union statement");
+        }
+
+        let result = parse_query("MATCH (p:Person) RETURN p.name UNION MATCH (c:Company) RETURN c.name");
+        assert!(matches!(result, Ok(Statement::Union { all: false, .. })));
+    }
+
     #[test]
     fn test_parse_match_invalid_syntax() {
         let query = "MATCH (p:Person";
@@ -1352,9 +2371,9 @@ mod progress_reporting_tests {
 // =============================================================================
 
 mod multi_page_foundation_tests {
-    use ruzu::storage::{BufferPool, DiskManager, PageRange, PAGE_SIZE};
     use ruzu::calculate_pages_needed;
     use ruzu::storage::DatabaseHeader;
+    use ruzu::storage::{BufferPool, DiskManager, PageRange, PAGE_SIZE};
     use tempfile::TempDir;
     use uuid::Uuid;
 